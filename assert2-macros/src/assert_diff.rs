@@ -0,0 +1,142 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+/// Generate the `StructuralDiff` implementation for a struct or enum.
+pub fn derive_assert_diff(input: syn::DeriveInput) -> TokenStream {
+	let syn::DeriveInput { ident, mut generics, data, .. } = input;
+
+	// Fields of a generic type are compared with `PartialEq`, since specialization does not work for generic types.
+	let type_params: Vec<_> = generics.type_params().map(|param| param.ident.clone()).collect();
+	let where_clause = generics.make_where_clause();
+	for param in type_params {
+		where_clause.predicates.push(syn::parse_quote!(#param: ::core::cmp::PartialEq + ::core::fmt::Debug));
+	}
+
+	let body = match data {
+		syn::Data::Struct(data) => {
+			let (left, right, diffs) = match diff_fields(&data.fields) {
+				Ok(x) => x,
+				Err(e) => return e.into_compile_error(),
+			};
+			quote! {
+				let Self #left = self;
+				let Self #right = other;
+				#diffs
+			}
+		},
+		syn::Data::Enum(data) => {
+			let arms = data.variants.iter().map(|variant| {
+				let name = &variant.ident;
+				let (left, right, diffs) = diff_fields(&variant.fields)?;
+				Ok(quote! {
+					(Self::#name #left, Self::#name #right) => {
+						#diffs
+					},
+				})
+			});
+			let arms = match arms.collect::<syn::Result<Vec<_>>>() {
+				Ok(x) => x,
+				Err(e) => return e.into_compile_error(),
+			};
+			quote! {
+				match (self, other) {
+					#(#arms)*
+					#[allow(unreachable_patterns)]
+					_ => diffs.push(::assert2::FieldDiff::new(path, self, other)),
+				}
+			}
+		},
+		syn::Data::Union(data) => {
+			return syn::Error::new_spanned(data.union_token, "`AssertDiff` can not be derived for unions").into_compile_error();
+		},
+	};
+
+	let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+	quote! {
+		impl #impl_generics ::assert2::StructuralDiff for #ident #type_generics #where_clause {
+			#[allow(unused_variables)]
+			fn structural_diff(&self, other: &Self, path: &str, diffs: &mut ::std::vec::Vec<::assert2::FieldDiff>) {
+				#[allow(unused_imports)]
+				use ::assert2::__assert2_impl::structural::{IsPartialEqField, IsStructuralField};
+				#body
+			}
+		}
+	}
+}
+
+/// How a field is compared, as set by the `#[assert2(...)]` attribute.
+enum Compare {
+	/// Compare the field recursively or with `PartialEq`.
+	Default,
+
+	/// Do not compare the field at all.
+	Skip,
+
+	/// Compare the field with a custom function taking both fields by reference.
+	With(syn::Path),
+}
+
+impl Compare {
+	/// Parse the `#[assert2(...)]` attributes of a field.
+	fn from_attributes(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+		let mut compare = Self::Default;
+		for attr in attrs {
+			if !attr.path().is_ident("assert2") {
+				continue;
+			}
+			attr.parse_nested_meta(|meta| {
+				if !matches!(compare, Self::Default) {
+					return Err(meta.error("only one of `skip` or `compare_with` can be used on a field"));
+				}
+				if meta.path.is_ident("skip") {
+					compare = Self::Skip;
+					Ok(())
+				} else if meta.path.is_ident("compare_with") {
+					compare = Self::With(meta.value()?.parse()?);
+					Ok(())
+				} else {
+					Err(meta.error("expected `skip` or `compare_with = path`"))
+				}
+			})?;
+		}
+		Ok(compare)
+	}
+}
+
+/// Generate the patterns to destructure both values and the code to compare the fields.
+fn diff_fields(fields: &syn::Fields) -> syn::Result<(TokenStream, TokenStream, TokenStream)> {
+	// Use mixed site hygiene for the bindings, so that they can not conflict with the names in the implementation.
+	let left: Vec<_> = (0..fields.len()).map(|i| syn::Ident::new(&format!("left_{i}"), Span::mixed_site())).collect();
+	let right: Vec<_> = (0..fields.len()).map(|i| syn::Ident::new(&format!("right_{i}"), Span::mixed_site())).collect();
+
+	let pattern = |vars: &[syn::Ident]| match fields {
+		syn::Fields::Named(_) => {
+			let idents = fields.iter().map(|field| field.ident.as_ref().unwrap());
+			quote!({ #(#idents: #vars,)* })
+		},
+		syn::Fields::Unnamed(_) => quote!(( #(#vars,)* )),
+		syn::Fields::Unit => quote!(),
+	};
+
+	let mut diffs = TokenStream::new();
+	for (i, field) in fields.iter().enumerate() {
+		let name = match &field.ident {
+			Some(ident) => ident.to_string(),
+			None => i.to_string(),
+		};
+		let (left, right) = (&left[i], &right[i]);
+		let field_path = quote!(&::assert2::__assert2_impl::structural::field_path(path, #name));
+		diffs.extend(match Compare::from_attributes(&field.attrs)? {
+			Compare::Default => quote! {
+				(&&::assert2::__assert2_impl::structural::Field(#left, #right)).__assert2_field_diff(#field_path, diffs);
+			},
+			Compare::Skip => quote!(),
+			Compare::With(compare) => quote! {
+				if !#compare(#left, #right) {
+					diffs.push(::assert2::FieldDiff::new(#field_path, #left, #right));
+				}
+			},
+		});
+	}
+	Ok((pattern(&left), pattern(&right), diffs))
+}
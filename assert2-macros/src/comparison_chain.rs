@@ -0,0 +1,172 @@
+use proc_macro2::{Spacing, Span, TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+
+use crate::expression_to_string;
+use crate::site_var;
+use crate::tokens_to_string;
+use crate::FormatArgs;
+use crate::Fragments;
+
+/// A chain of comparisons like `a < b <= c`.
+///
+/// The compiler rejects chained comparisons, so they are parsed manually by splitting the tokens at the comparison operators.
+pub struct ComparisonChain {
+	operands: Vec<syn::Expr>,
+	operators: Vec<syn::BinOp>,
+}
+
+impl ComparisonChain {
+	/// Try to parse a chain of at least two comparisons.
+	///
+	/// Returns `None` if the tokens are not a comparison chain.
+	pub fn parse(tokens: Vec<TokenTree>) -> Option<Self> {
+		let mut operands = Vec::new();
+		let mut operators = Vec::new();
+		let mut start = 0;
+		let mut i = 0;
+		while i < tokens.len() {
+			let Some(len) = comparison_len(&tokens, i) else {
+				i += 1;
+				continue;
+			};
+			// Only split if the tokens before the operator form a complete operand.
+			// Otherwise, the operator is part of something else, like the generic arguments of a path.
+			if let Some(operand) = parse_operand(&tokens[start..i]) {
+				operands.push(operand);
+				operators.push(syn::parse2(tokens[i..i + len].iter().cloned().collect()).ok()?);
+				start = i + len;
+			}
+			i += len;
+		}
+		operands.push(parse_operand(&tokens[start..])?);
+
+		if operators.len() < 2 {
+			return None;
+		}
+		Some(Self { operands, operators })
+	}
+}
+
+/// Get the length of the comparison operator at the given index, if there is one.
+fn comparison_len(tokens: &[TokenTree], index: usize) -> Option<usize> {
+	let punct = |i: usize| match tokens.get(i) {
+		Some(TokenTree::Punct(x)) => Some(x),
+		_ => None,
+	};
+	let current = punct(index)?;
+
+	// Skip the second character of multi-character operators like `->` and `=>`.
+	if let Some(previous) = index.checked_sub(1).and_then(punct) {
+		if previous.spacing() == Spacing::Joint {
+			return None;
+		}
+	}
+
+	let next = punct(index + 1).filter(|_| current.spacing() == Spacing::Joint).map(|x| x.as_char());
+	match (current.as_char(), next) {
+		('<' | '>' | '=' | '!', Some('=')) => Some(2),
+		('<', Some('<')) | ('>', Some('>')) => None,
+		('<' | '>', _) => Some(1),
+		_ => None,
+	}
+}
+
+/// Parse the tokens of a single operand.
+///
+/// Operands with operators that have a lower precedence than comparisons are rejected,
+/// since they would not be operands of the comparison if the chain was written out with `&&`.
+fn parse_operand(tokens: &[TokenTree]) -> Option<syn::Expr> {
+	if tokens.is_empty() {
+		return None;
+	}
+	let expr: syn::Expr = syn::parse2(tokens.iter().cloned().collect()).ok()?;
+	match &expr {
+		syn::Expr::Binary(syn::ExprBinary { op: syn::BinOp::And(_) | syn::BinOp::Or(_), .. }) => None,
+		syn::Expr::Assign(_) | syn::Expr::Closure(_) | syn::Expr::Let(_) | syn::Expr::Range(_) => None,
+		_ => Some(expr),
+	}
+}
+
+/// Collect the tokens of the expression up to the custom message or options.
+pub fn expression_tokens(input: syn::parse::ParseStream) -> syn::Result<Vec<TokenTree>> {
+	let mut tokens = Vec::new();
+	while !input.is_empty() && !input.peek(syn::token::Comma) && !input.peek(syn::token::Semi) {
+		tokens.push(input.parse()?);
+	}
+	Ok(tokens)
+}
+
+/// Generate the code to check all comparisons of a chain.
+///
+/// All operands are evaluated exactly once, from left to right, before any comparison is made.
+pub fn check_comparison_chain(crate_name: syn::Path, macro_name: syn::Expr, chain: ComparisonChain, format_args: Option<FormatArgs>, print: &syn::Ident) -> TokenStream {
+	let ComparisonChain { operands, operators } = chain;
+	let mut fragments = Fragments::new();
+	let operand_strs: Vec<_> = operands.iter()
+		.map(|operand| expression_to_string(&crate_name, operand.to_token_stream(), &mut fragments))
+		.collect();
+	let operator_strs: Vec<_> = operators.iter()
+		.map(|operator| tokens_to_string(operator.to_token_stream(), &mut fragments))
+		.collect();
+
+	let custom_msg = match format_args {
+		Some(x) => quote!(Some(format_args!(#x))),
+		None => quote!(None),
+	};
+	let site = site_var();
+
+	// Use mixed site hygiene for the operands, so that the custom message can not see them.
+	let vars: Vec<_> = (0..operands.len())
+		.map(|i| syn::Ident::new(&format!("operand_{i}"), Span::mixed_site()))
+		.collect();
+	let type_vars: Vec<_> = (0..operands.len())
+		.map(|i| syn::Ident::new(&format!("type_{i}"), Span::mixed_site()))
+		.collect();
+	let integer_vars: Vec<_> = (0..operands.len())
+		.map(|i| syn::Ident::new(&format!("integer_{i}"), Span::mixed_site()))
+		.collect();
+
+	let links = operators.iter().enumerate().map(|(i, operator)| {
+		let left = &vars[i];
+		let right = &vars[i + 1];
+		quote!(if !(#left #operator #right) { ::core::option::Option::Some(#i) })
+	});
+
+	quote! {
+		match (#(&(#operands),)*) {
+			(#(#vars,)*) => {
+				let failed = #(#links else)* { ::core::option::Option::None };
+				match failed {
+					::core::option::Option::None => Ok(()),
+					::core::option::Option::Some(failed) => {
+						#[allow(unused_imports)]
+						use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+						#[allow(unused_imports)]
+						use #crate_name::__assert2_impl::integer::{IsInteger, IsNotInteger};
+						#(let #type_vars = #crate_name::__assert2_impl::type_name_of(#vars);)*
+						#(let #integer_vars = (&&#crate_name::__assert2_impl::integer::Wrap(#vars)).__assert2_integer().wrap(#vars);)*
+						#(let #vars = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(#vars)).__assert2_maybe_debug().wrap(#vars);)*
+						#(let #vars = #crate_name::__assert2_impl::integer::Radix { integer: #integer_vars, format: None, value: #vars };)*
+						Err(#crate_name::__assert2_impl::print::FailedCheck {
+							macro_name: #macro_name,
+							file: ::core::panic::Location::caller().file(),
+							line: ::core::panic::Location::caller().line(),
+							column: ::core::panic::Location::caller().column(),
+							custom_msg: #custom_msg,
+							expression: #crate_name::__assert2_impl::print::ComparisonChain {
+								operands: &[#(&#vars as &dyn ::core::fmt::Debug,)*],
+								operators: &[#(#operator_strs,)*],
+								expressions: &[#(#operand_strs,)*],
+								types: &[#(#type_vars,)*],
+								failed,
+							},
+							fragments: #fragments,
+							defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+							site: #site,
+						}.#print())
+					},
+				}
+			},
+		}
+	}
+}
@@ -0,0 +1,208 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+
+use crate::expression_to_string;
+use crate::is_comparison;
+use crate::negation::operand_place;
+use crate::site_var;
+use crate::tokens_to_string;
+use crate::FormatArgs;
+use crate::Fragments;
+
+/// Check if an expression is a chain of `||` operators.
+pub fn is_disjunction(expr: &syn::ExprBinary) -> bool {
+	matches!(expr.op, syn::BinOp::Or(_)) && expr.attrs.is_empty()
+}
+
+/// Split a chain of `||` operators into the individual alternatives.
+fn flatten_disjunction(expr: syn::Expr, alternatives: &mut Vec<syn::Expr>) {
+	match expr {
+		syn::Expr::Binary(syn::ExprBinary { left, op: syn::BinOp::Or(_), right, attrs }) if attrs.is_empty() => {
+			flatten_disjunction(*left, alternatives);
+			flatten_disjunction(*right, alternatives);
+		},
+		expr => alternatives.push(expr),
+	}
+}
+
+/// Check if an operand can be evaluated again without side effects.
+fn is_reevaluable(expr: &syn::Expr) -> bool {
+	operand_place(expr).is_some() || matches!(expr, syn::Expr::Lit(_))
+}
+
+/// A single alternative of a disjunction.
+enum Alternative {
+	Comparison {
+		left: Box<syn::Expr>,
+		right: Box<syn::Expr>,
+		op: syn::BinOp,
+		left_expr: TokenStream,
+		right_expr: TokenStream,
+		op_str: TokenStream,
+		left_var: syn::Ident,
+		right_var: syn::Ident,
+	},
+	Bool {
+		expr: syn::Expr,
+		expr_str: TokenStream,
+	},
+}
+
+/// Generate the code to check a chain of `||` operators.
+///
+/// The alternatives are evaluated lazily from left to right, just like the `||` operator does.
+/// Each alternative is evaluated to a `bool`, so no borrows are held while the later alternatives run.
+/// If all alternatives fail, the operands of comparisons are borrowed again to expand them in the failure report.
+/// This is only done for comparisons of places and literals, since other operands may have side effects.
+pub fn check_disjunction(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprBinary, format_args: Option<FormatArgs>, print: &syn::Ident) -> TokenStream {
+	let mut fragments = Fragments::new();
+	let mut alternatives = Vec::new();
+	flatten_disjunction(syn::Expr::Binary(expr), &mut alternatives);
+
+	// Stringify all alternatives first, so all fragments are known when generating the failure path.
+	let alternatives: Vec<_> = alternatives.into_iter().enumerate().map(|(i, alternative)| {
+		match alternative {
+			syn::Expr::Binary(syn::ExprBinary { left, op, right, attrs }) if attrs.is_empty() && is_comparison(&op) && is_reevaluable(&left) && is_reevaluable(&right) => {
+				let left_expr = expression_to_string(&crate_name, left.to_token_stream(), &mut fragments);
+				let right_expr = expression_to_string(&crate_name, right.to_token_stream(), &mut fragments);
+				let op_str = tokens_to_string(op.to_token_stream(), &mut fragments);
+				// Use mixed site hygiene for the operands, so that later alternatives and the custom message can not see them.
+				let left_var = syn::Ident::new(&format!("left_{i}"), Span::mixed_site());
+				let right_var = syn::Ident::new(&format!("right_{i}"), Span::mixed_site());
+				Alternative::Comparison { left, right, op, left_expr, right_expr, op_str, left_var, right_var }
+			},
+			expr => {
+				let expr_str = expression_to_string(&crate_name, expr.to_token_stream(), &mut fragments);
+				Alternative::Bool { expr, expr_str }
+			},
+		}
+	}).collect();
+
+	let custom_msg = match format_args {
+		Some(x) => quote!(Some(format_args!(#x))),
+		None => quote!(None),
+	};
+	let site = site_var();
+
+	// Build the string for the full expression from the strings of the alternatives,
+	// so that the fragments are only registered once.
+	let mut alternative_strs = TokenStream::new();
+	let mut expansions = TokenStream::new();
+	let mut printers = TokenStream::new();
+	for (i, alternative) in alternatives.iter().enumerate() {
+		if i > 0 {
+			alternative_strs.extend(quote!(" || ",));
+		}
+		match alternative {
+			Alternative::Comparison { left_expr, right_expr, op_str, left_var, right_var, .. } => {
+				let left_type = syn::Ident::new(&format!("{left_var}_type"), Span::mixed_site());
+				let right_type = syn::Ident::new(&format!("{right_var}_type"), Span::mixed_site());
+				let left_integer = syn::Ident::new(&format!("{left_var}_integer"), Span::mixed_site());
+				let right_integer = syn::Ident::new(&format!("{right_var}_integer"), Span::mixed_site());
+				let left_address = syn::Ident::new(&format!("{left_var}_address"), Span::mixed_site());
+				let right_address = syn::Ident::new(&format!("{right_var}_address"), Span::mixed_site());
+				let left_text = syn::Ident::new(&format!("{left_var}_text"), Span::mixed_site());
+				let right_text = syn::Ident::new(&format!("{right_var}_text"), Span::mixed_site());
+				let left_bytes = syn::Ident::new(&format!("{left_var}_bytes"), Span::mixed_site());
+				let right_bytes = syn::Ident::new(&format!("{right_var}_bytes"), Span::mixed_site());
+				alternative_strs.extend(quote!(#left_expr, " ", #op_str, " ", #right_expr,));
+				expansions.extend(quote! {
+					let #left_type = #crate_name::__assert2_impl::type_name_of(#left_var);
+					let #right_type = #crate_name::__assert2_impl::type_name_of(#right_var);
+					let #left_address = (&&#crate_name::__assert2_impl::address::Wrap(#left_var)).__assert2_address();
+					let #right_address = (&&#crate_name::__assert2_impl::address::Wrap(#right_var)).__assert2_address();
+					let #left_text = (&&#crate_name::__assert2_impl::text::Wrap(#left_var)).__assert2_text();
+					let #right_text = (&&#crate_name::__assert2_impl::text::Wrap(#right_var)).__assert2_text();
+					let #left_bytes = (&&#crate_name::__assert2_impl::bytes::Wrap(#left_var)).__assert2_bytes();
+					let #right_bytes = (&&#crate_name::__assert2_impl::bytes::Wrap(#right_var)).__assert2_bytes();
+					let #left_integer = (&&#crate_name::__assert2_impl::integer::Wrap(#left_var)).__assert2_integer().wrap(#left_var);
+					let #right_integer = (&&#crate_name::__assert2_impl::integer::Wrap(#right_var)).__assert2_integer().wrap(#right_var);
+					let #left_var = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(#left_var)).__assert2_maybe_debug().wrap(#left_var);
+					let #right_var = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(#right_var)).__assert2_maybe_debug().wrap(#right_var);
+					let #left_var = #crate_name::__assert2_impl::integer::Radix { integer: #left_integer, format: None, value: #left_var };
+					let #right_var = #crate_name::__assert2_impl::integer::Radix { integer: #right_integer, format: None, value: #right_var };
+				});
+				printers.extend(quote! {
+					&#crate_name::__assert2_impl::print::BinaryOp {
+						left: &#left_var,
+						right: &#right_var,
+						operator: #op_str,
+						left_expr: #left_expr,
+						right_expr: #right_expr,
+						left_type: #left_type,
+						right_type: #right_type,
+						left_address: #left_address,
+						right_address: #right_address,
+						left_text: #left_text,
+						right_text: #right_text,
+						left_bytes: #left_bytes,
+						right_bytes: #right_bytes,
+					},
+				});
+			},
+			Alternative::Bool { expr_str, .. } => {
+				alternative_strs.extend(quote!(#expr_str,));
+				printers.extend(quote! {
+					&#crate_name::__assert2_impl::print::BooleanExpr {
+						expression: #expr_str,
+					},
+				});
+			},
+		}
+	}
+
+	let mut failure = quote! {{
+		#[allow(unused_imports)]
+		use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+		#[allow(unused_imports)]
+		use #crate_name::__assert2_impl::integer::{IsInteger, IsNotInteger};
+		#[allow(unused_imports)]
+		use #crate_name::__assert2_impl::address::{IsReference, IsNotReference};
+		#[allow(unused_imports)]
+		use #crate_name::__assert2_impl::text::{IsText, IsNotText};
+		#[allow(unused_imports)]
+		use #crate_name::__assert2_impl::bytes::{IsBytes, IsNotBytes};
+		#expansions
+		Err(#crate_name::__assert2_impl::print::FailedCheck {
+			macro_name: #macro_name,
+			file: ::core::panic::Location::caller().file(),
+			line: ::core::panic::Location::caller().line(),
+			column: ::core::panic::Location::caller().column(),
+			custom_msg: #custom_msg,
+			expression: #crate_name::__assert2_impl::print::Disjunction {
+				expression: ::core::concat!(#alternative_strs),
+				alternatives: &[#printers],
+			},
+			fragments: #fragments,
+			defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+			site: #site,
+		}.#print())
+	}};
+
+	// Borrow the operands of the comparisons again for the failure path.
+	for alternative in alternatives.iter().rev() {
+		if let Alternative::Comparison { left, right, left_var, right_var, .. } = alternative {
+			failure = quote! {
+				match (&(#left), &(#right)) {
+					(#left_var, #right_var) => #failure,
+				}
+			};
+		}
+	}
+
+	// Wrap the failure path in the checks of the alternatives, from the inside out.
+	let mut check = failure;
+	for alternative in alternatives.iter().rev() {
+		let condition = match alternative {
+			Alternative::Comparison { left, right, op, .. } => quote!(#left #op #right),
+			Alternative::Bool { expr, .. } => quote!(#expr),
+		};
+		check = quote! {
+			match #condition {
+				true => Ok(()),
+				false => #check,
+			}
+		};
+	}
+	check
+}
@@ -0,0 +1,50 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+
+/// Parse a duration with a unit suffix like `2s` or `50ms`.
+///
+/// Any other expression is passed through unmodified.
+pub fn duration_impl(tokens: TokenStream) -> TokenStream {
+	let lit = match syn::parse2::<syn::Lit>(tokens.clone()) {
+		Ok(x) => x,
+		Err(_) => match syn::parse2::<syn::ExprParen>(tokens.clone()) {
+			Ok(x) => return x.expr.into_token_stream(),
+			Err(_) => return tokens,
+		},
+	};
+
+	let (digits, suffix, is_float) = match &lit {
+		syn::Lit::Int(x) => (x.base10_digits().to_owned(), x.suffix().to_owned(), false),
+		syn::Lit::Float(x) => (x.base10_digits().to_owned(), x.suffix().to_owned(), true),
+		_ => return tokens,
+	};
+
+	let nanos_per_unit: u64 = match suffix.as_str() {
+		"ns" => 1,
+		"us" => 1_000,
+		"ms" => 1_000_000,
+		"s" => 1_000_000_000,
+		"m" | "min" => 60 * 1_000_000_000,
+		"h" => 60 * 60 * 1_000_000_000,
+		"" => return syn::Error::new_spanned(lit, "missing unit for duration, expected one of: ns, us, ms, s, min, h").into_compile_error(),
+		_ => return syn::Error::new_spanned(lit, "invalid unit for duration, expected one of: ns, us, ms, s, min, h").into_compile_error(),
+	};
+
+	if is_float {
+		let value: f64 = match digits.parse() {
+			Ok(x) => x,
+			Err(e) => return syn::Error::new_spanned(lit, e).into_compile_error(),
+		};
+		let nanos = (value * nanos_per_unit as f64).round() as u64;
+		quote!(::core::time::Duration::from_nanos(#nanos))
+	} else {
+		let value: u64 = match digits.parse() {
+			Ok(x) => x,
+			Err(e) => return syn::Error::new_spanned(lit, e).into_compile_error(),
+		};
+		match value.checked_mul(nanos_per_unit) {
+			Some(nanos) => quote!(::core::time::Duration::from_nanos(#nanos)),
+			None => syn::Error::new_spanned(lit.to_token_stream(), "duration too large").into_compile_error(),
+		}
+	}
+}
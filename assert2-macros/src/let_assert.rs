@@ -2,6 +2,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote, quote_spanned};
 
 use crate::expression_to_string;
+use crate::site_var;
 use crate::tokens_to_string;
 use crate::FormatArgs;
 use crate::Fragments;
@@ -12,6 +13,9 @@ pub struct Args {
 	pattern: syn::Pat,
 	expression: syn::Expr,
 	format_args: Option<FormatArgs>,
+
+	/// The block to run if the pattern matches, given after `=>`.
+	body: Option<syn::Block>,
 }
 
 pub fn let_assert_impl(args: Args) -> TokenStream {
@@ -21,44 +25,114 @@ pub fn let_assert_impl(args: Args) -> TokenStream {
 		pattern,
 		expression,
 		format_args,
+		body,
 	} = args;
 
-	let mut fragments = Fragments::new();
-	let pat_str = tokens_to_string(pattern.to_token_stream(), &mut fragments);
-
-	let expr_str = expression_to_string(&crate_name, expression.to_token_stream(), &mut fragments);
-	let custom_msg = match format_args {
-		Some(x) => quote!(Some(format_args!(#x))),
-		None => quote!(None),
-	};
+	if let Some(body) = body {
+		return syn::Error::new_spanned(body, "unexpected block: only `let_check!()` takes a block to run").into_compile_error();
+	}
 
 	let value = quote_spanned!{ Span::mixed_site() => value };
+	let checkpoints = quote_spanned!{ Span::mixed_site() => checkpoints };
+	let site = site_var();
+	let print_failure = print_failure(&crate_name, &macro_name, &pattern, &expression, format_args, &value);
+
+	// A `let` statement requires parentheses around a top-level or-pattern.
+	let pattern = match pattern {
+		syn::Pat::Or(_) => quote!((#pattern)),
+		pattern => pattern.into_token_stream(),
+	};
 
 	quote! {
+		let #site = #crate_name::__assert2_site!();
+		let #checkpoints = #crate_name::__assert2_impl::checkpoint::Scope::enter();
 		let #value = #expression;
 		let #pattern = #value else {
-			#[allow(unused)]
-			use #crate_name::__assert2_impl::maybe_debug::{IsDebug, IsMaybeNotDebug};
-			let value = (&&#crate_name::__assert2_impl::maybe_debug::Wrap(&#value)).__assert2_maybe_debug().wrap(&#value);
-			#crate_name::__assert2_impl::print::FailedCheck {
-				macro_name: #macro_name,
-				file: file!(),
-				line: line!(),
-				column: column!(),
-				custom_msg: #custom_msg,
-				expression: #crate_name::__assert2_impl::print::MatchExpr {
-					print_let: false,
-					value: &value,
-					pattern: #pat_str,
-					expression: #expr_str,
+			#print_failure
+			panic!("{}", #crate_name::__assert2_impl::panic_message("assertion failed"));
+		};
+		drop(#checkpoints);
+	}
+}
+
+pub fn let_check_impl(args: Args) -> TokenStream {
+	let Args {
+		crate_name,
+		macro_name,
+		pattern,
+		expression,
+		format_args,
+		body,
+	} = args;
+
+	let Some(body) = body else {
+		return syn::Error::new(Span::call_site(), "expected `=> { ... }` with a block to run if the pattern matches").into_compile_error();
+	};
+
+	let value = quote_spanned!{ Span::mixed_site() => value };
+	let checkpoints = quote_spanned!{ Span::mixed_site() => checkpoints };
+	let guard = quote_spanned!{ Span::mixed_site() => guard };
+	let message = quote_spanned!{ Span::mixed_site() => message };
+	let site = site_var();
+	let print_failure = print_failure(&crate_name, &macro_name, &pattern, &expression, format_args, &value);
+
+	quote! {
+		let #guard = {
+			let #site = #crate_name::__assert2_site!();
+			let #checkpoints = #crate_name::__assert2_impl::checkpoint::Scope::enter();
+			match #expression {
+				#pattern => {
+					drop(#checkpoints);
+					#body
+					None
+				},
+				#value => {
+					#print_failure
+					drop(#checkpoints);
+					let #message = #crate_name::__assert2_impl::panic_message("check failed");
+					Some(#crate_name::__assert2_impl::FailGuard::new(move || panic!("{}", #message)))
 				},
-				fragments: #fragments,
-			}.print();
-			panic!("assertion failed");
+			}
 		};
 	}
 }
 
+/// Generate the code to print a value that did not match the pattern.
+///
+/// The generated code expects the execution counter of the site in the variable given by [`site_var()`].
+fn print_failure(crate_name: &syn::Path, macro_name: &syn::Expr, pattern: &syn::Pat, expression: &syn::Expr, format_args: Option<FormatArgs>, value: &TokenStream) -> TokenStream {
+	let mut fragments = Fragments::new();
+	let pat_str = tokens_to_string(pattern.to_token_stream(), &mut fragments);
+	let expr_str = expression_to_string(crate_name, expression.to_token_stream(), &mut fragments);
+	let custom_msg = match format_args {
+		Some(x) => quote!(Some(format_args!(#x))),
+		None => quote!(None),
+	};
+	let site = site_var();
+
+	quote! {
+		#[allow(unused)]
+		use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+		let value = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(&#value)).__assert2_maybe_debug().wrap(&#value);
+		#crate_name::__assert2_impl::print::FailedCheck {
+			macro_name: #macro_name,
+			file: ::core::panic::Location::caller().file(),
+			line: ::core::panic::Location::caller().line(),
+			column: ::core::panic::Location::caller().column(),
+			custom_msg: #custom_msg,
+			expression: #crate_name::__assert2_impl::print::MatchExpr {
+				print_let: false,
+				value: &value,
+				pattern: #pat_str,
+				expression: #expr_str,
+			},
+			fragments: #fragments,
+			defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+			site: #site,
+		}.print();
+	}
+}
+
 impl syn::parse::Parse for Args {
 	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
 		let crate_name = input.parse()?;
@@ -69,20 +143,34 @@ impl syn::parse::Parse for Args {
 		let _eq_token = input.parse::<syn::token::Eq>()?;
 		let expression = input.parse()?;
 
-		let format_args = if input.is_empty() {
-			FormatArgs::new()
-		} else {
+		// Parse the custom message arguments up to the optional `=> { ... }` block.
+		let mut format_args = FormatArgs::new();
+		if !input.is_empty() && !input.peek(syn::token::FatArrow) {
 			input.parse::<syn::token::Comma>()?;
-			FormatArgs::parse_terminated(input)?
-		};
+			while !input.is_empty() && !input.peek(syn::token::FatArrow) {
+				format_args.push_value(input.parse()?);
+				if input.is_empty() || input.peek(syn::token::FatArrow) {
+					break;
+				}
+				format_args.push_punct(input.parse()?);
+			}
+		}
 		let format_args = Some(format_args).filter(|x| !x.is_empty());
 
+		let body = if input.is_empty() {
+			None
+		} else {
+			input.parse::<syn::token::FatArrow>()?;
+			Some(input.parse()?)
+		};
+
 		Ok(Self {
 			crate_name,
 			macro_name,
 			pattern,
 			expression,
 			format_args,
+			body,
 		})
 	}
 }
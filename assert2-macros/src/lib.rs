@@ -5,8 +5,8 @@
 
 extern crate proc_macro;
 
-use proc_macro2::TokenStream;
-use quote::{ToTokens, quote};
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote, quote_spanned};
 use syn::punctuated::Punctuated;
 
 type FormatArgs = Punctuated<syn::Expr, syn::token::Comma>;
@@ -17,8 +17,15 @@ pub fn check_impl(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
 	hygiene_bug::fix(check_or_assert_impl(syn::parse_macro_input!(tokens)).into())
 }
 
+mod assert_diff;
+mod comparison_chain;
+mod disjunction;
+mod duration;
 mod hygiene_bug;
 mod let_assert;
+mod matches;
+mod method_call;
+mod negation;
 
 #[doc(hidden)]
 #[proc_macro]
@@ -26,66 +33,322 @@ pub fn let_assert_impl(tokens: proc_macro::TokenStream) -> proc_macro::TokenStre
 	hygiene_bug::fix(let_assert::let_assert_impl(syn::parse_macro_input!(tokens)).into())
 }
 
+#[doc(hidden)]
+#[proc_macro]
+pub fn let_check_impl(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	hygiene_bug::fix(let_assert::let_check_impl(syn::parse_macro_input!(tokens)).into())
+}
+
+/// Derive `StructuralDiff` to show only the differing fields when an equality check fails.
+///
+/// See the documentation of [`StructuralDiff`](https://docs.rs/assert2/latest/assert2/trait.StructuralDiff.html) for details.
+#[proc_macro_derive(AssertDiff, attributes(assert2))]
+pub fn derive_assert_diff(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	assert_diff::derive_assert_diff(syn::parse_macro_input!(tokens)).into()
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn duration_impl(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	duration::duration_impl(tokens.into()).into()
+}
+
 /// Real implementation for assert!() and check!().
 fn check_or_assert_impl(args: Args) -> TokenStream {
-	match args.expr {
-		syn::Expr::Binary(expr) => check_binary_op(args.crate_name, args.macro_name, expr, args.format_args),
-		syn::Expr::Let(expr) => check_let_expr(args.crate_name, args.macro_name, expr, args.format_args),
-		expr => check_bool_expr(args.crate_name, args.macro_name, expr, args.format_args),
-	}
-}
+	let crate_name = args.crate_name.clone();
+	let level = match args.options.level(&args.macro_name) {
+		Ok(level) => level,
+		Err(e) => return e.into_compile_error(),
+	};
 
-fn check_binary_op(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprBinary, format_args: Option<FormatArgs>) -> TokenStream {
-	match expr.op {
-		syn::BinOp::Eq(_) => (),
-		syn::BinOp::Lt(_) => (),
-		syn::BinOp::Le(_) => (),
-		syn::BinOp::Ne(_) => (),
-		syn::BinOp::Ge(_) => (),
-		syn::BinOp::Gt(_) => (),
-		_ => return check_bool_expr(crate_name, macro_name, syn::Expr::Binary(expr), format_args),
+	// Warnings are printed with a different header, so the check needs to know the level.
+	let print = match level {
+		Level::Warn => syn::Ident::new("print_warning", Span::call_site()),
+		Level::Error | Level::Fatal => syn::Ident::new("print", Span::call_site()),
 	};
 
+	let check = match args.expr {
+		Condition::Expr(syn::Expr::Assign(expr)) => {
+			return syn::Error::new_spanned(expr.eq_token, "expected a condition, found an assignment: use `==` to compare for equality").into_compile_error();
+		},
+		Condition::Expr(syn::Expr::Binary(expr)) if is_comparison(&expr.op) => check_binary_op(args.crate_name, args.macro_name, expr, args.format_args, &args.options, &print),
+		_ if args.options.has_operand_options() => return args.options.reject_operand_options().into_compile_error(),
+		Condition::Chain(chain) => comparison_chain::check_comparison_chain(args.crate_name, args.macro_name, chain, args.format_args, &print),
+		Condition::Expr(syn::Expr::Binary(expr)) if is_let_chain(&expr) => check_let_chain(args.crate_name, args.macro_name, expr, args.format_args, &print),
+		Condition::Expr(syn::Expr::Binary(expr)) if disjunction::is_disjunction(&expr) => disjunction::check_disjunction(args.crate_name, args.macro_name, expr, args.format_args, &print),
+		Condition::Expr(syn::Expr::Unary(expr)) if negation::is_negation(&expr) && negation::has_operands(&expr) => negation::check_negation(args.crate_name, args.macro_name, expr, args.format_args, &print),
+		Condition::Expr(syn::Expr::Macro(expr)) if matches::parse_matches(&expr).is_some() => {
+			let matches = matches::parse_matches(&expr).unwrap();
+			matches::check_matches(args.crate_name, args.macro_name, matches, args.format_args, &print)
+		},
+		Condition::Expr(syn::Expr::MethodCall(expr)) if method_call::is_known_method(&expr) => method_call::check_method_call(args.crate_name, args.macro_name, expr, args.format_args, &print),
+		Condition::Expr(syn::Expr::Let(expr)) => check_let_expr(args.crate_name, args.macro_name, expr, args.format_args, &print),
+		Condition::Expr(expr) => check_bool_expr(args.crate_name, args.macro_name, expr, args.format_args, &print),
+	};
+
+	let check = match level {
+		Level::Warn => quote! {{
+			let _ = #check;
+			::core::result::Result::<(), #crate_name::Failure>::Ok(())
+		}},
+		Level::Error => check,
+		Level::Fatal => quote! {
+			match #check {
+				::core::result::Result::Ok(value) => ::core::result::Result::Ok(value),
+				::core::result::Result::Err(failure) => {
+					#crate_name::__assert2_panic!("assertion failed");
+					::core::result::Result::Err(failure)
+				},
+			}
+		},
+	};
+
+	// Record checkpoints while evaluating the expression, so they can be printed on failure.
+	let checkpoints = quote_spanned!{ Span::mixed_site() => checkpoints };
+	let site = site_var();
+	quote! {{
+		let #site = #crate_name::__assert2_site!();
+		let #checkpoints = #crate_name::__assert2_impl::checkpoint::Scope::enter();
+		#check
+	}}
+}
+
+/// The variable that holds the execution counter of the assertion site.
+fn site_var() -> TokenStream {
+	quote_spanned!{ Span::mixed_site() => site }
+}
+
+/// Check if a binary operator is a comparison.
+fn is_comparison(op: &syn::BinOp) -> bool {
+	matches!(op,
+		syn::BinOp::Eq(_)
+		| syn::BinOp::Lt(_)
+		| syn::BinOp::Le(_)
+		| syn::BinOp::Ne(_)
+		| syn::BinOp::Ge(_)
+		| syn::BinOp::Gt(_)
+	)
+}
+
+fn check_binary_op(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprBinary, format_args: Option<FormatArgs>, options: &Options, print: &syn::Ident) -> TokenStream {
 	let syn::ExprBinary { left, right, op, .. } = &expr;
 	let mut fragments = Fragments::new();
 	let left_expr = expression_to_string(&crate_name, left.to_token_stream(), &mut fragments);
 	let right_expr = expression_to_string(&crate_name, right.to_token_stream(), &mut fragments);
 	let op_str = tokens_to_string(op.to_token_stream(), &mut fragments);
 
+	// Capture the leaf operands of arithmetic before the comparison is evaluated, since the arithmetic may move them.
+	let mut leaves = Vec::new();
+	arithmetic_leaves(left, true, &mut leaves);
+	arithmetic_leaves(right, true, &mut leaves);
+	let mut leaf_strs = Vec::new();
+	let mut leaf_vars = Vec::new();
+	let mut captures = TokenStream::new();
+	for leaf in leaves {
+		let var = syn::Ident::new(&format!("leaf_{}", leaf_vars.len()), Span::mixed_site());
+		captures.extend(quote! {
+			let #var = (&&#crate_name::__assert2_impl::copy_value::Wrap(&#leaf)).__assert2_copy_value();
+		});
+		leaf_strs.push(expression_to_string(&crate_name, leaf.to_token_stream(), &mut fragments));
+		leaf_vars.push(var);
+	}
+
 	let custom_msg = match format_args {
 		Some(x) => quote!(Some(format_args!(#x))),
 		None => quote!(None),
 	};
+	let site = site_var();
 
-	quote! {
+	// Use mixed site hygiene for the operands, so that the custom message and expansion overrides can not see them.
+	let left_var = quote_spanned!{ Span::mixed_site() => left };
+	let right_var = quote_spanned!{ Span::mixed_site() => right };
+	let (display, integers) = match options.format.as_ref().map(|(_, format)| *format) {
+		Some(Format::Display) => (quote!(Some(true)), quote!(None)),
+		Some(Format::Debug) => (quote!(Some(false)), quote!(None)),
+		Some(Format::Decimal) => (quote!(None), quote!(Some(#crate_name::__assert2_impl::integer::IntegerFormat::Decimal))),
+		Some(Format::Hex) => (quote!(None), quote!(Some(#crate_name::__assert2_impl::integer::IntegerFormat::Hex))),
+		Some(Format::Binary) => (quote!(None), quote!(Some(#crate_name::__assert2_impl::integer::IntegerFormat::Binary))),
+		None => (quote!(None), quote!(None)),
+	};
+	let expand = |var: &TokenStream, expansion: &Option<(syn::Ident, syn::Expr)>| match expansion {
+		Some((_, expansion)) => quote! {
+			let #var = #crate_name::__assert2_impl::print::RawExpansion(::std::format!("{}", #expansion));
+		},
+		None => {
+			let integer_var = quote_spanned!{ Span::mixed_site() => integer };
+			quote! {
+				let #integer_var = (&&#crate_name::__assert2_impl::integer::Wrap(#var)).__assert2_integer().wrap(#var);
+				let #var = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(#var)).__assert2_maybe_debug().wrap_with(#var, #display);
+				let #var = #crate_name::__assert2_impl::integer::Radix { integer: #integer_var, format: #integers, value: #var };
+			}
+		},
+	};
+	let expand_left = expand(&left_var, &options.expand_left);
+	let expand_right = expand(&right_var, &options.expand_right);
+	let left_type = quote_spanned!{ Span::mixed_site() => left_type };
+	let right_type = quote_spanned!{ Span::mixed_site() => right_type };
+	let left_address = quote_spanned!{ Span::mixed_site() => left_address };
+	let right_address = quote_spanned!{ Span::mixed_site() => right_address };
+	let left_text = quote_spanned!{ Span::mixed_site() => left_text };
+	let right_text = quote_spanned!{ Span::mixed_site() => right_text };
+	let left_bytes = quote_spanned!{ Span::mixed_site() => left_bytes };
+	let right_bytes = quote_spanned!{ Span::mixed_site() => right_bytes };
+	// Print strings as raw text and byte buffers as a hexdump, unless the format or expansion is overridden.
+	let contents = if options.has_operand_options() {
+		quote! {
+			let (#left_text, #right_text) = (None, None);
+			let (#left_bytes, #right_bytes) = (None, None);
+		}
+	} else {
+		quote! {
+			#[allow(unused_imports)]
+			use #crate_name::__assert2_impl::text::{IsText, IsNotText};
+			let #left_text = (&&#crate_name::__assert2_impl::text::Wrap(#left_var)).__assert2_text();
+			let #right_text = (&&#crate_name::__assert2_impl::text::Wrap(#right_var)).__assert2_text();
+			#[allow(unused_imports)]
+			use #crate_name::__assert2_impl::bytes::{IsBytes, IsNotBytes};
+			let #left_bytes = (&&#crate_name::__assert2_impl::bytes::Wrap(#left_var)).__assert2_bytes();
+			let #right_bytes = (&&#crate_name::__assert2_impl::bytes::Wrap(#right_var)).__assert2_bytes();
+		}
+	};
+
+	let mut expression = quote! {
+		#crate_name::__assert2_impl::print::BinaryOp {
+			left: &#left_var,
+			right: &#right_var,
+			operator: #op_str,
+			left_expr: #left_expr,
+			right_expr: #right_expr,
+			left_type: #left_type,
+			right_type: #right_type,
+			left_address: #left_address,
+			right_address: #right_address,
+			left_text: #left_text,
+			right_text: #right_text,
+			left_bytes: #left_bytes,
+			right_bytes: #right_bytes,
+		}
+	};
+	// Show the bits that differ if both sides are integers, unless the expansion is overridden.
+	if matches!(op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) && !options.has_expand() {
+		expression = quote! {
+			#crate_name::__assert2_impl::print::BitDiff {
+				check: #expression,
+				left: #left_var.integer,
+				right: #right_var.integer,
+				format: #integers,
+			}
+		};
+	}
+	// Compare both sides field by field if they implement `StructuralDiff`, unless the format or expansion is overridden.
+	// Strings and byte buffers have their own expansion, which takes precedence.
+	let mut structural_diff = TokenStream::new();
+	if matches!(op, syn::BinOp::Eq(_)) && !options.has_operand_options() {
+		let diffs_var = quote_spanned!{ Span::mixed_site() => diffs };
+		structural_diff = quote! {
+			let #diffs_var = if (#left_text.is_some() && #right_text.is_some()) || (#left_bytes.is_some() && #right_bytes.is_some()) {
+				None
+			} else {
+				#[allow(unused_imports)]
+				use #crate_name::__assert2_impl::structural::{IsNotStructuralRoot, IsSerializeRoot, IsStructuralRoot};
+				(&&&#crate_name::__assert2_impl::structural::Root(#left_var, #right_var)).__assert2_structural_diff()
+			};
+		};
+		expression = quote! {
+			#crate_name::__assert2_impl::print::FieldDiffs {
+				check: #expression,
+				diffs: #diffs_var,
+			}
+		};
+	}
+	if !leaf_vars.is_empty() {
+		expression = quote! {
+			#crate_name::__assert2_impl::print::WithValues {
+				check: #expression,
+				values: &[#((#leaf_strs, #leaf_vars.as_ref().map(|x| x as &dyn ::core::fmt::Debug)),)*],
+			}
+		};
+		captures = quote! {
+			#[allow(unused_imports)]
+			use #crate_name::__assert2_impl::copy_value::{IsCopy, IsNotCopy};
+			#captures
+		};
+	}
+
+	quote! {{
+		#captures
 		match (&(#left), &(#right)) {
-			(left, right) if !(left #op right) => {
-				use #crate_name::__assert2_impl::maybe_debug::{IsDebug, IsMaybeNotDebug};
-				let left = (&&#crate_name::__assert2_impl::maybe_debug::Wrap(left)).__assert2_maybe_debug().wrap(left);
-				let right = (&&#crate_name::__assert2_impl::maybe_debug::Wrap(right)).__assert2_maybe_debug().wrap(right);
-				#crate_name::__assert2_impl::print::FailedCheck {
+			(#left_var, #right_var) if !(#left_var #op #right_var) => {
+				#[allow(unused_imports)]
+				use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+				#[allow(unused_imports)]
+				use #crate_name::__assert2_impl::integer::{IsInteger, IsNotInteger};
+				let #left_type = #crate_name::__assert2_impl::type_name_of(#left_var);
+				let #right_type = #crate_name::__assert2_impl::type_name_of(#right_var);
+				#[allow(unused_imports)]
+				use #crate_name::__assert2_impl::address::{IsReference, IsNotReference};
+				let #left_address = (&&#crate_name::__assert2_impl::address::Wrap(#left_var)).__assert2_address();
+				let #right_address = (&&#crate_name::__assert2_impl::address::Wrap(#right_var)).__assert2_address();
+				#contents
+				#structural_diff
+				#expand_left
+				#expand_right
+				Err(#crate_name::__assert2_impl::print::FailedCheck {
 					macro_name: #macro_name,
-					file: file!(),
-					line: line!(),
-					column: column!(),
+					file: ::core::panic::Location::caller().file(),
+					line: ::core::panic::Location::caller().line(),
+					column: ::core::panic::Location::caller().column(),
 					custom_msg: #custom_msg,
-					expression: #crate_name::__assert2_impl::print::BinaryOp {
-						left: &left,
-						right: &right,
-						operator: #op_str,
-						left_expr: #left_expr,
-						right_expr: #right_expr,
-					},
+					expression: #expression,
 					fragments: #fragments,
-				}.print();
-				Err(())
+					defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+					site: #site,
+				}.#print())
 			}
 			_ => Ok(()),
 		}
+	}}
+}
+
+/// Collect the simple places that are used as operands of arithmetic in one side of a comparison.
+///
+/// A side that is a simple place by itself is not collected, since its value is printed anyway.
+fn arithmetic_leaves<'a>(expr: &'a syn::Expr, top: bool, leaves: &mut Vec<&'a syn::Expr>) {
+	match expr {
+		syn::Expr::Binary(syn::ExprBinary { left, op, right, .. }) if is_arithmetic(op) => {
+			arithmetic_leaves(left, false, leaves);
+			arithmetic_leaves(right, false, leaves);
+		},
+		syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => arithmetic_leaves(expr, false, leaves),
+		syn::Expr::Cast(syn::ExprCast { expr, .. }) => arithmetic_leaves(expr, false, leaves),
+		syn::Expr::Paren(syn::ExprParen { expr, .. }) => arithmetic_leaves(expr, top, leaves),
+		expr if !top && is_simple_place(expr) => {
+			let name = expr.to_token_stream().to_string();
+			if !leaves.iter().any(|leaf| leaf.to_token_stream().to_string() == name) {
+				leaves.push(expr);
+			}
+		},
+		_ => (),
 	}
 }
 
-fn check_bool_expr(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::Expr, format_args: Option<FormatArgs>) -> TokenStream {
+/// Check if a binary operator is an arithmetic or bitwise operator.
+fn is_arithmetic(op: &syn::BinOp) -> bool {
+	matches!(op,
+		syn::BinOp::Add(_)
+		| syn::BinOp::Sub(_)
+		| syn::BinOp::Mul(_)
+		| syn::BinOp::Div(_)
+		| syn::BinOp::Rem(_)
+		| syn::BinOp::BitAnd(_)
+		| syn::BinOp::BitOr(_)
+		| syn::BinOp::BitXor(_)
+		| syn::BinOp::Shl(_)
+		| syn::BinOp::Shr(_)
+	)
+}
+
+fn check_bool_expr(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::Expr, format_args: Option<FormatArgs>, print: &syn::Ident) -> TokenStream {
 	let mut fragments = Fragments::new();
 	let expr_str = expression_to_string(&crate_name, expr.to_token_stream(), &mut fragments);
 
@@ -93,29 +356,31 @@ fn check_bool_expr(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::Expr
 		Some(x) => quote!(Some(format_args!(#x))),
 		None => quote!(None),
 	};
+	let site = site_var();
 
 	quote! {
 		match #expr {
 			false => {
-				#crate_name::__assert2_impl::print::FailedCheck {
+				Err(#crate_name::__assert2_impl::print::FailedCheck {
 					macro_name: #macro_name,
-					file: file!(),
-					line: line!(),
-					column: column!(),
+					file: ::core::panic::Location::caller().file(),
+					line: ::core::panic::Location::caller().line(),
+					column: ::core::panic::Location::caller().column(),
 					custom_msg: #custom_msg,
 					expression: #crate_name::__assert2_impl::print::BooleanExpr {
 						expression: #expr_str,
 					},
 					fragments: #fragments,
-				}.print();
-				Err(())
+					defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+					site: #site,
+				}.#print())
 			}
 			true => Ok(()),
 		}
 	}
 }
 
-fn check_let_expr(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprLet, format_args: Option<FormatArgs>) -> TokenStream {
+fn check_let_expr(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprLet, format_args: Option<FormatArgs>, print: &syn::Ident) -> TokenStream {
 	let syn::ExprLet {
 		pat,
 		expr,
@@ -130,18 +395,35 @@ fn check_let_expr(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprL
 		Some(x) => quote!(Some(format_args!(#x))),
 		None => quote!(None),
 	};
+	let site = site_var();
+
+	// Procedural macros can not emit warnings on stable, so use a deprecated item to trigger one.
+	let irrefutable_warning = if is_irrefutable(&pat) {
+		use syn::spanned::Spanned;
+		quote_spanned! { pat.span() => {
+			#[deprecated(note = "the pattern is irrefutable, so this assertion can never fail")]
+			struct IrrefutablePattern;
+			let _ = IrrefutablePattern;
+		}}
+	} else {
+		TokenStream::new()
+	};
+
+	// Return the bindings as a tuple, so that `assert!()` can evaluate to them.
+	let bindings = pattern_bindings(&pat);
 
 	quote! {
+		#irrefutable_warning
 		match &(#expr) {
-			#pat => Ok(()),
+			#pat => Ok((#(#bindings,)*)),
 			value => {
-				use #crate_name::__assert2_impl::maybe_debug::{IsDebug, IsMaybeNotDebug};
-				let value = (&&#crate_name::__assert2_impl::maybe_debug::Wrap(value)).__assert2_maybe_debug().wrap(value);
-				#crate_name::__assert2_impl::print::FailedCheck {
+				use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+				let value = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(value)).__assert2_maybe_debug().wrap(value);
+				Err(#crate_name::__assert2_impl::print::FailedCheck {
 					macro_name: #macro_name,
-					file: file!(),
-					line: line!(),
-					column: column!(),
+					file: ::core::panic::Location::caller().file(),
+					line: ::core::panic::Location::caller().line(),
+					column: ::core::panic::Location::caller().column(),
 					custom_msg: #custom_msg,
 					expression: #crate_name::__assert2_impl::print::MatchExpr {
 						print_let: true,
@@ -150,13 +432,365 @@ fn check_let_expr(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprL
 						expression: #expr_str,
 					},
 					fragments: #fragments,
-				}.print();
-				Err(())
+					defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+					site: #site,
+				}.#print())
 			}
 		}
 	}
 }
 
+/// Check if an expression is a chain of `&&` operators with at least one `let` expression.
+fn is_let_chain(expr: &syn::ExprBinary) -> bool {
+	let mut steps = Vec::new();
+	flatten_let_chain(&syn::Expr::Binary(expr.clone()), &mut steps);
+	steps.iter().any(|step| matches!(step, syn::Expr::Let(_)))
+}
+
+/// Split a chain of `&&` operators into the individual steps.
+fn flatten_let_chain(expr: &syn::Expr, steps: &mut Vec<syn::Expr>) {
+	match expr {
+		syn::Expr::Binary(syn::ExprBinary { left, op: syn::BinOp::And(_), right, attrs }) if attrs.is_empty() => {
+			flatten_let_chain(left, steps);
+			flatten_let_chain(right, steps);
+		},
+		expr => steps.push(expr.clone()),
+	}
+}
+
+fn check_let_chain(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprBinary, format_args: Option<FormatArgs>, print: &syn::Ident) -> TokenStream {
+	let mut fragments = Fragments::new();
+	let mut steps = Vec::new();
+	flatten_let_chain(&syn::Expr::Binary(expr), &mut steps);
+
+	// Stringify all steps first, so all fragments are known when generating the failure paths.
+	let steps: Vec<_> = steps.into_iter().map(|step| {
+		match step {
+			syn::Expr::Let(syn::ExprLet { pat, expr, .. }) => {
+				let pat_str = tokens_to_string(pat.to_token_stream(), &mut fragments);
+				let expr_str = expression_to_string(&crate_name, expr.to_token_stream(), &mut fragments);
+				LetChainStep::Let { pat, expr, pat_str, expr_str }
+			},
+			expr => {
+				let expr_str = expression_to_string(&crate_name, expr.to_token_stream(), &mut fragments);
+				LetChainStep::Bool { expr, expr_str }
+			},
+		}
+	}).collect();
+
+	// The compiler refuses to parse a let chain as expression outside of `if` and `while`,
+	// so build the string for the full chain from the strings of the steps.
+	let mut step_strs = TokenStream::new();
+	for (i, step) in steps.iter().enumerate() {
+		if i > 0 {
+			step_strs.extend(quote!(" && ",));
+		}
+		match step {
+			LetChainStep::Let { pat_str, expr_str, .. } => step_strs.extend(quote!("let ", #pat_str, " = ", #expr_str,)),
+			LetChainStep::Bool { expr_str, .. } => step_strs.extend(quote!(#expr_str,)),
+		}
+	}
+	let chain_str = quote!(::core::concat!(#step_strs));
+
+	let custom_msg = match format_args {
+		Some(x) => quote!(Some(format_args!(#x))),
+		None => quote!(None),
+	};
+
+	let context = LetChainContext {
+		crate_name: &crate_name,
+		macro_name: &macro_name,
+		chain_str: &chain_str,
+		custom_msg: &custom_msg,
+		fragments: &fragments,
+		print,
+	};
+	let check = context.generate(&steps, &mut Vec::new());
+	quote! {{
+		use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+		#check
+	}}
+}
+
+/// A single step of a let chain.
+enum LetChainStep {
+	Let {
+		pat: Box<syn::Pat>,
+		expr: Box<syn::Expr>,
+		pat_str: TokenStream,
+		expr_str: TokenStream,
+	},
+	Bool {
+		expr: syn::Expr,
+		expr_str: TokenStream,
+	},
+}
+
+/// A variable bound by a step of a let chain.
+struct LetChainBinding {
+	/// The name of the variable.
+	name: String,
+
+	/// The variable itself.
+	ident: syn::Ident,
+
+	/// The hygienic variable that holds the `Debug` output of the variable, once it has been captured.
+	var: syn::Ident,
+
+	/// If the `Debug` output has been captured already, because a later step may move the variable.
+	captured: bool,
+}
+
+/// Shared information for generating the code of a let chain.
+struct LetChainContext<'a> {
+	crate_name: &'a syn::Path,
+	macro_name: &'a syn::Expr,
+	chain_str: &'a TokenStream,
+	custom_msg: &'a TokenStream,
+	fragments: &'a Fragments,
+	print: &'a syn::Ident,
+}
+
+impl LetChainContext<'_> {
+	/// Generate the code for the remaining steps of a let chain.
+	///
+	/// The `bindings` contain the variables bound by earlier steps.
+	/// Their `Debug` output is only captured when a step fails,
+	/// unless a step may move them: then it is captured right before that step.
+	fn generate(&self, steps: &[LetChainStep], bindings: &mut Vec<LetChainBinding>) -> TokenStream {
+		let Some((step, remaining)) = steps.split_first() else {
+			return quote!(Ok(()));
+		};
+		let crate_name = self.crate_name;
+
+		match step {
+			LetChainStep::Let { pat, expr, pat_str, expr_str } => {
+				let captures = self.capture_moved(expr, bindings);
+				let failure = self.failure(bindings, quote! {
+					#crate_name::__assert2_impl::print::MatchExpr {
+						print_let: true,
+						value: &value,
+						pattern: #pat_str,
+						expression: #expr_str,
+					}
+				});
+
+				for ident in pattern_bindings(pat) {
+					let name = ident.to_string();
+					let var = syn::Ident::new(&format!("binding_{}", bindings.len()), Span::mixed_site());
+					bindings.retain(|existing| existing.name != name);
+					bindings.push(LetChainBinding { name, ident, var, captured: false });
+				}
+				let next = self.generate(remaining, bindings);
+
+				quote! {{
+					#captures
+					match #expr {
+						#pat => #next,
+						value => {
+							let value = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(&value)).__assert2_maybe_debug().wrap(&value);
+							#failure
+						}
+					}
+				}}
+			},
+			LetChainStep::Bool { expr, expr_str } => {
+				let captures = self.capture_moved(expr, bindings);
+				let failure = self.failure(bindings, quote! {
+					#crate_name::__assert2_impl::print::BooleanExpr {
+						expression: #expr_str,
+					}
+				});
+				let next = self.generate(remaining, bindings);
+				quote! {{
+					#captures
+					match #expr {
+						true => #next,
+						false => #failure,
+					}
+				}}
+			},
+		}
+	}
+
+	/// Generate the code to capture the `Debug` output of the bindings that may be moved by an expression.
+	fn capture_moved(&self, expr: &syn::Expr, bindings: &mut [LetChainBinding]) -> TokenStream {
+		let mut captures = TokenStream::new();
+		for binding in bindings.iter_mut().filter(|binding| !binding.captured && may_move(expr, &binding.ident)) {
+			captures.extend(self.capture(binding));
+			binding.captured = true;
+		}
+		captures
+	}
+
+	/// Generate the code to capture the `Debug` output of a binding.
+	fn capture(&self, binding: &LetChainBinding) -> TokenStream {
+		let crate_name = self.crate_name;
+		let LetChainBinding { ident, var, .. } = binding;
+		quote! {
+			let #var = #crate_name::__assert2_impl::print::format_binding(
+				&(&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(&#ident)).__assert2_maybe_debug().wrap(&#ident)
+			);
+		}
+	}
+
+	/// Generate the code to report a failed step of a let chain.
+	fn failure(&self, bindings: &[LetChainBinding], step: TokenStream) -> TokenStream {
+		let Self { crate_name, macro_name, chain_str, custom_msg, fragments, print } = self;
+		let captures = bindings.iter().filter(|binding| !binding.captured).map(|binding| self.capture(binding));
+		let names = bindings.iter().map(|binding| &binding.name);
+		let vars = bindings.iter().map(|binding| &binding.var);
+		let site = site_var();
+		quote! {{
+			#(#captures)*
+			Err(#crate_name::__assert2_impl::print::FailedCheck {
+				macro_name: #macro_name,
+				file: ::core::panic::Location::caller().file(),
+				line: ::core::panic::Location::caller().line(),
+				column: ::core::panic::Location::caller().column(),
+				custom_msg: #custom_msg,
+				expression: #crate_name::__assert2_impl::print::LetChain {
+					expression: #chain_str,
+					bindings: &[#((#names, #vars.as_str()),)*],
+					step: #step,
+				},
+				fragments: #fragments,
+				defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+				site: #site,
+			}.#print())
+		}}
+	}
+}
+
+/// Check if an expression may move a variable.
+///
+/// Borrowing the variable or comparing it does not move it.
+/// Any other use may move it, including calling a method on it, since the method may take `self` by value.
+fn may_move(expr: &syn::Expr, ident: &syn::Ident) -> bool {
+	struct Visitor<'a> {
+		ident: &'a syn::Ident,
+		found: bool,
+	}
+
+	impl Visitor<'_> {
+		/// Check if an expression is the variable or a field of it.
+		fn is_variable(&self, expr: &syn::Expr) -> bool {
+			match expr {
+				syn::Expr::Path(syn::ExprPath { qself: None, path, .. }) => path.is_ident(self.ident),
+				syn::Expr::Field(syn::ExprField { base, .. }) => self.is_variable(base),
+				syn::Expr::Paren(syn::ExprParen { expr, .. }) => self.is_variable(expr),
+				_ => false,
+			}
+		}
+	}
+
+	impl<'ast> syn::visit::Visit<'ast> for Visitor<'_> {
+		fn visit_expr(&mut self, expr: &'ast syn::Expr) {
+			match expr {
+				syn::Expr::Path(syn::ExprPath { qself: None, path, .. }) => self.found |= path.is_ident(self.ident),
+				syn::Expr::Reference(syn::ExprReference { expr, .. }) if self.is_variable(expr) => (),
+				syn::Expr::Binary(syn::ExprBinary { left, op, right, .. }) if is_comparison(op) => {
+					for side in [left, right] {
+						if !self.is_variable(side) {
+							self.visit_expr(side);
+						}
+					}
+				},
+				expr => syn::visit::visit_expr(self, expr),
+			}
+		}
+
+		// The tokens of a macro can not be analyzed, so assume that any mention of the variable may move it.
+		fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+			fn mentions(tokens: TokenStream, ident: &syn::Ident) -> bool {
+				tokens.into_iter().any(|token| match token {
+					proc_macro2::TokenTree::Ident(x) => x == *ident,
+					proc_macro2::TokenTree::Group(group) => mentions(group.stream(), ident),
+					_ => false,
+				})
+			}
+			self.found |= mentions(mac.tokens.clone(), self.ident);
+		}
+	}
+
+	let mut visitor = Visitor { ident, found: false };
+	syn::visit::Visit::visit_expr(&mut visitor, expr);
+	visitor.found
+}
+
+/// Check if a pattern can never fail to match.
+///
+/// This only recognizes patterns that are irrefutable regardless of the type of the value.
+fn is_irrefutable(pat: &syn::Pat) -> bool {
+	match pat {
+		syn::Pat::Wild(_) => true,
+		syn::Pat::Rest(_) => true,
+		syn::Pat::Ident(pat) => {
+			let subpattern_irrefutable = match &pat.subpat {
+				Some((_, subpat)) => is_irrefutable(subpat),
+				None => true,
+			};
+			is_binding_name(&pat.ident) && subpattern_irrefutable
+		},
+		syn::Pat::Tuple(pat) => pat.elems.iter().all(is_irrefutable),
+		syn::Pat::Reference(pat) => is_irrefutable(&pat.pat),
+		syn::Pat::Paren(pat) => is_irrefutable(&pat.pat),
+		syn::Pat::Type(pat) => is_irrefutable(&pat.pat),
+		_ => false,
+	}
+}
+
+/// Check if an identifier in a pattern looks like a new variable binding.
+///
+/// Identifiers that start with an uppercase letter are assumed to be constants or unit variants.
+fn is_binding_name(ident: &syn::Ident) -> bool {
+	!ident.to_string().starts_with(|c: char| c.is_uppercase())
+}
+
+/// Check if an expression is a place expression that can be borrowed without side effects.
+///
+/// Only local variables and fields or indices of them are accepted.
+/// Paths that start with an uppercase letter are assumed to be constants or unit variants,
+/// which may not have a known type on their own.
+fn is_simple_place(expr: &syn::Expr) -> bool {
+	match expr {
+		syn::Expr::Path(syn::ExprPath { qself: None, path, attrs }) => {
+			attrs.is_empty() && path.get_ident().map_or(false, is_binding_name)
+		},
+		syn::Expr::Field(syn::ExprField { base, attrs, .. }) => attrs.is_empty() && is_simple_place(base),
+		syn::Expr::Index(syn::ExprIndex { expr, index, attrs, .. }) => {
+			attrs.is_empty() && is_simple_place(expr) && (is_simple_place(index) || matches!(**index, syn::Expr::Lit(_)))
+		},
+		syn::Expr::Paren(syn::ExprParen { expr, attrs, .. }) => attrs.is_empty() && is_simple_place(expr),
+		_ => false,
+	}
+}
+
+/// Get the variables bound by a pattern.
+fn pattern_bindings(pat: &syn::Pat) -> Vec<syn::Ident> {
+	struct Visitor(Vec<syn::Ident>);
+
+	impl<'ast> syn::visit::Visit<'ast> for Visitor {
+		fn visit_pat_ident(&mut self, pat: &'ast syn::PatIdent) {
+			if is_binding_name(&pat.ident) {
+				self.0.push(pat.ident.clone());
+			}
+			syn::visit::visit_pat_ident(self, pat);
+		}
+
+		// All alternatives of an or-pattern bind the same variables, so only look at the first one.
+		fn visit_pat_or(&mut self, pat: &'ast syn::PatOr) {
+			if let Some(first) = pat.cases.first() {
+				self.visit_pat(first);
+			}
+		}
+	}
+
+	let mut visitor = Visitor(Vec::new());
+	syn::visit::Visit::visit_pat(&mut visitor, pat);
+	visitor.0
+}
+
 fn tokens_to_string(ts: TokenStream, fragments: &mut Fragments) -> TokenStream {
 	#[cfg(nightly)]
 	{
@@ -234,8 +868,39 @@ impl quote::ToTokens for Fragments {
 struct Args {
 	crate_name: syn::Path,
 	macro_name: syn::Expr,
-	expr: syn::Expr,
+	expr: Condition,
 	format_args: Option<FormatArgs>,
+	options: Options,
+}
+
+/// The condition to check.
+enum Condition {
+	/// A regular expression.
+	Expr(syn::Expr),
+
+	/// A chain of comparisons like `a < b <= c`, which is not a valid expression.
+	Chain(comparison_chain::ComparisonChain),
+}
+
+impl syn::parse::Parse for Condition {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		use syn::parse::discouraged::Speculative;
+
+		let fork = input.fork();
+		match fork.parse() {
+			Ok(expr) => {
+				input.advance_to(&fork);
+				Ok(Self::Expr(expr))
+			},
+			// Only try to parse a comparison chain if the tokens are not a valid expression.
+			Err(error) => {
+				let tokens = comparison_chain::expression_tokens(input)?;
+				comparison_chain::ComparisonChain::parse(tokens)
+					.map(Self::Chain)
+					.ok_or(error)
+			},
+		}
+	}
 }
 
 impl syn::parse::Parse for Args {
@@ -245,11 +910,25 @@ impl syn::parse::Parse for Args {
 		let macro_name = input.parse()?;
 		let _comma: syn::token::Comma = input.parse()?;
 		let expr = input.parse()?;
-		let format_args = if input.is_empty() {
-			FormatArgs::new()
-		} else {
+
+		// Parse the custom message arguments up to the optional `; options` clause.
+		let mut format_args = FormatArgs::new();
+		if !input.is_empty() && !input.peek(syn::token::Semi) {
 			input.parse::<syn::token::Comma>()?;
-			FormatArgs::parse_terminated(input)?
+			while !input.is_empty() && !input.peek(syn::token::Semi) {
+				format_args.push_value(input.parse()?);
+				if input.is_empty() || input.peek(syn::token::Semi) {
+					break;
+				}
+				format_args.push_punct(input.parse()?);
+			}
+		}
+
+		let options = if input.is_empty() {
+			Options::default()
+		} else {
+			input.parse::<syn::token::Semi>()?;
+			input.parse()?
 		};
 
 		let format_args = Some(format_args).filter(|x| !x.is_empty());
@@ -258,6 +937,153 @@ impl syn::parse::Parse for Args {
 			macro_name,
 			expr,
 			format_args,
+			options,
 		})
 	}
 }
+
+/// The severity of a check.
+#[derive(Copy, Clone)]
+enum Level {
+	/// Print the failure, but never fail the test.
+	Warn,
+
+	/// Use the default semantics of the macro.
+	Error,
+
+	/// Panic immediately on failure.
+	Fatal,
+}
+
+/// Options given after the expression and custom message: `check!(expr, "message"; key = value)`.
+#[derive(Default)]
+struct Options {
+	level: Option<(syn::Ident, Level)>,
+
+	/// Expression that renders the left operand of a comparison, used instead of the `Debug` output.
+	expand_left: Option<(syn::Ident, syn::Expr)>,
+
+	/// Expression that renders the right operand of a comparison, used instead of the `Debug` output.
+	expand_right: Option<(syn::Ident, syn::Expr)>,
+
+	/// Override for the format of the operands of a comparison.
+	format: Option<(syn::Ident, Format)>,
+}
+
+/// The value of the `format` option.
+#[derive(Copy, Clone)]
+enum Format {
+	/// Print values with their `Display` implementation if they have one.
+	Display,
+
+	/// Print values with their `Debug` implementation if they have one.
+	Debug,
+
+	/// Print integers in decimal.
+	Decimal,
+
+	/// Print integers in hexadecimal.
+	Hex,
+
+	/// Print integers in binary.
+	Binary,
+}
+
+impl Options {
+	/// Get the severity level of the check.
+	///
+	/// Only `check!()` supports severity levels, since it is the only macro that can both delay and skip a failure.
+	fn level(&self, macro_name: &syn::Expr) -> syn::Result<Level> {
+		let Some((key, level)) = &self.level else {
+			return Ok(Level::Error);
+		};
+		match macro_name {
+			syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(name), .. }) if name.value() == "check" => Ok(*level),
+			_ => Err(syn::Error::new_spanned(key, "the `level` option is only supported by `check!()`")),
+		}
+	}
+
+	/// Check if an expansion override is given for one of the operands.
+	fn has_expand(&self) -> bool {
+		self.expand_left.is_some() || self.expand_right.is_some()
+	}
+
+	/// Check if an option is given that only applies to the operands of a comparison.
+	fn has_operand_options(&self) -> bool {
+		self.has_expand() || self.format.is_some()
+	}
+
+	/// Get the error for an operand option on an expression that is not a comparison.
+	///
+	/// Only comparisons have operands that can be expanded.
+	fn reject_operand_options(&self) -> syn::Error {
+		let key = self.expand_left.as_ref()
+			.or(self.expand_right.as_ref())
+			.map(|(key, _)| key)
+			.or(self.format.as_ref().map(|(key, _)| key))
+			.unwrap();
+		syn::Error::new_spanned(key, format!("the `{key}` option is only supported for comparisons"))
+	}
+}
+
+impl syn::parse::Parse for Options {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let mut options = Self::default();
+		while !input.is_empty() {
+			let key: syn::Ident = input.parse()?;
+			input.parse::<syn::token::Eq>()?;
+			if key == "level" {
+				if options.level.is_some() {
+					return Err(syn::Error::new_spanned(key, "duplicate option: `level`"));
+				}
+				let value: syn::Ident = input.parse()?;
+				let level = if value == "warn" {
+					Level::Warn
+				} else if value == "error" {
+					Level::Error
+				} else if value == "fatal" {
+					Level::Fatal
+				} else {
+					return Err(syn::Error::new_spanned(value, "invalid level: expected `warn`, `error` or `fatal`"));
+				};
+				options.level = Some((key, level));
+			} else if key == "expand_left" || key == "expand_right" {
+				let option = if key == "expand_left" {
+					&mut options.expand_left
+				} else {
+					&mut options.expand_right
+				};
+				if option.is_some() {
+					return Err(syn::Error::new_spanned(&key, format!("duplicate option: `{key}`")));
+				}
+				*option = Some((key, input.parse()?));
+			} else if key == "format" {
+				if options.format.is_some() {
+					return Err(syn::Error::new_spanned(key, "duplicate option: `format`"));
+				}
+				let value: syn::Ident = input.parse()?;
+				let format = if value == "display" {
+					Format::Display
+				} else if value == "debug" {
+					Format::Debug
+				} else if value == "decimal" {
+					Format::Decimal
+				} else if value == "hex" {
+					Format::Hex
+				} else if value == "binary" {
+					Format::Binary
+				} else {
+					return Err(syn::Error::new_spanned(value, "invalid format: expected `display`, `debug`, `decimal`, `hex` or `binary`"));
+				};
+				options.format = Some((key, format));
+			} else {
+				return Err(syn::Error::new_spanned(&key, format!("unknown option: `{key}`")));
+			}
+			if input.is_empty() {
+				break;
+			}
+			input.parse::<syn::token::Comma>()?;
+		}
+		Ok(options)
+	}
+}
@@ -0,0 +1,102 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+
+use crate::expression_to_string;
+use crate::site_var;
+use crate::tokens_to_string;
+use crate::FormatArgs;
+use crate::Fragments;
+
+/// The arguments of a `matches!()` invocation: `matches!(expr, pattern if guard)`.
+pub struct MatchesArgs {
+	expr: syn::Expr,
+	pat: syn::Pat,
+	guard: Option<syn::Expr>,
+}
+
+impl syn::parse::Parse for MatchesArgs {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let expr = input.parse()?;
+		let _comma: syn::token::Comma = input.parse()?;
+		let pat = syn::Pat::parse_multi_with_leading_vert(input)?;
+		let guard = if input.peek(syn::token::If) {
+			let _if: syn::token::If = input.parse()?;
+			Some(input.parse()?)
+		} else {
+			None
+		};
+		let _comma: Option<syn::token::Comma> = input.parse()?;
+		Ok(Self { expr, pat, guard })
+	}
+}
+
+/// Parse the arguments of an invocation of the standard `matches!()` macro.
+///
+/// Returns `None` if the macro is not `matches!()`, `std::matches!()` or `core::matches!()`,
+/// or if the arguments could not be parsed.
+pub fn parse_matches(expr: &syn::ExprMacro) -> Option<MatchesArgs> {
+	let path = &expr.mac.path;
+	let segments: Vec<_> = path.segments.iter().map(|segment| segment.ident.to_string()).collect();
+	let is_matches = match segments.as_slice() {
+		[name] => path.leading_colon.is_none() && name == "matches",
+		[krate, name] => (krate == "std" || krate == "core") && name == "matches",
+		_ => false,
+	};
+	if !is_matches || !expr.attrs.is_empty() {
+		return None;
+	}
+	expr.mac.parse_body().ok()
+}
+
+/// Generate the code to check a `matches!()` invocation as if it was a `let` expression.
+///
+/// A guard is printed as a let chain: `let pattern = expr && guard`.
+pub fn check_matches(crate_name: syn::Path, macro_name: syn::Expr, args: MatchesArgs, format_args: Option<FormatArgs>, print: &syn::Ident) -> TokenStream {
+	let MatchesArgs { expr, pat, guard } = args;
+
+	let mut fragments = Fragments::new();
+	let pat_str = tokens_to_string(pat.to_token_stream(), &mut fragments);
+	let mut expr_str = expression_to_string(&crate_name, expr.to_token_stream(), &mut fragments);
+	if let Some(guard) = &guard {
+		let guard_str = expression_to_string(&crate_name, guard.to_token_stream(), &mut fragments);
+		expr_str = quote!(::core::concat!(#expr_str, " && ", #guard_str));
+	}
+	let guard = guard.map(|guard| quote!(if #guard));
+
+	let custom_msg = match format_args {
+		Some(x) => quote!(Some(format_args!(#x))),
+		None => quote!(None),
+	};
+	let site = site_var();
+
+	// Bind the value by reference in the failure path, so that a place expression is not moved,
+	// just like it would not be moved by `matches!()`.
+	let value = syn::Ident::new("value", Span::mixed_site());
+
+	quote! {
+		match #expr {
+			#pat #guard => Ok(()),
+			ref #value => {
+				#[allow(unused_imports)]
+				use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+				let #value = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(#value)).__assert2_maybe_debug().wrap(#value);
+				Err(#crate_name::__assert2_impl::print::FailedCheck {
+					macro_name: #macro_name,
+					file: ::core::panic::Location::caller().file(),
+					line: ::core::panic::Location::caller().line(),
+					column: ::core::panic::Location::caller().column(),
+					custom_msg: #custom_msg,
+					expression: #crate_name::__assert2_impl::print::MatchExpr {
+						print_let: true,
+						value: &#value,
+						pattern: #pat_str,
+						expression: #expr_str,
+					},
+					fragments: #fragments,
+					defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+					site: #site,
+				}.#print())
+			}
+		}
+	}
+}
@@ -0,0 +1,83 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+
+use crate::expression_to_string;
+use crate::site_var;
+use crate::FormatArgs;
+use crate::Fragments;
+
+/// Well-known boolean methods that take `&self`, with the number of arguments they take.
+const METHODS: &[(&str, usize)] = &[
+	("is_ok", 0),
+	("is_err", 0),
+	("is_some", 0),
+	("is_none", 0),
+	("contains", 1),
+	("starts_with", 1),
+	("ends_with", 1),
+	("eq_ignore_ascii_case", 1),
+];
+
+/// Check if an expression is a call to a well-known boolean method.
+///
+/// The arguments must be references or literals,
+/// so that they can be printed after the call without having been moved.
+pub fn is_known_method(expr: &syn::ExprMethodCall) -> bool {
+	let method = expr.method.to_string();
+	expr.attrs.is_empty()
+		&& expr.turbofish.is_none()
+		&& METHODS.iter().any(|&(name, args)| name == method && args == expr.args.len())
+		&& expr.args.iter().all(|arg| matches!(arg, syn::Expr::Reference(_) | syn::Expr::Lit(_)))
+}
+
+/// Generate the code to check a call to a well-known boolean method, printing the receiver and the arguments on failure.
+///
+/// The receiver is evaluated once and borrowed, and the method is called on the reference.
+pub fn check_method_call(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprMethodCall, format_args: Option<FormatArgs>, print: &syn::Ident) -> TokenStream {
+	let mut fragments = Fragments::new();
+	let expr_str = expression_to_string(&crate_name, expr.to_token_stream(), &mut fragments);
+
+	let custom_msg = match format_args {
+		Some(x) => quote!(Some(format_args!(#x))),
+		None => quote!(None),
+	};
+	let site = site_var();
+
+	let syn::ExprMethodCall { receiver, method, args, .. } = expr;
+
+	// Use mixed site hygiene for the operands, so that the custom message can not see them.
+	let receiver_var = syn::Ident::new("receiver", Span::mixed_site());
+	let arg_vars: Vec<_> = (0..args.len())
+		.map(|i| syn::Ident::new(&format!("arg_{i}"), Span::mixed_site()))
+		.collect();
+	let args = args.iter();
+
+	quote! {
+		match (&(#receiver), #(#args,)*) {
+			(#receiver_var, #(#arg_vars,)*) => match #receiver_var.#method(#(#arg_vars,)*) {
+				true => Ok(()),
+				false => {
+					#[allow(unused_imports)]
+					use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+					let #receiver_var = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(#receiver_var)).__assert2_maybe_debug().wrap(#receiver_var);
+					#(let #arg_vars = (&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(&#arg_vars)).__assert2_maybe_debug().wrap(&#arg_vars);)*
+					Err(#crate_name::__assert2_impl::print::FailedCheck {
+						macro_name: #macro_name,
+						file: ::core::panic::Location::caller().file(),
+						line: ::core::panic::Location::caller().line(),
+						column: ::core::panic::Location::caller().column(),
+						custom_msg: #custom_msg,
+						expression: #crate_name::__assert2_impl::print::MethodCall {
+							expression: #expr_str,
+							receiver: &#receiver_var,
+							args: &[#(&#arg_vars as &dyn ::core::fmt::Debug,)*],
+						},
+						fragments: #fragments,
+						defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+						site: #site,
+					}.#print())
+				},
+			},
+		}
+	}
+}
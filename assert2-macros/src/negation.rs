@@ -0,0 +1,191 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+
+use crate::expression_to_string;
+use crate::is_comparison;
+use crate::is_simple_place;
+use crate::method_call::is_known_method;
+use crate::site_var;
+use crate::FormatArgs;
+use crate::Fragments;
+
+/// Check if an expression is a negation: `!inner`.
+pub fn is_negation(expr: &syn::ExprUnary) -> bool {
+	matches!(expr.op, syn::UnOp::Not(_)) && expr.attrs.is_empty()
+}
+
+/// Remove any number of parentheses around an expression.
+fn strip_parens(expr: &syn::Expr) -> &syn::Expr {
+	match expr {
+		syn::Expr::Paren(syn::ExprParen { expr, attrs, .. }) if attrs.is_empty() => strip_parens(expr),
+		expr => expr,
+	}
+}
+
+/// Get the place expression behind an operand, if the operand is a (reference to a) simple place.
+pub fn operand_place(expr: &syn::Expr) -> Option<&syn::Expr> {
+	match strip_parens(expr) {
+		syn::Expr::Reference(syn::ExprReference { expr, attrs, .. }) if attrs.is_empty() => operand_place(expr),
+		expr if is_simple_place(expr) => Some(expr),
+		_ => None,
+	}
+}
+
+/// Check if an argument is a reference or a literal, so that it is not moved by a call.
+fn is_borrowed_arg(arg: &syn::Expr) -> bool {
+	matches!(arg, syn::Expr::Reference(_) | syn::Expr::Lit(_))
+}
+
+/// Get the receiver and arguments of a method call or function call that can be evaluated by reference before the call.
+///
+/// Method calls must be calls to well-known methods that take `&self`,
+/// and the arguments of function calls must be references or literals,
+/// so that none of them are moved by the call.
+fn call_operands(expr: &syn::Expr) -> Option<Vec<&syn::Expr>> {
+	match expr {
+		syn::Expr::MethodCall(call) if is_known_method(call) => {
+			Some(std::iter::once(&*call.receiver).chain(&call.args).collect())
+		},
+		syn::Expr::Call(call) if call.attrs.is_empty() && call.args.iter().all(is_borrowed_arg) => {
+			Some(call.args.iter().collect())
+		},
+		_ => None,
+	}
+}
+
+/// Check if the operands of a negated expression can be printed.
+pub fn has_operands(expr: &syn::ExprUnary) -> bool {
+	match strip_parens(&expr.expr) {
+		syn::Expr::Binary(inner) => is_comparison(&inner.op),
+		inner => call_operands(inner).map_or(false, |operands| operands.iter().any(|x| operand_place(x).is_some())),
+	}
+}
+
+/// Generate the code to check a negated expression, printing the operands of the inner expression.
+///
+/// For a negated comparison, both sides of the comparison are evaluated once and printed.
+/// For a negated method call or function call, the receiver and arguments that are simple place expressions are printed.
+/// The receiver and arguments are evaluated by reference before the call, so they can still be printed if the check fails.
+pub fn check_negation(crate_name: syn::Path, macro_name: syn::Expr, expr: syn::ExprUnary, format_args: Option<FormatArgs>, print: &syn::Ident) -> TokenStream {
+	let mut fragments = Fragments::new();
+	let expr_str = expression_to_string(&crate_name, expr.to_token_stream(), &mut fragments);
+
+	let custom_msg = match format_args {
+		Some(x) => quote!(Some(format_args!(#x))),
+		None => quote!(None),
+	};
+	let site = site_var();
+
+	let capture = |place: &syn::Expr| quote! {
+		#crate_name::__assert2_impl::print::format_binding(
+			&(&&&&#crate_name::__assert2_impl::maybe_debug::Wrap(&#place)).__assert2_maybe_debug().wrap(&#place)
+		)
+	};
+
+	// Stringify all operands first, so all fragments are known when generating the failure path.
+	let comparison = match strip_parens(&expr.expr) {
+		syn::Expr::Binary(syn::ExprBinary { left, op, right, .. }) if is_comparison(op) => Some((left, op, right)),
+		_ => None,
+	};
+	let mut operands = Vec::new();
+	let mut captures = TokenStream::new();
+	match comparison {
+		Some((left, _, right)) => {
+			// Use mixed site hygiene for the operands, so that the custom message can not see them.
+			for (name, operand) in [("left", left), ("right", right)] {
+				let var = syn::Ident::new(name, Span::mixed_site());
+				let value = capture(&syn::parse_quote!(*#var));
+				captures.extend(quote!(let #var = #value;));
+				operands.push((expression_to_string(&crate_name, operand.to_token_stream(), &mut fragments), var));
+			}
+		},
+		None => {
+			for (i, operand) in call_operands(strip_parens(&expr.expr)).unwrap_or_default().into_iter().enumerate() {
+				if operand_place(operand).is_none() {
+					continue;
+				}
+				let var = syn::Ident::new(&format!("operand_{i}"), Span::mixed_site());
+				let value = capture(&syn::parse_quote!(*#var));
+				captures.extend(quote!(let #var = #value;));
+				operands.push((expression_to_string(&crate_name, operand.to_token_stream(), &mut fragments), var));
+			}
+		},
+	}
+
+	let names = operands.iter().map(|(name, _)| name);
+	let vars = operands.iter().map(|(_, var)| var);
+	let failure = quote! {
+		Err(#crate_name::__assert2_impl::print::FailedCheck {
+			macro_name: #macro_name,
+			file: ::core::panic::Location::caller().file(),
+			line: ::core::panic::Location::caller().line(),
+			column: ::core::panic::Location::caller().column(),
+			custom_msg: #custom_msg,
+			expression: #crate_name::__assert2_impl::print::Negation {
+				expression: #expr_str,
+				operands: &[#((#names, #vars.as_str()),)*],
+			},
+			fragments: #fragments,
+			defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+			site: #site,
+		}.#print())
+	};
+
+	let check = match comparison {
+		Some((left, op, right)) => {
+			let left_var = &operands[0].1;
+			let right_var = &operands[1].1;
+			quote! {
+				match (&(#left), &(#right)) {
+					(#left_var, #right_var) if !(#left_var #op #right_var) => Ok(()),
+					(#left_var, #right_var) => {
+						#captures
+						#failure
+					},
+				}
+			}
+		},
+		None => {
+			let vars: Vec<_> = (0..call_operands(strip_parens(&expr.expr)).unwrap_or_default().len())
+				.map(|i| syn::Ident::new(&format!("operand_{i}"), Span::mixed_site()))
+				.collect();
+			let check = |call: TokenStream| quote! {
+				match !#call {
+					true => Ok(()),
+					false => {
+						#captures
+						#failure
+					},
+				}
+			};
+			match strip_parens(&expr.expr) {
+				syn::Expr::MethodCall(syn::ExprMethodCall { receiver, method, args, .. }) => {
+					let (receiver_var, arg_vars) = vars.split_first().unwrap();
+					let args = args.iter();
+					let check = check(quote!(#receiver_var.#method(#(#arg_vars,)*)));
+					quote! {
+						match (&(#receiver), #(#args,)*) {
+							(#receiver_var, #(#arg_vars,)*) => #check,
+						}
+					}
+				},
+				syn::Expr::Call(syn::ExprCall { func, args, .. }) => {
+					let check = check(quote!(#func(#(#vars,)*)));
+					let args = args.iter();
+					quote! {
+						match (#(#args,)*) {
+							(#(#vars,)*) => #check,
+						}
+					}
+				},
+				_ => unreachable!(),
+			}
+		},
+	};
+
+	quote! {{
+		#[allow(unused_imports)]
+		use #crate_name::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+		#check
+	}}
+}
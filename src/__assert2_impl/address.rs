@@ -0,0 +1,40 @@
+/// Wrapper to get the address that a reference points to, using autoref specialization.
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+pub trait IsReference {
+	fn __assert2_address(&self) -> Option<*const ()>;
+}
+
+pub trait IsNotReference {
+	fn __assert2_address(&self) -> Option<*const ()> {
+		None
+	}
+}
+
+impl<T: ?Sized> IsReference for &Wrap<'_, &T> {
+	fn __assert2_address(&self) -> Option<*const ()> {
+		Some(*self.0 as *const T as *const ())
+	}
+}
+
+impl<T: ?Sized> IsReference for &Wrap<'_, &mut T> {
+	fn __assert2_address(&self) -> Option<*const ()> {
+		Some(&**self.0 as *const T as *const ())
+	}
+}
+
+impl<T: ?Sized> IsNotReference for Wrap<'_, T> {}
+
+#[test]
+#[allow(clippy::needless_borrow)]
+fn test_address() {
+	use crate::assert;
+	let value = 5;
+	let reference = &value;
+	let mut other = 6;
+	let mutable = &mut other;
+	assert!((&&Wrap(&reference)).__assert2_address() == Some(&value as *const i32 as *const ()));
+	let expected = &*mutable as *const i32 as *const ();
+	assert!((&&Wrap(&mutable)).__assert2_address() == Some(expected));
+	assert!((&&Wrap(&value)).__assert2_address().is_none());
+}
@@ -0,0 +1,188 @@
+use std::fmt::{Debug, Write};
+use std::ops::Range;
+
+/// The part of a value that is checked by an assertion.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Affix {
+	/// Check that a value starts with the expected value.
+	Prefix,
+
+	/// Check that a value ends with the expected value.
+	Suffix,
+}
+
+/// The rendered values of a failed prefix or suffix check.
+pub struct AffixMismatch {
+	/// The debug representation of the actual value.
+	pub actual: String,
+
+	/// The range of `actual` where it diverges from the expected value.
+	pub actual_highlight: Range<usize>,
+
+	/// The debug representation of the expected prefix or suffix.
+	pub expected: String,
+
+	/// The range of `expected` that is not matched by the actual value.
+	pub expected_highlight: Range<usize>,
+}
+
+/// Wrapper to select the right way to compare the actual and expected value.
+pub struct Wrap<'a, A: ?Sized, B: ?Sized>(pub &'a A, pub &'a B);
+
+/// Compare string-like values character by character.
+pub trait IsStr {
+	fn __assert2_check_affix(&self, affix: Affix) -> Option<AffixMismatch>;
+}
+
+/// Compare slice-like values element by element.
+pub trait IsSlice<T> {
+	fn __assert2_check_affix(&self, affix: Affix) -> Option<AffixMismatch>;
+}
+
+impl<A, B> IsStr for &Wrap<'_, A, B>
+where
+	A: AsRef<str> + ?Sized,
+	B: AsRef<str> + ?Sized,
+{
+	fn __assert2_check_affix(&self, affix: Affix) -> Option<AffixMismatch> {
+		let actual = self.0.as_ref();
+		let expected = self.1.as_ref();
+		let actual_chars: Vec<char> = actual.chars().collect();
+		let expected_chars: Vec<char> = expected.chars().collect();
+		let (actual_highlight, expected_highlight) = divergence(&actual_chars, &expected_chars, affix)?;
+		let (actual, actual_highlight) = render_str(actual, actual_highlight);
+		let (expected, expected_highlight) = render_str(expected, expected_highlight);
+		Some(AffixMismatch {
+			actual,
+			actual_highlight,
+			expected,
+			expected_highlight,
+		})
+	}
+}
+
+impl<A, B, T> IsSlice<T> for Wrap<'_, A, B>
+where
+	A: AsRef<[T]> + ?Sized,
+	B: AsRef<[T]> + ?Sized,
+	T: PartialEq + Debug,
+{
+	fn __assert2_check_affix(&self, affix: Affix) -> Option<AffixMismatch> {
+		let actual = self.0.as_ref();
+		let expected = self.1.as_ref();
+		let (actual_highlight, expected_highlight) = divergence(actual, expected, affix)?;
+		let (actual, actual_highlight) = render_slice(actual, actual_highlight);
+		let (expected, expected_highlight) = render_slice(expected, expected_highlight);
+		Some(AffixMismatch {
+			actual,
+			actual_highlight,
+			expected,
+			expected_highlight,
+		})
+	}
+}
+
+/// Find where `actual` diverges from the expected prefix or suffix.
+///
+/// Returns `None` if `actual` starts or ends with `expected`.
+/// Otherwise, returns the range of elements in `actual` that differ from the expected value,
+/// and the range of elements in `expected` that are not matched.
+fn divergence<T: PartialEq>(actual: &[T], expected: &[T], affix: Affix) -> Option<(Range<usize>, Range<usize>)> {
+	let compared = actual.len().min(expected.len());
+	match affix {
+		Affix::Prefix => {
+			let common = actual.iter()
+				.zip(expected)
+				.take_while(|(a, b)| a == b)
+				.count();
+			if common == expected.len() {
+				return None;
+			}
+			Some((common..compared, common..expected.len()))
+		},
+		Affix::Suffix => {
+			let common = actual.iter().rev()
+				.zip(expected.iter().rev())
+				.take_while(|(a, b)| a == b)
+				.count();
+			if common == expected.len() {
+				return None;
+			}
+			Some((actual.len() - compared..actual.len() - common, 0..expected.len() - common))
+		},
+	}
+}
+
+/// Render a string with the debug format, and translate a range of characters to a range in the output.
+fn render_str(text: &str, chars: Range<usize>) -> (String, Range<usize>) {
+	/// Get the debug representation of a string without the surrounding quotes.
+	fn escape(text: &str) -> String {
+		let escaped = format!("{text:?}");
+		escaped[1..escaped.len() - 1].to_owned()
+	}
+
+	let byte_index = |chars: usize| text.char_indices().nth(chars).map_or(text.len(), |(i, _)| i);
+	let start = byte_index(chars.start);
+	let end = byte_index(chars.end);
+
+	let mut output = String::from("\"");
+	output.push_str(&escape(&text[..start]));
+	let highlight_start = output.len();
+	output.push_str(&escape(&text[start..end]));
+	let highlight_end = output.len();
+	output.push_str(&escape(&text[end..]));
+	output.push('"');
+	(output, highlight_start..highlight_end)
+}
+
+/// Render a slice with the debug format, and translate a range of elements to a range in the output.
+fn render_slice<T: Debug>(items: &[T], range: Range<usize>) -> (String, Range<usize>) {
+	let mut output = String::from("[");
+	let mut start = None;
+	let mut end = None;
+	for (i, item) in items.iter().enumerate() {
+		if i > 0 {
+			output.push_str(", ");
+		}
+		if i == range.start {
+			start = Some(output.len());
+		}
+		write!(output, "{item:?}").unwrap();
+		if i + 1 == range.end && !range.is_empty() {
+			end = Some(output.len());
+		}
+	}
+	let start = start.unwrap_or(output.len());
+	let end = end.unwrap_or(start);
+	output.push(']');
+	(output, start..end)
+}
+
+#[test]
+fn test_divergence() {
+	use crate::assert;
+	assert!(divergence(b"hello", b"he", Affix::Prefix) == None);
+	assert!(divergence(b"hello", b"", Affix::Prefix) == None);
+	assert!(divergence(b"hello", b"help", Affix::Prefix) == Some((3..4, 3..4)));
+	assert!(divergence(b"he", b"hello", Affix::Prefix) == Some((2..2, 2..5)));
+	assert!(divergence(b"hello", b"llo", Affix::Suffix) == None);
+	assert!(divergence(b"hello", b"yellow", Affix::Suffix) == Some((0..5, 0..6)));
+	assert!(divergence(b"hello", b"jello", Affix::Suffix) == Some((0..1, 0..1)));
+	assert!(divergence(b"lo", b"hello", Affix::Suffix) == Some((0..0, 0..3)));
+}
+
+#[test]
+fn test_render() {
+	use crate::assert;
+	let (text, range) = render_str("aé\n", 1..2);
+	assert!(text == "\"aé\\n\"");
+	assert!(&text[range] == "é");
+	let (text, range) = render_str("a\tb", 1..2);
+	assert!(&text[range] == "\\t");
+	let (text, range) = render_slice(&[1, 20, 3], 1..3);
+	assert!(text == "[1, 20, 3]");
+	assert!(&text[range] == "20, 3");
+	let (text, range) = render_slice(&[1, 2], 2..2);
+	assert!(range == (5..5));
+	assert!(text == "[1, 2]");
+}
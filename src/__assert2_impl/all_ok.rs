@@ -0,0 +1,79 @@
+/// A value that can be converted to a `Result`.
+///
+/// This is implemented for `Result` and references to a `Result`,
+/// so that `assert_all_ok!()` can take iterators that yield results by value or by reference.
+pub trait AsResult {
+	type Ok;
+	type Err;
+
+	fn into_result(self) -> Result<Self::Ok, Self::Err>;
+}
+
+impl<T, E> AsResult for Result<T, E> {
+	type Ok = T;
+	type Err = E;
+
+	fn into_result(self) -> Result<T, E> {
+		self
+	}
+}
+
+impl<'a, T, E> AsResult for &'a Result<T, E> {
+	type Ok = &'a T;
+	type Err = &'a E;
+
+	fn into_result(self) -> Result<&'a T, &'a E> {
+		self.as_ref()
+	}
+}
+
+/// The first error found by `assert_all_ok!()`.
+pub struct FirstErr<E> {
+	/// The index of the first error.
+	pub index: usize,
+
+	/// The first error.
+	pub error: E,
+
+	/// The total number of errors, if the remaining results were checked.
+	pub total: Option<usize>,
+}
+
+/// Collect the `Ok` values of an iterator of results, stopping at the first error.
+///
+/// If `count_errors` is true, the remaining results are consumed to count the total number of errors.
+pub fn check<I, R>(results: I, count_errors: bool) -> Result<Vec<R::Ok>, FirstErr<R::Err>>
+where
+	I: IntoIterator<Item = R>,
+	R: AsResult,
+{
+	let mut values = Vec::new();
+	let mut results = results.into_iter().enumerate();
+	for (index, result) in &mut results {
+		match result.into_result() {
+			Ok(value) => values.push(value),
+			Err(error) => {
+				let total = count_errors.then(|| 1 + results.map(|(_, result)| result.into_result()).filter(Result::is_err).count());
+				return Err(FirstErr { index, error, total });
+			},
+		}
+	}
+	Ok(values)
+}
+
+#[test]
+fn test_check() {
+	use crate::{assert, let_assert};
+	let results: Vec<Result<i32, &str>> = vec![Ok(1), Err("a"), Ok(3), Err("b"), Err("c")];
+	let_assert!(Err(first) = check(&results, false));
+	assert!(first.index == 1);
+	assert!(*first.error == "a");
+	assert!(first.total == None);
+
+	let_assert!(Err(first) = check(results.clone(), true));
+	assert!(first.error == "a");
+	assert!(first.total == Some(3));
+
+	let_assert!(Ok(values) = check(vec![Ok::<_, ()>(1), Ok(2)], true));
+	assert!(values == [1, 2]);
+}
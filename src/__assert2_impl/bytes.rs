@@ -0,0 +1,115 @@
+/// Wrapper to get the contents of a byte buffer operand, using autoref specialization.
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+pub trait IsBytes<'a> {
+	fn __assert2_bytes(&self) -> Option<&'a [u8]>;
+}
+
+pub trait IsNotBytes<'a> {
+	fn __assert2_bytes(&self) -> Option<&'a [u8]> {
+		None
+	}
+}
+
+impl<'a, T: ByteBuffer + ?Sized> IsBytes<'a> for &Wrap<'a, T> {
+	fn __assert2_bytes(&self) -> Option<&'a [u8]> {
+		self.0.as_bytes()
+	}
+}
+
+impl<'a, T: ?Sized> IsNotBytes<'a> for Wrap<'a, T> {}
+
+/// A buffer of integers that is printed as a hexdump if the integers are bytes.
+///
+/// This is implemented for buffers of all integer types, and not just for `u8`,
+/// so that it does not influence the type inference of integer literals in the buffer.
+/// It is not implemented for strings, even though they can be viewed as bytes.
+pub trait ByteBuffer {
+	/// Get the contents of the buffer, if it is a buffer of `u8`.
+	fn as_bytes(&self) -> Option<&[u8]>;
+}
+
+/// An element of a [`ByteBuffer`].
+pub trait Element: Sized + Clone {
+	/// Get the slice as bytes, if `Self` is `u8`.
+	fn slice_as_bytes(slice: &[Self]) -> Option<&[u8]>;
+}
+
+impl Element for u8 {
+	fn slice_as_bytes(slice: &[Self]) -> Option<&[u8]> {
+		Some(slice)
+	}
+}
+
+macro_rules! impl_element {
+	($($type:ty),*) => {
+		$(
+			impl Element for $type {
+				fn slice_as_bytes(_slice: &[Self]) -> Option<&[u8]> {
+					None
+				}
+			}
+		)*
+	};
+}
+
+impl_element!(u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<T: Element> ByteBuffer for [T] {
+	fn as_bytes(&self) -> Option<&[u8]> {
+		T::slice_as_bytes(self)
+	}
+}
+
+impl<T: Element, const N: usize> ByteBuffer for [T; N] {
+	fn as_bytes(&self) -> Option<&[u8]> {
+		T::slice_as_bytes(self)
+	}
+}
+
+impl<T: Element> ByteBuffer for Vec<T> {
+	fn as_bytes(&self) -> Option<&[u8]> {
+		T::slice_as_bytes(self)
+	}
+}
+
+impl<T: Element> ByteBuffer for Box<[T]> {
+	fn as_bytes(&self) -> Option<&[u8]> {
+		T::slice_as_bytes(self)
+	}
+}
+
+impl<T: Element> ByteBuffer for std::borrow::Cow<'_, [T]> {
+	fn as_bytes(&self) -> Option<&[u8]> {
+		T::slice_as_bytes(self)
+	}
+}
+
+impl<T: ByteBuffer + ?Sized> ByteBuffer for &T {
+	fn as_bytes(&self) -> Option<&[u8]> {
+		(**self).as_bytes()
+	}
+}
+
+impl<T: ByteBuffer + ?Sized> ByteBuffer for &mut T {
+	fn as_bytes(&self) -> Option<&[u8]> {
+		(**self).as_bytes()
+	}
+}
+
+#[test]
+#[allow(clippy::needless_borrow)]
+fn test_bytes() {
+	use crate::assert;
+	assert!((&&Wrap(&vec![1u8, 2])).__assert2_bytes() == Some(&[1, 2][..]));
+	assert!((&&Wrap(&b"ab")).__assert2_bytes() == Some(&b"ab"[..]));
+	assert!((&&Wrap(&&b"ab"[..])).__assert2_bytes() == Some(&b"ab"[..]));
+	assert!((&&Wrap("ab")).__assert2_bytes().is_none());
+	assert!((&&Wrap(&vec![1u16, 2])).__assert2_bytes().is_none());
+	assert!((&&Wrap(&vec![String::new()])).__assert2_bytes().is_none());
+
+	// Integer literals are not inferred to be bytes.
+	let literals = vec![1, 2];
+	assert!((&&Wrap(&literals)).__assert2_bytes().is_none());
+	assert!(std::mem::size_of_val(&literals[0]) == 4);
+}
@@ -0,0 +1,104 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The maximum number of items recorded for a single checkpoint.
+const MAX_ITEMS: usize = 16;
+
+thread_local! {
+	/// Stack of recorded checkpoints, one entry for each assertion that is currently being evaluated.
+	///
+	/// Each entry is tagged with the ID of its [`Scope`].
+	static CHECKPOINTS: RefCell<Vec<(usize, Vec<Checkpoint>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The ID of the next [`Scope`].
+static NEXT_SCOPE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The recorded values of a named checkpoint.
+pub struct Checkpoint {
+	/// The name of the checkpoint.
+	pub name: &'static str,
+
+	/// The `Debug` output of the recorded items.
+	pub items: Vec<String>,
+
+	/// True if there were more items than could be recorded.
+	pub truncated: bool,
+}
+
+/// Scope guard that enables recording of checkpoints while an assertion is being evaluated.
+///
+/// The expression of an assertion may contain `.await`,
+/// so scopes of different assertions can be interleaved or dropped on a different thread.
+/// That is why a scope removes its own entry instead of the innermost one.
+pub struct Scope {
+	id: usize,
+}
+
+impl Scope {
+	/// Start recording checkpoints for a new assertion.
+	pub fn enter() -> Self {
+		let id = NEXT_SCOPE_ID.fetch_add(1, Ordering::Relaxed);
+		CHECKPOINTS.with(|stack| stack.borrow_mut().push((id, Vec::new())));
+		Self { id }
+	}
+}
+
+impl Drop for Scope {
+	fn drop(&mut self) {
+		CHECKPOINTS.with(|stack| stack.borrow_mut().retain(|(id, _)| *id != self.id));
+	}
+}
+
+/// Record an item for a checkpoint of the innermost assertion that is being evaluated.
+///
+/// If no assertion is being evaluated, the item is ignored.
+pub fn record(name: &'static str, item: &dyn std::fmt::Debug) {
+	CHECKPOINTS.with(|stack| {
+		let mut stack = stack.borrow_mut();
+		let Some((_, checkpoints)) = stack.last_mut() else {
+			return;
+		};
+
+		let index = match checkpoints.iter().position(|x| x.name == name) {
+			Some(index) => index,
+			None => {
+				checkpoints.push(Checkpoint { name, items: Vec::new(), truncated: false });
+				checkpoints.len() - 1
+			},
+		};
+		let checkpoint = &mut checkpoints[index];
+		if checkpoint.items.len() < MAX_ITEMS {
+			checkpoint.items.push(format!("{item:?}"));
+		} else {
+			checkpoint.truncated = true;
+		}
+	})
+}
+
+/// Take the recorded checkpoints of the innermost assertion that is being evaluated.
+pub fn take() -> Vec<Checkpoint> {
+	CHECKPOINTS.with(|stack| {
+		stack.borrow_mut()
+			.last_mut()
+			.map(|(_, checkpoints)| std::mem::take(checkpoints))
+			.unwrap_or_default()
+	})
+}
+
+#[test]
+fn test_scope_drop_out_of_order() {
+	use crate::assert;
+	// Take the IDs outside of the assertions, since the assertions enter a scope themselves.
+	let ids = || CHECKPOINTS.with(|stack| stack.borrow().iter().map(|(id, _)| *id).collect::<Vec<_>>());
+	let first = Scope::enter();
+	let second = Scope::enter();
+	let before = ids();
+	drop(first);
+	let after_first = ids();
+	drop(second);
+	let after_second = ids();
+	assert!(before.len() == 2);
+	assert!(after_first == [before[1]]);
+	assert!(after_second.is_empty());
+}
@@ -0,0 +1,36 @@
+use std::fmt::Debug;
+
+/// Wrapper to copy a value only if it implements `Copy` and `Debug`, using autoref specialization.
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+pub trait IsCopy<T> {
+	fn __assert2_copy_value(&self) -> Option<T>;
+}
+
+pub trait IsNotCopy {
+	fn __assert2_copy_value(&self) -> Option<NotCopy> {
+		None
+	}
+}
+
+impl<T: Copy + Debug> IsCopy<T> for &Wrap<'_, T> {
+	fn __assert2_copy_value(&self) -> Option<T> {
+		Some(*self.0)
+	}
+}
+
+impl<T: ?Sized> IsNotCopy for Wrap<'_, T> {}
+
+/// Placeholder for a value that could not be copied.
+#[derive(Debug)]
+pub enum NotCopy {}
+
+#[test]
+#[allow(clippy::needless_borrow)]
+fn test_copy_value() {
+	use crate::assert;
+	let number = 5;
+	let string = String::from("foo");
+	assert!((&&Wrap(&number)).__assert2_copy_value() == Some(5));
+	assert!(let None = (&&Wrap(&string)).__assert2_copy_value());
+}
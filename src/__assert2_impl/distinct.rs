@@ -0,0 +1,22 @@
+/// Get the indices of all pairs of values that compare equal.
+///
+/// Each pair is reported once, with the lowest index first.
+pub fn equal_pairs<T: PartialEq + ?Sized>(values: &[&T]) -> Vec<(usize, usize)> {
+	let mut pairs = Vec::new();
+	for (i, a) in values.iter().enumerate() {
+		for (j, b) in values.iter().enumerate().skip(i + 1) {
+			if a == b {
+				pairs.push((i, j));
+			}
+		}
+	}
+	pairs
+}
+
+#[test]
+fn test_equal_pairs() {
+	use crate::assert;
+	assert!(equal_pairs(&[&1, &2, &3]).is_empty());
+	assert!(equal_pairs::<i32>(&[]).is_empty());
+	assert!(equal_pairs(&[&1, &2, &1, &2, &1]) == [(0, 2), (0, 4), (1, 3), (2, 4)]);
+}
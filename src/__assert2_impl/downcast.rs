@@ -0,0 +1,146 @@
+use std::any::{Any, TypeId};
+use std::error::Error;
+
+/// A type-erased value that can describe its concrete type.
+pub trait Downcast {
+	/// Describe the concrete type of the value, as far as it can be determined.
+	fn __assert2_actual(&self) -> ActualType;
+}
+
+/// A type-erased value that can be downcast to `T`.
+pub trait DowncastTo<T> {
+	fn __assert2_downcast_ref(&self) -> Option<&T>;
+}
+
+/// Get a reference to the value to downcast from a reference.
+pub trait AsDowncastRef<'a> {
+	type Target: Downcast + ?Sized;
+
+	fn __assert2_as_downcast(self) -> &'a Self::Target;
+}
+
+/// Get a reference to the value to downcast from a `Box`.
+pub trait AsDowncastBox {
+	type Target: Downcast + ?Sized;
+
+	fn __assert2_as_downcast(&self) -> &Self::Target;
+}
+
+impl<'a, D: Downcast + ?Sized> AsDowncastRef<'a> for &'a D {
+	type Target = D;
+
+	fn __assert2_as_downcast(self) -> &'a D {
+		self
+	}
+}
+
+impl<D: Downcast + ?Sized> AsDowncastBox for Box<D> {
+	type Target = D;
+
+	fn __assert2_as_downcast(&self) -> &D {
+		self
+	}
+}
+
+/// What is known about the concrete type of a value that could not be downcast.
+#[derive(Debug)]
+pub struct ActualType {
+	/// The name of the type, if it is one of the well-known types.
+	pub name: Option<&'static str>,
+
+	/// The type ID of the value, if it is available.
+	pub type_id: Option<TypeId>,
+
+	/// The `Debug` representation of the value, if it is available.
+	pub value: Option<String>,
+}
+
+/// Try to downcast a value to a list of well-known types, and return the name and `Debug` representation of the first match.
+macro_rules! well_known {
+	($value:expr, $($type:ty),* $(,)?) => {
+		None
+		$(.or_else(|| $value.downcast_ref::<$type>().map(|x| (std::any::type_name::<$type>(), format!("{x:?}")))))*
+	};
+}
+
+/// Describe the type of a `dyn Any`.
+fn describe_any(value: &dyn Any) -> ActualType {
+	let known: Option<(&'static str, String)> = well_known!(value,
+		String, &'static str, Box<str>,
+		bool, char,
+		u8, u16, u32, u64, u128, usize,
+		i8, i16, i32, i64, i128, isize,
+		f32, f64,
+		(),
+	);
+	ActualType {
+		name: known.as_ref().map(|x| x.0),
+		type_id: Some(Any::type_id(value)),
+		value: known.map(|x| x.1),
+	}
+}
+
+/// Describe the type of a `dyn Error`.
+fn describe_error(error: &(dyn Error + 'static)) -> ActualType {
+	let known: Option<(&'static str, String)> = well_known!(error,
+		std::io::Error,
+		std::fmt::Error,
+		std::num::ParseIntError,
+		std::num::ParseFloatError,
+		std::num::TryFromIntError,
+		std::str::ParseBoolError,
+		std::str::Utf8Error,
+		std::string::FromUtf8Error,
+		std::char::ParseCharError,
+		std::net::AddrParseError,
+	);
+	ActualType {
+		name: known.map(|x| x.0),
+		type_id: None,
+		value: Some(format!("{error:?}")),
+	}
+}
+
+macro_rules! impl_downcast {
+	($describe:ident, $bound:path, $($type:ty),*) => {
+		$(
+			impl Downcast for $type {
+				fn __assert2_actual(&self) -> ActualType {
+					$describe(self)
+				}
+			}
+
+			impl<T: $bound + 'static> DowncastTo<T> for $type {
+				fn __assert2_downcast_ref(&self) -> Option<&T> {
+					self.downcast_ref()
+				}
+			}
+		)*
+	};
+}
+
+impl_downcast!(describe_any, Any, dyn Any, dyn Any + Send, dyn Any + Send + Sync);
+impl_downcast!(describe_error, Error, dyn Error, dyn Error + Send, dyn Error + Send + Sync);
+
+#[test]
+fn test_describe() {
+	use crate::assert;
+	let value: Box<dyn Any> = Box::new(String::from("foo"));
+	let actual = value.__assert2_as_downcast().__assert2_actual();
+	assert!(actual.name == Some("alloc::string::String"));
+	assert!(actual.type_id == Some(TypeId::of::<String>()));
+	assert!(actual.value.as_deref() == Some("\"foo\""));
+
+	struct Unknown;
+	let value: Box<dyn Any + Send> = Box::new(Unknown);
+	let actual = value.__assert2_as_downcast().__assert2_actual();
+	assert!(actual.name == None);
+	assert!(actual.type_id == Some(TypeId::of::<Unknown>()));
+	assert!(actual.value == None);
+
+	let error = "x".parse::<i32>().unwrap_err();
+	let error: &dyn Error = &error;
+	let actual = error.__assert2_as_downcast().__assert2_actual();
+	assert!(actual.name == Some("core::num::error::ParseIntError"));
+	assert!(actual.value.as_deref() == Some("ParseIntError { kind: InvalidDigit }"));
+}
@@ -0,0 +1,42 @@
+use std::error::Error;
+
+/// Wrapper to select the right way to get a `&dyn Error` from a value.
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+/// Get a `&dyn Error` from a value that implements `Error`.
+pub trait IsError<'a> {
+	fn __assert2_as_error(&self) -> &'a (dyn Error + 'static);
+}
+
+/// Get a `&dyn Error` from a value that implements `AsRef<dyn Error>`, like `Box<dyn Error>` or `anyhow::Error`.
+pub trait IsErrorRef<'a> {
+	fn __assert2_as_error(&self) -> &'a (dyn Error + 'static);
+}
+
+/// Get a `&dyn Error` from a value that implements `AsRef<dyn Error + Send + Sync>`, like `Box<dyn Error + Send + Sync>`.
+pub trait IsSendSyncErrorRef<'a> {
+	fn __assert2_as_error(&self) -> &'a (dyn Error + 'static);
+}
+
+impl<'a, T: Error + 'static> IsError<'a> for &&Wrap<'a, T> {
+	fn __assert2_as_error(&self) -> &'a (dyn Error + 'static) {
+		self.0
+	}
+}
+
+impl<'a, T: AsRef<dyn Error + 'static> + ?Sized> IsErrorRef<'a> for &Wrap<'a, T> {
+	fn __assert2_as_error(&self) -> &'a (dyn Error + 'static) {
+		self.0.as_ref()
+	}
+}
+
+impl<'a, T: AsRef<dyn Error + Send + Sync + 'static> + ?Sized> IsSendSyncErrorRef<'a> for Wrap<'a, T> {
+	fn __assert2_as_error(&self) -> &'a (dyn Error + 'static) {
+		self.0.as_ref()
+	}
+}
+
+/// Get an error and all its sources, starting with the error itself.
+pub fn chain<'a>(error: &'a (dyn Error + 'static)) -> Vec<&'a (dyn Error + 'static)> {
+	std::iter::successors(Some(error), |&error| error.source()).collect()
+}
@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The reason a golden file comparison failed.
+pub enum GoldenMismatch {
+	/// The golden file could not be read.
+	Unreadable(std::io::Error),
+
+	/// The golden file could not be updated.
+	Unwritable(std::io::Error),
+
+	/// The golden file has different contents.
+	Different(String),
+
+	/// The snapshot file does not exist.
+	Missing,
+}
+
+/// Resolve the path of a golden file relative to the manifest directory of the calling crate.
+pub fn resolve_path(manifest_dir: &str, path: impl AsRef<Path>) -> PathBuf {
+	Path::new(manifest_dir).join(path)
+}
+
+thread_local! {
+	/// The number of unnamed snapshots taken so far by each function on the current thread.
+	static SNAPSHOT_COUNTS: RefCell<HashMap<&'static str, usize>> = RefCell::new(HashMap::new());
+}
+
+/// Get the path of the function that contains an item, given the item.
+///
+/// The item should be a function defined directly in the function body.
+/// Closures around the item are not part of the returned path.
+pub fn function_path<T>(_item: T) -> &'static str {
+	let mut path = std::any::type_name::<T>();
+	if let Some((parent, _item)) = path.rsplit_once("::") {
+		path = parent;
+	}
+	while let Some(parent) = path.strip_suffix("::{{closure}}") {
+		path = parent;
+	}
+	path
+}
+
+/// Get the path of a snapshot file.
+///
+/// Snapshots are stored in the `tests/snapshots` directory of the calling crate.
+/// If no name is given, the name is derived from the path of the function that contains the assertion.
+/// The second and later unnamed snapshots taken by the same function get a number appended to the name.
+pub fn snapshot_path(manifest_dir: &str, function: &'static str, name: Option<&str>) -> PathBuf {
+	let name = match name {
+		Some(name) => name.to_owned(),
+		None => {
+			let count = SNAPSHOT_COUNTS.with(|counts| {
+				let mut counts = counts.borrow_mut();
+				let count = counts.entry(function).or_insert(0);
+				*count += 1;
+				*count
+			});
+			let name = function.replace("::", "__");
+			if count == 1 {
+				name
+			} else {
+				format!("{name}-{count}")
+			}
+		},
+	};
+	Path::new(manifest_dir).join("tests").join("snapshots").join(format!("{name}.snap"))
+}
+
+/// Compare a snapshot with the contents of a snapshot file.
+///
+/// If the snapshot file does not exist, the comparison fails,
+/// unless the `ASSERT2_UPDATE_GOLDEN` environment variable is set.
+/// Otherwise, this behaves exactly like [`compare()`].
+pub fn compare_snapshot(actual: &str, path: &Path) -> Result<(), GoldenMismatch> {
+	if !should_update() && !path.exists() {
+		return Err(GoldenMismatch::Missing);
+	}
+	compare(actual, path)
+}
+
+/// Compare a string with the contents of a golden file.
+///
+/// If the `ASSERT2_UPDATE_GOLDEN` environment variable is set,
+/// the golden file is overwritten with `actual` instead.
+pub fn compare(actual: &str, path: &Path) -> Result<(), GoldenMismatch> {
+	if should_update() {
+		return update(actual, path).map_err(GoldenMismatch::Unwritable);
+	}
+
+	let expected = std::fs::read_to_string(path).map_err(GoldenMismatch::Unreadable)?;
+	if expected == actual {
+		Ok(())
+	} else {
+		Err(GoldenMismatch::Different(expected))
+	}
+}
+
+/// Overwrite the golden file, creating parent directories as needed.
+fn update(actual: &str, path: &Path) -> std::io::Result<()> {
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+	std::fs::write(path, actual)
+}
+
+/// Check if the `ASSERT2_UPDATE_GOLDEN` environment variable is set to a true-like value.
+fn should_update() -> bool {
+	match std::env::var_os("ASSERT2_UPDATE_GOLDEN") {
+		None => false,
+		Some(value) => !(value.is_empty() || value == "0" || value.eq_ignore_ascii_case("false") || value.eq_ignore_ascii_case("no")),
+	}
+}
+
+#[test]
+fn test_function_path() {
+	use crate::assert;
+	fn item() {}
+	assert!(function_path(item) == "assert2::__assert2_impl::golden::test_function_path");
+	let closure = || function_path(item);
+	assert!(closure() == "assert2::__assert2_impl::golden::test_function_path");
+}
@@ -0,0 +1,138 @@
+use std::fmt::{Debug, Formatter};
+
+pub use super::print::options::IntegerFormat;
+use super::print::options::AssertOptions;
+
+/// Wrapper to detect if a value is a primitive integer, using autoref specialization.
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+pub trait IsInteger {
+	fn __assert2_integer(&self) -> IntegerTag {
+		IntegerTag
+	}
+}
+
+pub trait IsNotInteger {
+	fn __assert2_integer(&self) -> NotIntegerTag {
+		NotIntegerTag
+	}
+}
+
+impl<T: Integer> IsInteger for &Wrap<'_, T> {}
+impl<T: ?Sized> IsNotInteger for Wrap<'_, T> {}
+
+pub struct IntegerTag;
+pub struct NotIntegerTag;
+
+impl IntegerTag {
+	pub fn wrap<T: Integer>(self, v: &T) -> Option<&dyn Integer> {
+		Some(v)
+	}
+}
+
+impl NotIntegerTag {
+	pub fn wrap<T: ?Sized>(self, _v: &T) -> Option<&dyn Integer> {
+		None
+	}
+}
+
+/// A primitive integer that can be printed in hexadecimal or binary.
+pub trait Integer {
+	/// Format the integer in hexadecimal, padded to the width of the type.
+	fn fmt_hex(&self, f: &mut Formatter) -> std::fmt::Result;
+
+	/// Format the integer in binary, padded to the width of the type.
+	fn fmt_binary(&self, f: &mut Formatter) -> std::fmt::Result;
+
+	/// Get the width of the type in bits.
+	fn width(&self) -> u32;
+
+	/// Get the bits of the integer, using the two's complement representation for negative values.
+	fn to_bits(&self) -> u128;
+}
+
+macro_rules! impl_integer {
+	($($type:ty),*) => {
+		$(
+			impl Integer for $type {
+				fn fmt_hex(&self, f: &mut Formatter) -> std::fmt::Result {
+					write!(f, "{:#0width$x}", self, width = 2 + 2 * std::mem::size_of::<Self>())
+				}
+
+				fn fmt_binary(&self, f: &mut Formatter) -> std::fmt::Result {
+					write!(f, "{:#0width$b}", self, width = 2 + 8 * std::mem::size_of::<Self>())
+				}
+
+				fn width(&self) -> u32 {
+					<$type>::BITS
+				}
+
+				#[allow(clippy::unnecessary_cast)]
+				fn to_bits(&self) -> u128 {
+					(*self as u128) & (u128::MAX >> (128 - <$type>::BITS))
+				}
+			}
+		)*
+	};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<T: Integer + ?Sized> Integer for &T {
+	fn fmt_hex(&self, f: &mut Formatter) -> std::fmt::Result {
+		(**self).fmt_hex(f)
+	}
+
+	fn fmt_binary(&self, f: &mut Formatter) -> std::fmt::Result {
+		(**self).fmt_binary(f)
+	}
+
+	fn width(&self) -> u32 {
+		(**self).width()
+	}
+
+	fn to_bits(&self) -> u128 {
+		(**self).to_bits()
+	}
+}
+
+/// Wrapper to print an operand in the integer format selected by the options, if it is an integer.
+pub struct Radix<'a, W> {
+	/// The operand, if it is an integer.
+	pub integer: Option<&'a dyn Integer>,
+
+	/// Override for the integer format of the assertion, if any.
+	pub format: Option<IntegerFormat>,
+
+	/// The operand, wrapped to be printed with `Debug` if it is not printed as an integer.
+	pub value: W,
+}
+
+impl<W: Debug> Debug for Radix<'_, W> {
+	fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+		let format = self.format.unwrap_or_else(|| AssertOptions::get().integers);
+		match (self.integer, format) {
+			(Some(integer), IntegerFormat::Hex) => integer.fmt_hex(f),
+			(Some(integer), IntegerFormat::Binary) => integer.fmt_binary(f),
+			_ => self.value.fmt(f),
+		}
+	}
+}
+
+#[test]
+#[allow(clippy::needless_borrow)]
+fn test_integer_format() {
+	use crate::assert;
+	let format = |value: &dyn Debug| format!("{value:?}");
+	fn radix(integer: Option<&dyn Integer>, format: IntegerFormat) -> Radix<'_, &str> {
+		Radix { integer, format: Some(format), value: "value" }
+	}
+
+	assert!(format(&radix((&&Wrap(&0x4fu8)).__assert2_integer().wrap(&0x4fu8), IntegerFormat::Hex)) == "0x4f");
+	assert!(format(&radix((&&Wrap(&0x4fu16)).__assert2_integer().wrap(&0x4fu16), IntegerFormat::Hex)) == "0x004f");
+	assert!(format(&radix((&&Wrap(&-1i8)).__assert2_integer().wrap(&-1i8), IntegerFormat::Hex)) == "0xff");
+	assert!(format(&radix((&&Wrap(&5u8)).__assert2_integer().wrap(&5u8), IntegerFormat::Binary)) == "0b00000101");
+	assert!(format(&radix((&&Wrap(&&5u8)).__assert2_integer().wrap(&&5u8), IntegerFormat::Binary)) == "0b00000101");
+	assert!(format(&radix((&&Wrap(&5u8)).__assert2_integer().wrap(&5u8), IntegerFormat::Decimal)) == "\"value\"");
+	assert!(format(&radix((&&Wrap("5")).__assert2_integer().wrap("5"), IntegerFormat::Hex)) == "\"value\"");
+}
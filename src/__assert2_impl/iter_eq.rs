@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+
+/// The number of elements to show before and after the first difference.
+const CONTEXT: usize = 3;
+
+/// The first difference between two iterators.
+#[derive(Debug)]
+pub struct IterMismatch<L, R> {
+	/// The index of the first element that differs.
+	pub index: usize,
+
+	/// The index of the first element in the windows.
+	pub start: usize,
+
+	/// The elements of the left iterator around the first difference.
+	pub left: Vec<L>,
+
+	/// The elements of the right iterator around the first difference.
+	pub right: Vec<R>,
+
+	/// The total number of elements produced by the left iterator.
+	pub left_len: usize,
+
+	/// The total number of elements produced by the right iterator.
+	pub right_len: usize,
+}
+
+/// Compare two iterators element by element.
+///
+/// Returns `None` if both iterators produce equal elements and have the same length.
+/// Otherwise, both iterators are consumed completely to determine their lengths.
+pub fn compare<L, R>(left: L, right: R) -> Option<IterMismatch<L::Item, R::Item>>
+where
+	L: IntoIterator,
+	R: IntoIterator,
+	L::Item: PartialEq<R::Item>,
+{
+	let mut left = left.into_iter();
+	let mut right = right.into_iter();
+	let mut left_before = VecDeque::with_capacity(CONTEXT + 1);
+	let mut right_before = VecDeque::with_capacity(CONTEXT + 1);
+	let mut index = 0;
+
+	loop {
+		let (a, b) = match (left.next(), right.next()) {
+			(None, None) => return None,
+			(Some(a), Some(b)) if a == b => (a, b),
+			(a, b) => {
+				let start = index - left_before.len();
+				let (left, left_len) = window(left_before, a, left, index);
+				let (right, right_len) = window(right_before, b, right, index);
+				return Some(IterMismatch { index, start, left, right, left_len, right_len });
+			},
+		};
+		left_before.push_back(a);
+		right_before.push_back(b);
+		if left_before.len() > CONTEXT {
+			left_before.pop_front();
+			right_before.pop_front();
+		}
+		index += 1;
+	}
+}
+
+/// Collect the window around the first difference and count the total length of one side.
+fn window<T>(before: VecDeque<T>, first: Option<T>, mut rest: impl Iterator<Item = T>, index: usize) -> (Vec<T>, usize) {
+	let mut window = Vec::from(before);
+	let Some(first) = first else {
+		return (window, index);
+	};
+	let before = window.len();
+	window.push(first);
+	window.extend(rest.by_ref().take(CONTEXT));
+	let len = index + window.len() - before + rest.count();
+	(window, len)
+}
+
+#[test]
+fn test_compare() {
+	use crate::{assert, let_assert};
+	assert!(compare([1, 2, 3], vec![1, 2, 3]).is_none());
+	assert!(compare(Vec::<i32>::new(), []).is_none());
+
+	let_assert!(Some(mismatch) = compare(0..10, [0, 1, 2, 3, 4, 9, 6, 7, 8, 9]));
+	assert!(mismatch.index == 5);
+	assert!(mismatch.start == 2);
+	assert!(mismatch.left == [2, 3, 4, 5, 6, 7, 8]);
+	assert!(mismatch.right == [2, 3, 4, 9, 6, 7, 8]);
+	assert!(mismatch.left_len == 10);
+	assert!(mismatch.right_len == 10);
+
+	let_assert!(Some(mismatch) = compare(0..2, 0..20));
+	assert!(mismatch.index == 2);
+	assert!(mismatch.start == 0);
+	assert!(mismatch.left == [0, 1]);
+	assert!(mismatch.right == [0, 1, 2, 3, 4, 5]);
+	assert!(mismatch.left_len == 2);
+	assert!(mismatch.right_len == 20);
+
+	let_assert!(Some(mismatch) = compare([1], []));
+	assert!(mismatch.index == 0);
+	assert!(mismatch.left == [1]);
+	assert!(mismatch.right.is_empty());
+	assert!(mismatch.left_len == 1);
+	assert!(mismatch.right_len == 0);
+}
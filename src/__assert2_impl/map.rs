@@ -0,0 +1,93 @@
+/// Get the key and value of a map entry.
+///
+/// This is implemented for `(&K, &V)`, which is what maps produce when iterated by reference,
+/// and for `&(K, V)`, which is what a slice of tuples produces.
+pub trait AsEntry {
+	type Key;
+	type Value;
+	fn as_entry(&self) -> (&Self::Key, &Self::Value);
+}
+
+impl<K, V> AsEntry for (&K, &V) {
+	type Key = K;
+	type Value = V;
+
+	fn as_entry(&self) -> (&K, &V) {
+		(self.0, self.1)
+	}
+}
+
+impl<K, V> AsEntry for &(K, V) {
+	type Key = K;
+	type Value = V;
+
+	fn as_entry(&self) -> (&K, &V) {
+		(&self.0, &self.1)
+	}
+}
+
+/// The differences between two maps.
+pub struct MapDiff<'a, K, L, R> {
+	/// The entries with a key that only occurs in the left map.
+	pub only_left: Vec<(&'a K, &'a L)>,
+
+	/// The entries with a key that only occurs in the right map.
+	pub only_right: Vec<(&'a K, &'a R)>,
+
+	/// The keys that occur in both maps with different values.
+	pub different: Vec<(&'a K, &'a L, &'a R)>,
+}
+
+impl<K, L, R> MapDiff<'_, K, L, R> {
+	/// Check if the maps are equal.
+	pub fn is_empty(&self) -> bool {
+		self.only_left.is_empty() && self.only_right.is_empty() && self.different.is_empty()
+	}
+}
+
+/// Compare the entries of two maps.
+///
+/// Keys are compared with [`PartialEq`], so this does not require the keys to implement [`Hash`](std::hash::Hash) or [`Ord`].
+pub fn compare<'a, K, L, R>(left: &'a [L], right: &'a [R]) -> MapDiff<'a, K, L::Value, R::Value>
+where
+	K: PartialEq,
+	L: AsEntry<Key = K>,
+	R: AsEntry<Key = K>,
+	L::Value: PartialEq<R::Value>,
+{
+	let mut only_left = Vec::new();
+	let mut different = Vec::new();
+	for (key, left_value) in left.iter().map(AsEntry::as_entry) {
+		match right.iter().map(AsEntry::as_entry).find(|(right_key, _)| *right_key == key) {
+			None => only_left.push((key, left_value)),
+			Some((_, right_value)) => {
+				if left_value != right_value {
+					different.push((key, left_value, right_value));
+				}
+			},
+		}
+	}
+
+	let only_right = right.iter()
+		.map(AsEntry::as_entry)
+		.filter(|(key, _)| !left.iter().any(|entry| entry.as_entry().0 == *key))
+		.collect();
+
+	MapDiff { only_left, only_right, different }
+}
+
+#[test]
+fn test_compare() {
+	use crate::assert;
+	let left: Vec<_> = [(1, "a"), (2, "b")].iter().collect();
+	let right: Vec<_> = [(2, "b"), (1, "a")].iter().collect();
+	let diff = compare(&left, &right);
+	assert!(diff.is_empty());
+
+	let left: Vec<_> = [(1, "a"), (2, "b"), (3, "c")].iter().collect();
+	let right: Vec<_> = [(2, "x"), (3, "c"), (4, "d")].iter().collect();
+	let diff = compare(&left, &right);
+	assert!(diff.only_left == [(&1, &"a")]);
+	assert!(diff.only_right == [(&4, &"d")]);
+	assert!(diff.different == [(&2, &"b", &"x")]);
+}
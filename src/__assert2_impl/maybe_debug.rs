@@ -1,35 +1,139 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
+
+use super::print::options::AssertOptions;
+use crate::AssertFormat;
 
 pub struct Wrap<'a, T: ?Sized>(pub &'a T);
 
+pub trait IsAssertFormat {
+	fn __assert2_maybe_debug(&self) -> AssertFormatTag {
+		AssertFormatTag
+	}
+}
+
+pub trait IsDebugAndDisplay {
+	fn __assert2_maybe_debug(&self) -> DebugAndDisplayTag {
+		DebugAndDisplayTag
+	}
+}
+
 pub trait IsDebug {
 	fn __assert2_maybe_debug(&self) -> DebugTag {
 		DebugTag
 	}
 }
 
+pub trait IsDisplay {
+	fn __assert2_maybe_debug(&self) -> DisplayTag {
+		DisplayTag
+	}
+}
+
 pub trait IsMaybeNotDebug {
 	fn __assert2_maybe_debug(&self) -> MaybeNotDebugTag {
 		MaybeNotDebugTag
 	}
 }
 
-impl<T: Debug + ?Sized> IsDebug for &Wrap<'_, T> {}
+// Called as `(&&&&Wrap(value)).__assert2_maybe_debug()`, the implementations are found in this order:
+// by value, after one autoref, after one deref, after two derefs and after three derefs.
+impl<T: AssertFormat + ?Sized> IsAssertFormat for &&&Wrap<'_, T> {}
+impl<T: Debug + Display + ?Sized> IsDebugAndDisplay for &&&&Wrap<'_, T> {}
+impl<T: Debug + ?Sized> IsDebug for &&Wrap<'_, T> {}
+impl<T: Display + ?Sized> IsDisplay for &Wrap<'_, T> {}
 impl<T: ?Sized> IsMaybeNotDebug for Wrap<'_, T> {}
 
+pub struct AssertFormatTag;
+pub struct DebugAndDisplayTag;
 pub struct DebugTag;
+pub struct DisplayTag;
 pub struct MaybeNotDebugTag;
 
+impl AssertFormatTag {
+	pub fn wrap<'a, T: ?Sized>(self, v: &'a T) -> AssertFormatAsDebug<'a, T> {
+		AssertFormatAsDebug(v)
+	}
+
+	pub fn wrap_with<'a, T: ?Sized>(self, v: &'a T, _display: Option<bool>) -> AssertFormatAsDebug<'a, T> {
+		AssertFormatAsDebug(v)
+	}
+}
+
+impl DebugAndDisplayTag {
+	pub fn wrap<T: ?Sized>(self, v: &T) -> DebugOrDisplay<'_, T> {
+		self.wrap_with(v, None)
+	}
+
+	/// Wrap the value, overriding the `display` option if `display` is not `None`.
+	pub fn wrap_with<T: ?Sized>(self, v: &T, display: Option<bool>) -> DebugOrDisplay<'_, T> {
+		DebugOrDisplay { value: v, display }
+	}
+}
+
 impl DebugTag {
 	pub fn wrap<T: ?Sized>(self, v: &T) -> &T {
 		v
 	}
+
+	pub fn wrap_with<T: ?Sized>(self, v: &T, _display: Option<bool>) -> &T {
+		v
+	}
+}
+
+impl DisplayTag {
+	pub fn wrap<'a, T: ?Sized>(self, v: &'a T) -> DisplayAsDebug<'a, T> {
+		DisplayAsDebug(v)
+	}
+
+	pub fn wrap_with<'a, T: ?Sized>(self, v: &'a T, _display: Option<bool>) -> DisplayAsDebug<'a, T> {
+		DisplayAsDebug(v)
+	}
 }
 
 impl MaybeNotDebugTag {
 	pub fn wrap<'a, T: ?Sized>(self, v: &'a T) -> MaybeNotDebug<'a, T> {
 		MaybeNotDebug(v)
 	}
+
+	pub fn wrap_with<'a, T: ?Sized>(self, v: &'a T, _display: Option<bool>) -> MaybeNotDebug<'a, T> {
+		MaybeNotDebug(v)
+	}
+}
+
+/// Wrapper to print a value with its `AssertFormat` implementation.
+pub struct AssertFormatAsDebug<'a, T: ?Sized>(&'a T);
+
+impl<T: AssertFormat + ?Sized> Debug for AssertFormatAsDebug<'_, T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		self.0.assert_fmt(f)
+	}
+}
+
+/// Wrapper to print a value that implements both `Debug` and `Display` with the format selected by the options.
+pub struct DebugOrDisplay<'a, T: ?Sized> {
+	value: &'a T,
+
+	/// Override for the `display` option of the assertion, if any.
+	display: Option<bool>,
+}
+
+impl<T: Debug + Display + ?Sized> Debug for DebugOrDisplay<'_, T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		if self.display.unwrap_or_else(|| AssertOptions::get().display) {
+			Display::fmt(self.value, f)
+		} else {
+			Debug::fmt(self.value, f)
+		}
+	}
+}
+
+/// Wrapper to print a value that implements `Display` but not `Debug`.
+pub struct DisplayAsDebug<'a, T: ?Sized>(&'a T);
+
+impl<T: Display + ?Sized> Debug for DisplayAsDebug<'_, T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		Display::fmt(self.0, f)
+	}
 }
 
 pub struct MaybeNotDebug<'a, T: ?Sized>(&'a T);
@@ -1,19 +1,214 @@
+use std::cell::{Cell, RefCell};
+
 pub use assert2_macros::check_impl;
+pub use assert2_macros::duration_impl;
 pub use assert2_macros::let_assert_impl;
+pub use assert2_macros::let_check_impl;
 
+pub mod address;
+pub mod affix;
+pub mod all_ok;
+pub mod bytes;
+pub mod checkpoint;
+pub mod copy_value;
+pub mod distinct;
+pub mod downcast;
+pub mod error_chain;
+pub mod golden;
+pub mod integer;
+pub mod iter_eq;
+pub mod map;
 pub mod maybe_debug;
+pub mod poll;
 pub mod print;
+pub mod recv;
+#[cfg(feature = "serde")]
+pub mod serde_diff;
+#[cfg(feature = "regex")]
+pub mod regex;
+pub mod set;
+pub mod site;
+pub mod structural;
+pub mod text;
+pub mod unordered;
+
+thread_local! {
+	/// The number of failed checks on this thread that have not panicked yet.
+	static PENDING_FAILURES: Cell<usize> = const { Cell::new(0) };
+
+	/// The last failed assertion on this thread that has not been used for a panic message yet.
+	static LAST_FAILURE: RefCell<Option<crate::Failure>> = const { RefCell::new(None) };
+}
+
+/// Remember a failed assertion, to include it in the message of the following panic.
+pub(crate) fn set_last_failure(failure: &crate::Failure) {
+	LAST_FAILURE.with(|x| *x.borrow_mut() = Some(failure.clone()));
+}
+
+/// Get the message for the panic of a failed assertion.
+///
+/// The message starts with `prefix`, followed by the expression and location of the last failed assertion on the current thread.
+/// Test runners that only show the panic message, like IDEs, still show which assertion failed.
+pub fn panic_message(prefix: &str) -> String {
+	match LAST_FAILURE.with(|x| x.borrow_mut().take()) {
+		None => prefix.to_owned(),
+		Some(failure) => format!("{prefix}: {}", describe_failure(&failure)),
+	}
+}
+
+/// Describe a failed assertion on a single line, with its expression, location and custom message.
+pub(crate) fn describe_failure(failure: &crate::Failure) -> String {
+	let mut message = format!(
+		"{}!( {} ) at {}:{}:{}",
+		failure.macro_name(),
+		failure.expression(),
+		failure.file(),
+		failure.line(),
+		failure.column(),
+	);
+	if let Some(custom) = failure.message() {
+		message.push_str(": ");
+		message.push_str(custom);
+	}
+	message
+}
 
 /// Scope guard to panic when a check!() fails.
 ///
 /// The panic is done by a lambda passed to the guard,
 /// so that the line information points to the check!() invocation.
-pub struct FailGuard<T: FnMut()>(pub T);
+pub struct FailGuard<T: FnMut()>(T);
+
+impl<T: FnMut()> FailGuard<T> {
+	/// Create a guard for a failed check, that runs `fail` when it is dropped.
+	pub fn new(fail: T) -> Self {
+		add_pending_failures(1);
+		Self(fail)
+	}
+}
 
 impl<T: FnMut()> Drop for FailGuard<T> {
 	fn drop(&mut self) {
-		if !std::thread::panicking() {
+		remove_pending_failures(1);
+		if should_panic() {
 			(self.0)()
 		}
 	}
 }
+
+/// Get the number of failed checks on the current thread that have not panicked yet.
+pub fn pending_failures() -> usize {
+	PENDING_FAILURES.with(|x| x.get())
+}
+
+/// Count failed checks that will panic later.
+fn add_pending_failures(count: usize) {
+	PENDING_FAILURES.with(|x| x.set(x.get() + count));
+}
+
+/// Stop counting failed checks that panicked or will not panic anymore.
+fn remove_pending_failures(count: usize) {
+	PENDING_FAILURES.with(|x| x.set(x.get().saturating_sub(count)));
+}
+
+/// The variables bound by the pattern of `assert!(let ...)`, as a tuple.
+pub trait Bindings {
+	/// The value that `assert!()` evaluates to.
+	type Value;
+
+	/// Get the value that `assert!()` evaluates to.
+	///
+	/// A single binding is returned without the wrapping tuple.
+	fn into_value(self) -> Self::Value;
+
+	/// Handle a failed assertion.
+	///
+	/// This only returns if there are no bindings and the assertion should not panic.
+	#[track_caller]
+	fn on_failure() -> Self::Value;
+}
+
+impl Bindings for () {
+	type Value = ();
+
+	fn into_value(self) {}
+
+	#[track_caller]
+	fn on_failure() {
+		if should_panic() {
+			panic!("{}", panic_message("assertion failed"));
+		}
+	}
+}
+
+impl<T> Bindings for (T,) {
+	type Value = T;
+
+	fn into_value(self) -> T {
+		self.0
+	}
+
+	#[track_caller]
+	fn on_failure() -> T {
+		panic!("{}", panic_message("assertion failed"));
+	}
+}
+
+macro_rules! impl_bindings {
+	($($name:ident),*) => {
+		impl<$($name),*> Bindings for ($($name,)*) {
+			type Value = Self;
+
+			fn into_value(self) -> Self {
+				self
+			}
+
+			#[track_caller]
+			fn on_failure() -> Self {
+				panic!("{}", panic_message("assertion failed"));
+			}
+		}
+	};
+}
+
+impl_bindings!(A, B);
+impl_bindings!(A, B, C);
+impl_bindings!(A, B, C, D);
+impl_bindings!(A, B, C, D, E);
+impl_bindings!(A, B, C, D, E, F);
+impl_bindings!(A, B, C, D, E, F, G);
+impl_bindings!(A, B, C, D, E, F, G, H);
+impl_bindings!(A, B, C, D, E, F, G, H, I);
+impl_bindings!(A, B, C, D, E, F, G, H, I, J);
+impl_bindings!(A, B, C, D, E, F, G, H, I, J, K);
+impl_bindings!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Get the value of a passed `assert!()`, or panic if it failed.
+///
+/// The panic is subject to the configured [`PanickingPolicy`](crate::PanickingPolicy) only if the assertion has no bindings,
+/// since there is no value to evaluate to otherwise.
+#[track_caller]
+pub fn assert_result<B: Bindings>(result: Result<B, crate::Failure>) -> B::Value {
+	match result {
+		Ok(bindings) => bindings.into_value(),
+		Err(_) => B::on_failure(),
+	}
+}
+
+/// Get the name of the type of a value.
+pub fn type_name_of<T: ?Sized>(_value: &T) -> &'static str {
+	std::any::type_name::<T>()
+}
+
+/// Get the current time using the configured time source.
+pub fn now() -> std::time::Instant {
+	crate::clock::now()
+}
+
+/// Check if a failed assertion should panic.
+///
+/// If the current thread is not panicking, this always returns true.
+/// Otherwise, the result depends on the configured [`PanickingPolicy`](crate::PanickingPolicy).
+pub fn should_panic() -> bool {
+	crate::panicking::should_panic()
+}
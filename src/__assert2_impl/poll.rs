@@ -0,0 +1,54 @@
+use std::task::Poll;
+
+/// Wrapper to select the right way to get a `Poll` value from the argument of `assert_ready!()` and `assert_pending!()`.
+pub struct Wrap<T>(pub T);
+
+/// Get a `Poll` value from an existing `Poll`.
+pub trait PollValue {
+	type Output;
+
+	fn __assert2_poll(self) -> Poll<Self::Output>;
+}
+
+impl<T> PollValue for Wrap<Poll<T>> {
+	type Output = T;
+
+	fn __assert2_poll(self) -> Poll<T> {
+		self.0
+	}
+}
+
+/// Get a `Poll` value by polling a future once.
+///
+/// This trait is only implemented if the `async` feature is enabled.
+pub trait PollFuture {
+	type Output;
+
+	fn __assert2_poll(self) -> Poll<Self::Output>;
+}
+
+#[cfg(feature = "async")]
+impl<F: std::future::Future> PollFuture for Wrap<F> {
+	type Output = F::Output;
+
+	fn __assert2_poll(self) -> Poll<F::Output> {
+		let waker = noop_waker();
+		Box::pin(self.0).as_mut().poll(&mut std::task::Context::from_waker(&waker))
+	}
+}
+
+/// Create a waker that does nothing when woken.
+#[cfg(feature = "async")]
+fn noop_waker() -> std::task::Waker {
+	use std::task::{RawWaker, RawWakerVTable, Waker};
+
+	fn clone(_: *const ()) -> RawWaker {
+		RawWaker::new(std::ptr::null(), &VTABLE)
+	}
+	fn noop(_: *const ()) {}
+
+	static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+	// SAFETY: The vtable functions do not use the data pointer at all.
+	unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
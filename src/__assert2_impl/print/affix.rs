@@ -0,0 +1,37 @@
+use std::fmt::Write;
+
+use super::CheckExpression;
+use crate::__assert2_impl::affix::{Affix, AffixMismatch};
+use crate::diff::write_highlighted_range;
+use crate::theme::Themed;
+
+/// A failed check that a value starts or ends with an expected value.
+pub struct AffixEq<'a> {
+	pub mismatch: &'a AffixMismatch,
+	pub affix: Affix,
+	pub actual_expr: &'a str,
+	pub expected_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for AffixEq<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{actual}, {expected}",
+			actual   = Themed::paint_left(self.actual_expr),
+			expected = Themed::paint_right(self.expected_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		let affix = match self.affix {
+			Affix::Prefix => "prefix",
+			Affix::Suffix => "suffix",
+		};
+		writeln!(buffer, "with actual value:").unwrap();
+		buffer.push_str("  ");
+		write_highlighted_range(buffer, &self.mismatch.actual, self.mismatch.actual_highlight.clone(), crate::theme::left());
+		writeln!(buffer, "\nwith expected {affix}:").unwrap();
+		buffer.push_str("  ");
+		write_highlighted_range(buffer, &self.mismatch.expected, self.mismatch.expected_highlight.clone(), crate::theme::right());
+	}
+}
@@ -0,0 +1,31 @@
+use std::fmt::{Debug, Write};
+use yansi::Paint;
+
+use super::CheckExpression;
+use super::write_expanded_value;
+use crate::__assert2_impl::all_ok::FirstErr;
+use crate::theme::Themed;
+
+/// An iterator of results that contains an error.
+pub struct AllOk<'a, E> {
+	pub first: &'a FirstErr<E>,
+	pub expression: &'a str,
+}
+
+#[rustfmt::skip]
+impl<E: Debug> CheckExpression for AllOk<'_, E> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{}", Themed::paint_left(self.expression)).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with first error at index {}:", self.first.index.bold()).unwrap();
+		write_expanded_value(buffer, &self.first.error, crate::theme::right());
+		if let Some(total) = self.first.total {
+			writeln!(buffer, "with total errors:").unwrap();
+			writeln!(buffer, "  {}", total.bold()).unwrap();
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
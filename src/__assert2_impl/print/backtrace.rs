@@ -0,0 +1,95 @@
+use std::fmt::Write;
+
+/// A frame of a backtrace.
+#[derive(Debug, Eq, PartialEq)]
+struct Frame<'a> {
+	/// The demangled name of the function.
+	symbol: &'a str,
+
+	/// The source location of the frame, if known.
+	location: Option<&'a str>,
+}
+
+/// Capture a backtrace and write the frames of user code.
+///
+/// Frames of the standard library, the test harness and `assert2` itself are left out.
+pub(super) fn write_backtrace(buffer: &mut String) {
+	let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+	let frames = user_frames(&backtrace);
+	writeln!(buffer, "with backtrace:").unwrap();
+	if frames.is_empty() {
+		writeln!(buffer, "  (no frames with debug information)").unwrap();
+	}
+	for (i, frame) in frames.iter().enumerate() {
+		writeln!(buffer, "  {i}: {}", frame.symbol).unwrap();
+		if let Some(location) = frame.location {
+			writeln!(buffer, "       at {location}").unwrap();
+		}
+	}
+}
+
+/// Parse the formatted backtrace and keep only the frames of user code.
+///
+/// The runtime frames before the entry point are cut off at `__rust_begin_short_backtrace`,
+/// just like the standard library does for panic messages.
+fn user_frames(backtrace: &str) -> Vec<Frame<'_>> {
+	let mut frames = Vec::new();
+	let mut lines = backtrace.lines().peekable();
+	while let Some(line) = lines.next() {
+		let Some((_index, symbol)) = line.trim_start().split_once(": ") else {
+			continue;
+		};
+		let location = lines.peek().and_then(|x| x.trim_start().strip_prefix("at "));
+		if location.is_some() {
+			lines.next();
+		}
+		if symbol.contains("__rust_begin_short_backtrace") {
+			break;
+		}
+		// Trait implementations of the standard library for user types are recognized by their location.
+		match location {
+			Some(location) if !is_internal(symbol) && !location.starts_with("/rustc/") => frames.push(Frame { symbol, location: Some(location) }),
+			_ => (),
+		}
+	}
+	frames
+}
+
+/// Check if a symbol belongs to the standard library, the test harness or `assert2` itself.
+fn is_internal(symbol: &str) -> bool {
+	const PREFIXES: &[&str] = &["std::", "core::", "alloc::", "test::", "assert2::__assert2_impl::"];
+	let symbol = symbol.trim_start_matches(['<', '&']);
+	let symbol = symbol.strip_prefix("mut ").unwrap_or(symbol);
+	let symbol = symbol.strip_prefix("dyn ").unwrap_or(symbol);
+	PREFIXES.iter().any(|prefix| symbol.starts_with(prefix))
+}
+
+#[test]
+fn test_user_frames() {
+	use crate::assert;
+	let backtrace = concat!(
+		"   0: std::backtrace::Backtrace::force_capture\n",
+		"             at /rustc/library/std/src/backtrace.rs:312:9\n",
+		"   1: assert2::__assert2_impl::print::FailedCheck<T>::print\n",
+		"             at ./src/__assert2_impl/print/mod.rs:200:3\n",
+		"   2: helpers::check_sorted\n",
+		"             at ./tests/helpers.rs:12:5\n",
+		"   3: helpers::test_sorted::{{closure}}\n",
+		"             at ./tests/helpers.rs:20:3\n",
+		"   4: <F as core::ops::function::FnOnce<()>>::call_once\n",
+		"             at /rustc/library/core/src/ops/function.rs:250:5\n",
+		"   5: <fn() as core::ops::function::FnOnce<()>>::call_once\n",
+		"             at /rustc/59807616e1fa2540724bfbac14d7976d7e4a3860/library/core/src/ops/function.rs:250:5\n",
+		"   6: ffi_callback\n",
+		"   7: core::ops::function::FnOnce::call_once\n",
+		"             at /rustc/library/core/src/ops/function.rs:250:5\n",
+		"   8: std::sys::backtrace::__rust_begin_short_backtrace\n",
+		"             at /rustc/library/std/src/sys/backtrace.rs:166:18\n",
+		"   9: main\n",
+		"             at ./src/main.rs:1:1\n",
+	);
+	assert!(user_frames(backtrace) == [
+		Frame { symbol: "helpers::check_sorted", location: Some("./tests/helpers.rs:12:5") },
+		Frame { symbol: "helpers::test_sorted::{{closure}}", location: Some("./tests/helpers.rs:20:3") },
+	]);
+}
@@ -0,0 +1,66 @@
+use std::fmt::Write;
+
+use super::CheckExpression;
+use super::options::{AssertOptions, IntegerFormat};
+use crate::__assert2_impl::integer::Integer;
+use crate::theme::Themed;
+
+/// A failed equality check of integers, with the bits that differ if a hexadecimal or binary format is active.
+pub struct BitDiff<'a, Check> {
+	/// The failed check.
+	pub check: Check,
+
+	/// The left operand, if it is an integer.
+	pub left: Option<&'a dyn Integer>,
+
+	/// The right operand, if it is an integer.
+	pub right: Option<&'a dyn Integer>,
+
+	/// Override for the integer format of the assertion, if any.
+	pub format: Option<IntegerFormat>,
+}
+
+#[rustfmt::skip]
+impl<Check: CheckExpression> CheckExpression for BitDiff<'_, Check> {
+	fn write_expression(&self, buffer: &mut String) {
+		self.check.write_expression(buffer);
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		self.check.write_expansion(buffer);
+		let (Some(left), Some(right)) = (self.left, self.right) else {
+			return;
+		};
+		let format = self.format.unwrap_or_else(|| AssertOptions::get().integers);
+		if format == IntegerFormat::Decimal {
+			return;
+		}
+
+		let width = left.width().max(right.width());
+		let left = left.to_bits();
+		let right = right.to_bits();
+		if left & !right != 0 {
+			write!(buffer, "\nbits set only in left: {}", Themed::paint_left(&format_bits(left & !right, width, format))).unwrap();
+		}
+		if right & !left != 0 {
+			write!(buffer, "\nbits set only in right: {}", Themed::paint_right(&format_bits(right & !left, width, format))).unwrap();
+		}
+	}
+}
+
+/// Format a bit mask in hexadecimal or binary, padded to `width` bits.
+fn format_bits(bits: u128, width: u32, format: IntegerFormat) -> String {
+	match format {
+		IntegerFormat::Binary => format!("{:#0width$b}", bits, width = 2 + width as usize),
+		_ => format!("{:#0width$x}", bits, width = 2 + width as usize / 4),
+	}
+}
+
+#[test]
+fn test_format_bits() {
+	use crate::assert;
+	assert!(format_bits(0x40, 8, IntegerFormat::Hex) == "0x40");
+	assert!(format_bits(0x40, 16, IntegerFormat::Hex) == "0x0040");
+	assert!(format_bits(0b101, 8, IntegerFormat::Binary) == "0b00000101");
+	assert!(format_bits(u128::MAX, 128, IntegerFormat::Hex) == format!("0x{}", "f".repeat(32)));
+}
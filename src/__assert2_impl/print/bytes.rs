@@ -0,0 +1,214 @@
+use std::fmt::Write;
+use yansi::Paint;
+
+use super::{format_debug, CheckExpression};
+use crate::diff::{div_ceil, SingleLineDiff};
+use crate::theme::Themed;
+
+/// The number of bytes shown on a single row of a hexdump.
+const BYTES_PER_ROW: usize = 8;
+
+/// The number of bytes shown on a single row of a hexdump if the terminal is wide enough.
+const BYTES_PER_ROW_WIDE: usize = 16;
+
+/// The maximum number of rows shown in a hexdump.
+const MAX_ROWS: usize = 8;
+
+/// A failed comparison of two byte slices.
+pub struct BytesEq<'a> {
+	pub left: &'a [u8],
+	pub right: &'a [u8],
+	pub left_expr: &'a str,
+	pub right_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for BytesEq<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{left}, {right}",
+			left  = Themed::paint_left(self.left_expr),
+			right = Themed::paint_right(self.right_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		let Some(first) = first_difference(self.left, self.right) else {
+			write!(buffer, "{}", "Note: Left and right compared as unequal, but the bytes are identical!".red()).unwrap();
+			return;
+		};
+
+		writeln!(buffer, "with hexdump:").unwrap();
+		HexDump::new(self.left, self.right, first, bytes_per_row()).write(buffer);
+		write!(buffer, "\nNote: left is {} bytes, right is {} bytes, first difference at offset 0x{:x}.",
+			self.left.len(),
+			self.right.len(),
+			first,
+		).unwrap();
+	}
+}
+
+/// Write a hexdump of two byte buffers, followed by their lossy UTF-8 decoding.
+///
+/// The hexdump starts around the first difference, or at the start if the buffers are equal.
+/// This does not write a line break after the last line.
+pub(super) fn write_byte_buffers(buffer: &mut String, left: &[u8], right: &[u8], operator: &str) {
+	let first = first_difference(left, right).unwrap_or(0);
+	writeln!(buffer, "with hexdump:").unwrap();
+	HexDump::new(left, right, first, bytes_per_row()).write(buffer);
+
+	let left = format_debug(&String::from_utf8_lossy(left), false);
+	let right = format_debug(&String::from_utf8_lossy(right), false);
+	writeln!(buffer, "\nwith lossy UTF-8:").unwrap();
+	let diff = SingleLineDiff::new(&left, &right);
+	buffer.push_str("  ");
+	diff.write_left(buffer);
+	write!(buffer, " {} ", Themed::paint_operator(operator)).unwrap();
+	diff.write_right(buffer);
+}
+
+/// Find the offset of the first byte that differs between `left` and `right`.
+///
+/// If one input is a prefix of the other, the length of the shortest input is returned.
+/// If the inputs are equal, returns `None`.
+fn first_difference(left: &[u8], right: &[u8]) -> Option<usize> {
+	left.iter()
+		.zip(right)
+		.position(|(a, b)| a != b)
+		.or_else(|| Some(left.len().min(right.len())).filter(|_| left.len() != right.len()))
+}
+
+/// A side-by-side hexdump of the first differing region of two byte slices.
+struct HexDump<'a> {
+	left: &'a [u8],
+	right: &'a [u8],
+
+	/// The number of bytes per row.
+	bytes_per_row: usize,
+
+	/// The first row to print.
+	first_row: usize,
+
+	/// One past the last row to print.
+	end_row: usize,
+
+	/// The total number of rows needed to display the longest input.
+	total_rows: usize,
+}
+
+impl<'a> HexDump<'a> {
+	/// Create a hexdump around the given offset of the first difference.
+	///
+	/// The dump starts one row before the first difference,
+	/// and continues until one row after the differing region,
+	/// but it never exceeds [`MAX_ROWS`] rows.
+	fn new(left: &'a [u8], right: &'a [u8], first_difference: usize, bytes_per_row: usize) -> Self {
+		let total_rows = div_ceil(left.len().max(right.len()), bytes_per_row);
+		let diff_row = first_difference / bytes_per_row;
+		let first_row = diff_row.saturating_sub(1);
+
+		let mut end_row = diff_row;
+		while end_row < total_rows && row_differs(left, right, end_row * bytes_per_row..(end_row + 1) * bytes_per_row) {
+			end_row += 1;
+		}
+		let end_row = (end_row + 1).min(total_rows).min(first_row + MAX_ROWS);
+
+		Self {
+			left,
+			right,
+			bytes_per_row,
+			first_row,
+			end_row,
+			total_rows,
+		}
+	}
+
+	/// Write the hexdump to a buffer.
+	///
+	/// This does not write a line break after the last line.
+	fn write(&self, buffer: &mut String) {
+		let left_normal = yansi::Style::new().fg(crate::theme::left());
+		let left_highlight = crate::theme::highlight(crate::theme::left());
+		let right_normal = yansi::Style::new().fg(crate::theme::right());
+		let right_highlight = crate::theme::highlight(crate::theme::right());
+
+		let column_width = self.bytes_per_row * 3 + 1 + self.bytes_per_row;
+		write!(buffer, "  {:8}  {:column_width$}  {}",
+			"offset",
+			"left".paint_left(),
+			"right".paint_right(),
+		).unwrap();
+
+		if self.first_row > 0 {
+			buffer.push_str("\n  ...");
+		}
+		for row in self.first_row..self.end_row {
+			let range = row * self.bytes_per_row..(row + 1) * self.bytes_per_row;
+			write!(buffer, "\n  {:08x}  ", range.start).unwrap();
+			write_row(buffer, self.left, self.right, range.clone(), left_normal, left_highlight);
+			buffer.push_str("  ");
+			write_row(buffer, self.right, self.left, range, right_normal, right_highlight);
+			// Remove trailing whitespace.
+			buffer.truncate(buffer.trim_end().len());
+		}
+		if self.end_row < self.total_rows {
+			buffer.push_str("\n  ...");
+		}
+	}
+}
+
+/// Get the number of bytes to show per row, based on the terminal width.
+fn bytes_per_row() -> usize {
+	// Each byte takes 4 columns on both sides, plus 14 columns for the offset and padding.
+	match crate::terminal::size() {
+		Some(size) if size.width >= 2 * BYTES_PER_ROW_WIDE * 4 + 14 => BYTES_PER_ROW_WIDE,
+		_ => BYTES_PER_ROW,
+	}
+}
+
+/// Check if a row of the hexdump contains any differences.
+fn row_differs(left: &[u8], right: &[u8], range: std::ops::Range<usize>) -> bool {
+	left.get(range.start..range.end.min(left.len())) != right.get(range.start..range.end.min(right.len()))
+}
+
+/// Write a single row of a hexdump for `data`, highlighting the bytes that differ from `other`.
+fn write_row(buffer: &mut String, data: &[u8], other: &[u8], range: std::ops::Range<usize>, normal: yansi::Style, highlight: yansi::Style) {
+	let style = |i: usize| {
+		if data.get(i) == other.get(i) {
+			normal
+		} else {
+			highlight
+		}
+	};
+
+	for i in range.clone() {
+		match data.get(i) {
+			Some(byte) => write!(buffer, "{} ", format_args!("{byte:02x}").paint(style(i))).unwrap(),
+			None => buffer.push_str("   "),
+		}
+	}
+	buffer.push(' ');
+	for i in range {
+		match data.get(i) {
+			Some(&byte) => {
+				let c = if byte.is_ascii_graphic() || byte == b' ' {
+					byte as char
+				} else {
+					'.'
+				};
+				write!(buffer, "{}", c.paint(style(i))).unwrap();
+			},
+			None => buffer.push(' '),
+		}
+	}
+}
+
+#[test]
+fn test_first_difference() {
+	use crate::assert;
+	assert!(first_difference(b"abc", b"abc") == None);
+	assert!(first_difference(b"abc", b"abd") == Some(2));
+	assert!(first_difference(b"abc", b"xbc") == Some(0));
+	assert!(first_difference(b"abc", b"abcd") == Some(3));
+	assert!(first_difference(b"abcd", b"ab") == Some(2));
+	assert!(first_difference(b"", b"a") == Some(0));
+}
@@ -0,0 +1,70 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use super::BinaryOp;
+use super::CheckExpression;
+use super::format_debug;
+use crate::theme::Themed;
+
+/// A failed chain of comparisons like `a < b <= c`.
+pub struct ComparisonChain<'a> {
+	/// The values of all operands, from left to right.
+	pub operands: &'a [&'a dyn Debug],
+
+	/// The comparison operators between the operands.
+	pub operators: &'a [&'a str],
+
+	/// The expressions of all operands.
+	pub expressions: &'a [&'a str],
+
+	/// The names of the types of all operands.
+	pub types: &'a [&'a str],
+
+	/// The index of the first comparison that failed.
+	pub failed: usize,
+}
+
+impl ComparisonChain<'_> {
+	/// Get the first comparison of the chain that failed.
+	fn failed_link(&self) -> BinaryOp<'_, &dyn Debug, &dyn Debug> {
+		BinaryOp {
+			left: &self.operands[self.failed],
+			right: &self.operands[self.failed + 1],
+			operator: self.operators[self.failed],
+			left_expr: self.expressions[self.failed],
+			right_expr: self.expressions[self.failed + 1],
+			left_type: self.types[self.failed],
+			right_type: self.types[self.failed + 1],
+			left_address: None,
+			right_address: None,
+			left_text: None,
+			right_text: None,
+			left_bytes: None,
+			right_bytes: None,
+		}
+	}
+}
+
+#[rustfmt::skip]
+impl CheckExpression for ComparisonChain<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		for (i, expression) in self.expressions.iter().enumerate() {
+			if i > 0 {
+				write!(buffer, " {} ", Themed::paint_operator(self.operators[i - 1]).bold()).unwrap();
+			}
+			write!(buffer, "{}", Themed::paint_left(expression)).unwrap();
+		}
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with operands:").unwrap();
+		for (expression, value) in self.expressions.iter().zip(self.operands) {
+			writeln!(buffer, "  {} = {}", expression, format_debug(value, false).paint_left()).unwrap();
+		}
+		writeln!(buffer, "failed comparison:").unwrap();
+		buffer.push_str("  ");
+		self.failed_link().write_expression(buffer);
+		buffer.push('\n');
+		self.failed_link().write_expansion(buffer);
+	}
+}
@@ -0,0 +1,33 @@
+use std::fmt::Write;
+
+use super::CheckExpression;
+use crate::theme::Themed;
+
+/// A failed chain of alternatives: `a || b || ...`.
+pub struct Disjunction<'a> {
+	/// The full expression.
+	pub expression: &'a str,
+
+	/// All alternatives, which all evaluated to false.
+	pub alternatives: &'a [&'a dyn CheckExpression],
+}
+
+#[rustfmt::skip]
+impl CheckExpression for Disjunction<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{}", Themed::paint_left(self.expression)).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		for (i, alternative) in self.alternatives.iter().enumerate() {
+			if i > 0 {
+				buffer.push('\n');
+			}
+			writeln!(buffer, "alternative {} of {}:", i + 1, self.alternatives.len()).unwrap();
+			buffer.push_str("  ");
+			alternative.write_expression(buffer);
+			buffer.push('\n');
+			alternative.write_expansion(buffer);
+		}
+	}
+}
@@ -0,0 +1,54 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::theme::Themed;
+
+/// A failed check that all values are distinct.
+pub struct AllDistinct<'a, T: ?Sized> {
+	/// The values that were compared.
+	pub values: &'a [&'a T],
+
+	/// The indices of the values that compared equal.
+	pub pairs: &'a [(usize, usize)],
+
+	/// The expressions for the values.
+	pub expressions: &'a [&'a str],
+}
+
+#[rustfmt::skip]
+impl<T: Debug + ?Sized> CheckExpression for AllDistinct<'_, T> {
+	fn write_expression(&self, buffer: &mut String) {
+		for (i, expression) in self.expressions.iter().enumerate() {
+			if i > 0 {
+				buffer.push_str(", ");
+			}
+			write!(buffer, "{}", Themed::paint_left(expression)).unwrap();
+		}
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with equal pairs ({}):", self.pairs.len()).unwrap();
+		for &(a, b) in self.pairs {
+			writeln!(buffer, "  {} {} {}",
+				Themed::paint_left(self.expressions[a]),
+				Themed::paint_operator("==").bold(),
+				Themed::paint_left(self.expressions[b]),
+			).unwrap();
+		}
+
+		writeln!(buffer, "with expansion:").unwrap();
+		for (i, value) in self.values.iter().enumerate() {
+			if self.pairs.iter().any(|&(a, b)| a == i || b == i) {
+				writeln!(buffer, "  {} {} {}",
+					Themed::paint_name(self.expressions[i]),
+					Themed::paint_operator("=").bold(),
+					format_debug(value, false).paint_right(),
+				).unwrap();
+			}
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
@@ -0,0 +1,43 @@
+use std::fmt::Write;
+use yansi::Paint;
+
+use super::CheckExpression;
+use crate::__assert2_impl::downcast::ActualType;
+use crate::theme::Themed;
+
+/// A value that could not be downcast to the expected type.
+pub struct DowncastFailed<'a> {
+	/// The name of the expected type.
+	pub expected: &'static str,
+
+	/// What is known about the actual type of the value.
+	pub actual: ActualType,
+
+	pub value_expr: &'a str,
+	pub type_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for DowncastFailed<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{value}, {ty}",
+			value = Themed::paint_left(self.value_expr),
+			ty    = Themed::paint_right(self.type_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with expansion:").unwrap();
+		writeln!(buffer, "  expected type: {}", Themed::paint_right(self.expected)).unwrap();
+		match (self.actual.name, self.actual.type_id) {
+			(Some(name), _) => writeln!(buffer, "  actual type:   {}", Themed::paint_left(name)).unwrap(),
+			(None, Some(type_id)) => writeln!(buffer, "  actual type:   {} {}", "unknown".red(), format_args!("({type_id:?})").dim()).unwrap(),
+			(None, None) => writeln!(buffer, "  actual type:   {}", "unknown".red()).unwrap(),
+		}
+		if let Some(value) = &self.actual.value {
+			writeln!(buffer, "  actual value:  {}", Themed::paint_left(value)).unwrap();
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
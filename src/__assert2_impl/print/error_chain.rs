@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::fmt::Write;
+use yansi::Paint;
+
+use super::CheckExpression;
+use crate::theme::Themed;
+
+/// A failed search for a matching error in the chain of error sources.
+pub struct ErrChain<'a> {
+	pub chain: &'a [&'a (dyn Error + 'static)],
+
+	/// The indices of the errors that have the right type, but did not match the pattern.
+	pub type_matches: &'a [usize],
+
+	pub error_expr: &'a str,
+	pub type_name: &'a str,
+	pub pattern: Option<&'a str>,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for ErrChain<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{error}, {ty}",
+			error = Themed::paint_left(self.error_expr),
+			ty    = Themed::paint_right(self.type_name),
+		).unwrap();
+		if let Some(pattern) = self.pattern {
+			write!(buffer, " {} {}", Themed::paint_operator("=>").bold(), Themed::paint_right(pattern)).unwrap();
+		}
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with error chain:").unwrap();
+		let width = (self.chain.len() - 1).to_string().len();
+		for (i, error) in self.chain.iter().enumerate() {
+			writeln!(buffer, "  {}: {}", Themed::paint_name(&format_args!("{i:width$}")), error.paint_left()).unwrap();
+		}
+		match self.type_matches {
+			[] => {
+				write!(buffer, "{}", format_args!("Note: no error in the chain has type {}.", self.type_name).bold()).unwrap();
+			},
+			[index] => {
+				write!(buffer, "{}", format_args!("Note: the error at index {index} has type {}, but it does not match the pattern.", self.type_name).bold()).unwrap();
+			},
+			indices => {
+				let indices: Vec<_> = indices.iter().map(|i| i.to_string()).collect();
+				write!(buffer, "{}", format_args!("Note: the errors at indices {} have type {}, but they do not match the pattern.", indices.join(", "), self.type_name).bold()).unwrap();
+			},
+		}
+	}
+}
@@ -0,0 +1,70 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+use yansi::Paint;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::diff::SingleLineDiff;
+use crate::theme::Themed;
+
+/// A failed comparison of some fields of a struct.
+pub struct FieldsEq<'a> {
+	pub fields: &'a [Field<'a>],
+	pub actual_expr: &'a str,
+	pub expected_expr: &'a str,
+}
+
+/// The result of comparing a single field.
+pub struct Field<'a> {
+	pub name: &'a str,
+	pub equal: bool,
+	pub actual: &'a dyn Debug,
+	pub expected: &'a dyn Debug,
+}
+
+impl<'a> Field<'a> {
+	/// Compare the actual value of a field with the expected value.
+	pub fn new<Actual, Expected>(name: &'a str, actual: &'a Actual, expected: &'a Expected) -> Self
+	where
+		Actual: PartialEq<Expected> + Debug,
+		Expected: Debug,
+	{
+		Self {
+			name,
+			equal: actual == expected,
+			actual,
+			expected,
+		}
+	}
+}
+
+#[rustfmt::skip]
+impl CheckExpression for FieldsEq<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{actual}, {expected}",
+			actual   = Themed::paint_left(self.actual_expr),
+			expected = Themed::paint_right(self.expected_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with fields:").unwrap();
+		let width = self.fields.iter().map(|x| x.name.len()).max().unwrap_or(0);
+		for field in self.fields {
+			if field.equal {
+				writeln!(buffer, "  {} {}", Themed::paint_name(&format_args!("{:width$}", field.name)), "equal".dim()).unwrap();
+			} else {
+				let actual = format_debug(field.actual, false);
+				let expected = format_debug(field.expected, false);
+				let diff = SingleLineDiff::new(&actual, &expected);
+				write!(buffer, "  {} {}: ", Themed::paint_name(&format_args!("{:width$}", field.name)), "differs".red().bold()).unwrap();
+				diff.write_left(buffer);
+				write!(buffer, " {} ", Themed::paint_operator("!=")).unwrap();
+				diff.write_right(buffer);
+				buffer.push('\n');
+			}
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
@@ -0,0 +1,246 @@
+use std::fmt::{Debug, Write};
+
+/// Only start looking for repetition once the output is at least this long (in bytes, with whitespace collapsed).
+const REPETITION_CHECK_START: usize = 16 * 1024;
+
+/// Check for repetition every time this many bytes have been written (with whitespace collapsed).
+const REPETITION_CHECK_INTERVAL: usize = 1024;
+
+/// The size of the window at the end of the output that must be repeating to stop formatting.
+const REPETITION_WINDOW: usize = 8 * 1024;
+
+/// The maximum length of a repeating chunk.
+const REPETITION_MAX_PERIOD: usize = 1024;
+
+/// Format a value using the compact or pretty `Debug` format.
+///
+/// Formatting is aborted if the output keeps repeating the same chunk over and over,
+/// as happens when printing cyclic data structures (for example, graphs built with `Rc`).
+/// In that case, a note is added to the end of the output.
+///
+/// If the `sort` option is set, the entries of sets and maps are sorted.
+/// If the `max-len` option is set, the middle of longer output is replaced by a note.
+pub fn format_debug(value: &dyn Debug, pretty: bool) -> String {
+	let mut guard = RepetitionGuard::new();
+	let result = if pretty {
+		write!(guard, "{value:#?}")
+	} else {
+		write!(guard, "{value:?}")
+	};
+
+	let mut output = guard.output;
+	if guard.repeating {
+		output.push_str("\n... <repeating output truncated>");
+	} else if result.is_err() {
+		output.push_str("\n... <Debug implementation returned an error>");
+	}
+	let options = super::options::AssertOptions::get();
+	if options.sort {
+		output = super::sort::sort_unordered(&output);
+	}
+	if let Some(max_len) = options.max_len {
+		output = truncate_middle(output, max_len);
+	}
+	crate::redact::redact(output)
+}
+
+/// Replace the middle of a value that is longer than `max_len` bytes with a note, keeping the start and the end.
+///
+/// Multi-line values are truncated at line boundaries, so that the indentation of the remaining lines is intact.
+fn truncate_middle(value: String, max_len: usize) -> String {
+	if value.len() <= max_len {
+		return value;
+	}
+	let mut head = max_len / 2;
+	while !value.is_char_boundary(head) {
+		head -= 1;
+	}
+	let mut tail = value.len() - max_len / 2;
+	while !value.is_char_boundary(tail) {
+		tail += 1;
+	}
+
+	let multi_line = value.contains('\n');
+	if multi_line {
+		head = value[..head].rfind('\n').map_or(0, |i| i + 1);
+		tail = value[tail..].find('\n').map_or(value.len(), |i| tail + i + 1);
+	}
+
+	let elided = tail - head;
+	let mut output = String::with_capacity(head + (value.len() - tail) + 32);
+	output.push_str(&value[..head]);
+	if multi_line {
+		writeln!(output, "... <{elided} bytes elided> ...").unwrap();
+	} else {
+		write!(output, " ... <{elided} bytes elided> ... ").unwrap();
+	}
+	output.push_str(&value[tail..]);
+	output
+}
+
+/// String writer that stops accepting data when the output keeps repeating.
+///
+/// Only output that keeps repeating with an increasing nesting depth is considered to be repeating,
+/// as printed by cyclic data structures.
+/// Long flat output, like a large `Vec` of zeroes, is periodic too, but it is not cut off.
+struct RepetitionGuard {
+	/// The full output.
+	output: String,
+
+	/// The output with all runs of whitespace collapsed to a single space.
+	///
+	/// Pretty printed recursive structures keep increasing the indentation level,
+	/// so we look for repetitions in the collapsed output.
+	collapsed: Vec<u8>,
+
+	/// The length of `collapsed` the last time we checked for repetitions.
+	last_check: usize,
+
+	/// The current nesting depth of brackets, outside of string literals.
+	depth: usize,
+
+	/// True if the output is inside a string literal.
+	in_string: bool,
+
+	/// True if the previous byte was a backslash inside a string literal.
+	escaped: bool,
+
+	/// The length of `collapsed` and the nesting depth at every check.
+	depths: Vec<(usize, usize)>,
+
+	/// Set to true when repetition has been detected.
+	repeating: bool,
+}
+
+impl RepetitionGuard {
+	fn new() -> Self {
+		Self {
+			output: String::new(),
+			collapsed: Vec::new(),
+			last_check: 0,
+			depth: 0,
+			in_string: false,
+			escaped: false,
+			depths: Vec::new(),
+			repeating: false,
+		}
+	}
+
+	/// Update the nesting depth for a byte of output.
+	fn track_depth(&mut self, byte: u8) {
+		if self.in_string {
+			match byte {
+				_ if self.escaped => self.escaped = false,
+				b'\\' => self.escaped = true,
+				b'"' => self.in_string = false,
+				_ => (),
+			}
+			return;
+		}
+		match byte {
+			b'"' => self.in_string = true,
+			b'(' | b'[' | b'{' => self.depth += 1,
+			b')' | b']' | b'}' => self.depth = self.depth.saturating_sub(1),
+			_ => (),
+		}
+	}
+
+	/// Check if the end of the collapsed output is periodic, with a growing nesting depth.
+	fn is_repeating(&self) -> bool {
+		let start = self.collapsed.len() - REPETITION_WINDOW;
+		let start_depth = self.depths.iter().rev().find(|(len, _)| *len <= start).map_or(0, |(_, depth)| *depth);
+		if self.depth <= start_depth {
+			return false;
+		}
+		let window = &self.collapsed[start..];
+		(1..=REPETITION_MAX_PERIOD).any(|period| window[period..] == window[..REPETITION_WINDOW - period])
+	}
+}
+
+impl Write for RepetitionGuard {
+	fn write_str(&mut self, data: &str) -> std::fmt::Result {
+		if self.repeating {
+			return Err(std::fmt::Error);
+		}
+
+		self.output.push_str(data);
+		for byte in data.bytes() {
+			self.track_depth(byte);
+			if !byte.is_ascii_whitespace() {
+				self.collapsed.push(byte);
+			} else if self.collapsed.last() != Some(&b' ') {
+				self.collapsed.push(b' ');
+			}
+		}
+
+		let len = self.collapsed.len();
+		if len - self.last_check >= REPETITION_CHECK_INTERVAL {
+			self.last_check = len;
+			self.depths.push((len, self.depth));
+			if len >= REPETITION_CHECK_START && self.is_repeating() {
+				self.repeating = true;
+				return Err(std::fmt::Error);
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::assert;
+	use std::cell::RefCell;
+	use std::rc::Rc;
+
+	#[derive(Debug)]
+	struct Node {
+		#[allow(dead_code)]
+		next: RefCell<Option<Rc<Node>>>,
+	}
+
+	#[test]
+	fn test_truncate_middle() {
+		assert!(truncate_middle("short".into(), 10) == "short");
+		assert!(truncate_middle("0123456789abcdef".into(), 8) == "0123 ... <8 bytes elided> ... cdef");
+		assert!(truncate_middle("ääää".into(), 5) == "ä ... <4 bytes elided> ... ä");
+
+		let lines = "[\n    1,\n    2,\n    3,\n    4,\n]".to_string();
+		assert!(truncate_middle(lines, 20) == "[\n    1,\n... <14 bytes elided> ...\n    4,\n]");
+	}
+
+	#[test]
+	fn cyclic_debug_is_truncated() {
+		let node = Rc::new(Node { next: RefCell::new(None) });
+		*node.next.borrow_mut() = Some(node.clone());
+
+		let compact = format_debug(&node, false);
+		assert!(compact.ends_with("<repeating output truncated>"));
+		let pretty = format_debug(&node, true);
+		assert!(pretty.ends_with("<repeating output truncated>"));
+
+		// Break the cycle so the node can be dropped.
+		node.next.borrow_mut().take();
+	}
+
+	#[test]
+	fn normal_debug_is_not_truncated() {
+		let value = vec![(1, "foo"); 100];
+		assert!(format_debug(&value, false) == format!("{value:?}"));
+		assert!(format_debug(&value, true) == format!("{value:#?}"));
+	}
+
+	#[test]
+	fn flat_periodic_debug_is_not_truncated() {
+		// Much longer than the repetition window, with a difference near the end.
+		let left = vec![0u32; 10000];
+		let mut right = left.clone();
+		right[9000] = 1;
+		assert!(format_debug(&left, false) == format!("{left:?}"));
+		assert!(format_debug(&right, false) == format!("{right:?}"));
+		assert!(format_debug(&right, true) == format!("{right:#?}"));
+
+		let strings = vec!["[("; 10000];
+		assert!(format_debug(&strings, false) == format!("{strings:?}"));
+	}
+}
@@ -0,0 +1,75 @@
+use std::fmt::Write;
+use std::path::Path;
+use yansi::Paint;
+
+use super::CheckExpression;
+use crate::diff::MultiLineDiff;
+use crate::__assert2_impl::golden::GoldenMismatch;
+use crate::theme::Themed;
+
+/// A failed comparison of a string with the contents of a golden file.
+pub struct FileEq<'a> {
+	pub actual: &'a str,
+	pub path: &'a Path,
+	pub mismatch: &'a GoldenMismatch,
+	pub actual_expr: &'a str,
+	pub path_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for FileEq<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{actual}, {path}",
+			actual = Themed::paint_left(self.actual_expr),
+			path   = Themed::paint_right(self.path_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		write_mismatch(buffer, self.actual, self.path, self.mismatch);
+	}
+}
+
+/// A failed comparison of a value with a stored snapshot.
+pub struct SnapshotEq<'a> {
+	pub actual: &'a str,
+	pub path: &'a Path,
+	pub mismatch: &'a GoldenMismatch,
+	pub expression: &'a str,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for SnapshotEq<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{}", Themed::paint_left(self.expression)).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		write_mismatch(buffer, self.actual, self.path, self.mismatch);
+	}
+}
+
+/// Write the details of a mismatch between a string and a golden file.
+#[rustfmt::skip]
+fn write_mismatch(buffer: &mut String, actual: &str, path: &Path, mismatch: &GoldenMismatch) {
+	match mismatch {
+		GoldenMismatch::Unreadable(error) => {
+			write!(buffer, "{}", format_args!("Failed to read {}: {}", path.display(), error).red()).unwrap();
+		},
+		GoldenMismatch::Unwritable(error) => {
+			write!(buffer, "{}", format_args!("Failed to update {}: {}", path.display(), error).red()).unwrap();
+			return;
+		},
+		GoldenMismatch::Different(expected) => {
+			writeln!(buffer, "with diff against {}:", path.display().bold()).unwrap();
+			MultiLineDiff::new(actual, expected)
+				.write_interleaved(buffer);
+		},
+		GoldenMismatch::Missing => {
+			write!(buffer, "{}", format_args!("Snapshot {} does not exist.", path.display()).red()).unwrap();
+			write!(buffer, "\nNote: Run with {} to create the file.", "ASSERT2_UPDATE_GOLDEN=1".bold()).unwrap();
+			return;
+		},
+	}
+	write!(buffer, "\nNote: Run with {} to update the file.", "ASSERT2_UPDATE_GOLDEN=1".bold()).unwrap();
+}
@@ -0,0 +1,71 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+use yansi::Paint;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::__assert2_impl::iter_eq::IterMismatch;
+use crate::theme::Themed;
+
+/// A failed element-wise comparison of two iterators.
+pub struct IterEq<'a, L, R> {
+	/// The first difference between the iterators.
+	pub mismatch: &'a IterMismatch<L, R>,
+
+	pub left_expr: &'a str,
+	pub right_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl<L: Debug + PartialEq<R>, R: Debug> CheckExpression for IterEq<'_, L, R> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{} {} {}",
+			Themed::paint_left(self.left_expr),
+			Themed::paint_operator("==").bold(),
+			Themed::paint_right(self.right_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		let mismatch = self.mismatch;
+		let end = mismatch.start + mismatch.left.len().max(mismatch.right.len());
+		let width = end.saturating_sub(1).to_string().len();
+
+		writeln!(buffer, "with first difference at index {}:", mismatch.index).unwrap();
+		for index in mismatch.start..end {
+			let left = mismatch.left.get(index - mismatch.start);
+			let right = mismatch.right.get(index - mismatch.start);
+			let marker = if index == mismatch.index { ">" } else { " " };
+			let op = match (left, right) {
+				(Some(left), Some(right)) if left == right => "==",
+				_ => "!=",
+			};
+			writeln!(buffer, "  {} [{:>width$}] {} {} {}",
+				Paint::red(marker).bold(),
+				index,
+				format_element(left).paint_left(),
+				Themed::paint_operator(op).bold(),
+				format_element(right).paint_right(),
+			).unwrap();
+		}
+
+		if mismatch.left_len != mismatch.right_len {
+			writeln!(buffer, "with lengths:").unwrap();
+			writeln!(buffer, "  {} {} {}",
+				Themed::paint_left(&mismatch.left_len),
+				Themed::paint_operator("!=").bold(),
+				Themed::paint_right(&mismatch.right_len),
+			).unwrap();
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
+
+/// Format an element, or a placeholder if the iterator ended.
+fn format_element<T: Debug>(element: Option<&T>) -> String {
+	match element {
+		Some(element) => format_debug(element, false),
+		None => String::from("(end)"),
+	}
+}
@@ -0,0 +1,54 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+use yansi::Paint;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::theme::Themed;
+
+/// The maximum number of elements to print.
+const MAX_ELEMENTS: usize = 16;
+
+/// A failed check of the length of a collection.
+pub struct LenEq<'a, T> {
+	/// The elements of the collection.
+	pub elements: &'a [T],
+
+	/// The expected length.
+	pub expected: usize,
+
+	pub collection_expr: &'a str,
+
+	/// The expression for the expected length, or `None` if the collection should be empty.
+	pub len_expr: Option<&'a str>,
+}
+
+#[rustfmt::skip]
+impl<T: Debug> CheckExpression for LenEq<'_, T> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{}", Themed::paint_left(self.collection_expr)).unwrap();
+		if let Some(len_expr) = self.len_expr {
+			write!(buffer, ", {}", Themed::paint_right(len_expr)).unwrap();
+		}
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with length:").unwrap();
+		writeln!(buffer, "  {} {}",
+			Themed::paint_left(&self.elements.len()),
+			Themed::paint_right(&format_args!("(expected {})", self.expected)),
+		).unwrap();
+		writeln!(buffer, "with elements:").unwrap();
+		if self.elements.is_empty() {
+			writeln!(buffer, "  {}", "(none)".dim()).unwrap();
+		}
+		for element in self.elements.iter().take(MAX_ELEMENTS) {
+			writeln!(buffer, "  {}", format_debug(element, false).paint_left()).unwrap();
+		}
+		if self.elements.len() > MAX_ELEMENTS {
+			writeln!(buffer, "  {}", format_args!("... and {} more", self.elements.len() - MAX_ELEMENTS).dim()).unwrap();
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
@@ -0,0 +1,44 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::theme::Themed;
+
+/// A failed step of a let chain: `let pattern = expr && condition && ...`.
+pub struct LetChain<'a, Step> {
+	/// The full let chain.
+	pub expression: &'a str,
+
+	/// The variables bound by earlier steps, with their `Debug` output.
+	pub bindings: &'a [(&'a str, &'a str)],
+
+	/// The step that failed.
+	pub step: Step,
+}
+
+/// Format the value of a variable bound by a step of a let chain.
+pub fn format_binding(value: &dyn Debug) -> String {
+	format_debug(value, false)
+}
+
+#[rustfmt::skip]
+impl<Step: CheckExpression> CheckExpression for LetChain<'_, Step> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{}", Themed::paint_left(self.expression)).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		if !self.bindings.is_empty() {
+			writeln!(buffer, "with bindings:").unwrap();
+			for (name, value) in self.bindings {
+				writeln!(buffer, "  {} {} {}", Themed::paint_name(name), Themed::paint_operator("=").bold(), value).unwrap();
+			}
+		}
+		writeln!(buffer, "failed at:").unwrap();
+		buffer.push_str("  ");
+		self.step.write_expression(buffer);
+		buffer.push('\n');
+		self.step.write_expansion(buffer);
+	}
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+/// The URL template used if the `ASSERT2_LINK` environment variable is not set.
+const DEFAULT_TEMPLATE: &str = "file://{path}";
+
+/// Wrap the text of a location in an OSC 8 hyperlink to the source file.
+///
+/// The URL is made from the template in the `ASSERT2_LINK` environment variable, or `file://{path}` if it is not set.
+/// In the template, `{path}` is replaced by the absolute path of the file, and `{line}` and `{column}` by the location in the file.
+pub(super) fn hyperlink(text: &str, file: &str, line: u32, column: u32) -> String {
+	let template = std::env::var("ASSERT2_LINK").ok().filter(|x| !x.is_empty());
+	let template = template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+	let url = format_url(template, &absolute_path(file), line, column);
+	format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Get the absolute path of a source file from the path given by `file!()`.
+///
+/// If the file can not be found, the path is taken relative to the current directory.
+fn absolute_path(file: &str) -> PathBuf {
+	let path = super::source::find_source_file(file).unwrap_or_else(|| file.into());
+	std::env::current_dir().map_or_else(|_| path.clone(), |dir| dir.join(&path))
+}
+
+/// Fill in the placeholders of a URL template.
+fn format_url(template: &str, path: &std::path::Path, line: u32, column: u32) -> String {
+	let path = path.to_string_lossy().replace('\\', "/");
+	let path = if path.starts_with('/') { path } else { format!("/{path}") };
+	template
+		.replace("{path}", &encode_path(&path))
+		.replace("{line}", &line.to_string())
+		.replace("{column}", &column.to_string())
+}
+
+/// Percent-encode the characters of a path that are not allowed in a URL.
+fn encode_path(path: &str) -> String {
+	let mut output = String::with_capacity(path.len());
+	for byte in path.bytes() {
+		if byte.is_ascii_alphanumeric() || b"/-._~:".contains(&byte) {
+			output.push(byte as char);
+		} else {
+			output.push_str(&format!("%{byte:02X}"));
+		}
+	}
+	output
+}
+
+#[test]
+fn test_format_url() {
+	use crate::assert;
+	use std::path::Path;
+	assert!(format_url(DEFAULT_TEMPLATE, Path::new("/src/main.rs"), 3, 4) == "file:///src/main.rs");
+	assert!(format_url("vscode://file{path}:{line}:{column}", Path::new("/src/main.rs"), 3, 4) == "vscode://file/src/main.rs:3:4");
+	assert!(format_url(DEFAULT_TEMPLATE, Path::new("/my project/ä.rs"), 1, 1) == "file:///my%20project/%C3%A4.rs");
+	assert!(format_url(DEFAULT_TEMPLATE, Path::new("C:\\src\\main.rs"), 1, 1) == "file:///C:/src/main.rs");
+}
@@ -0,0 +1,69 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::__assert2_impl::map::MapDiff;
+use crate::diff::SingleLineDiff;
+use crate::theme::Themed;
+
+/// A failed comparison of two maps.
+pub struct MapEq<'a, K, L, R> {
+	/// The differences between the maps.
+	pub diff: &'a MapDiff<'a, K, L, R>,
+
+	pub left_expr: &'a str,
+	pub right_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl<K: Debug, L: Debug, R: Debug> CheckExpression for MapEq<'_, K, L, R> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{} {} {}",
+			Themed::paint_left(self.left_expr),
+			Themed::paint_operator("==").bold(),
+			Themed::paint_right(self.right_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		if !self.diff.only_left.is_empty() {
+			writeln!(buffer, "with keys only in left ({}):", self.diff.only_left.len()).unwrap();
+			for (key, value) in sorted_by_key(&self.diff.only_left, |x| x.0) {
+				writeln!(buffer, "  {}: {}", key.paint_name(), format_debug(value.1, false).paint_left()).unwrap();
+			}
+		}
+
+		if !self.diff.only_right.is_empty() {
+			writeln!(buffer, "with keys only in right ({}):", self.diff.only_right.len()).unwrap();
+			for (key, value) in sorted_by_key(&self.diff.only_right, |x| x.0) {
+				writeln!(buffer, "  {}: {}", key.paint_name(), format_debug(value.1, false).paint_right()).unwrap();
+			}
+		}
+
+		if !self.diff.different.is_empty() {
+			writeln!(buffer, "with different values ({}):", self.diff.different.len()).unwrap();
+			for (key, value) in sorted_by_key(&self.diff.different, |x| x.0) {
+				let left = format_debug(value.1, false);
+				let right = format_debug(value.2, false);
+				let diff = SingleLineDiff::new(&left, &right);
+				write!(buffer, "  {}: ", key.paint_name()).unwrap();
+				diff.write_left(buffer);
+				write!(buffer, " {} ", Themed::paint_operator("!=")).unwrap();
+				diff.write_right(buffer);
+				buffer.push('\n');
+			}
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
+
+/// Sort entries by the formatted key, so that the output does not depend on the iteration order of the map.
+fn sorted_by_key<'a, T, K: Debug>(entries: &'a [T], key: impl Fn(&T) -> &K) -> Vec<(String, &'a T)> {
+	let mut entries: Vec<_> = entries.iter()
+		.map(|entry| (format_debug(key(entry), false), entry))
+		.collect();
+	entries.sort_by(|a, b| a.0.cmp(&b.0));
+	entries
+}
@@ -0,0 +1,53 @@
+use std::fmt::{Debug, Write};
+
+use super::CheckExpression;
+use super::write_expanded_value;
+use crate::matchers::Matcher;
+use crate::theme::Themed;
+
+/// A value that does not match a matcher.
+pub struct MatcherExpr<'a, Value> {
+	pub value: &'a Value,
+	pub description: String,
+	pub explanation: String,
+	pub value_expr: &'a str,
+	pub matcher_expr: &'a str,
+}
+
+impl<'a, Value> MatcherExpr<'a, Value> {
+	/// Describe a failed match of `actual` against `matcher`.
+	///
+	/// The `value` is the printable form of `actual`.
+	pub fn new<T: ?Sized, M: Matcher<T>>(value: &'a Value, actual: &T, matcher: &M, value_expr: &'a str, matcher_expr: &'a str) -> Self {
+		Self {
+			value,
+			description: matcher.describe(),
+			explanation: matcher.explain(actual),
+			value_expr,
+			matcher_expr,
+		}
+	}
+}
+
+#[rustfmt::skip]
+impl<Value: Debug> CheckExpression for MatcherExpr<'_, Value> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{value}, {matcher}",
+			value   = Themed::paint_left(self.value_expr),
+			matcher = Themed::paint_right(self.matcher_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with expansion:").unwrap();
+		write_expanded_value(buffer, self.value, crate::theme::left());
+		writeln!(buffer, "expected a value that:").unwrap();
+		writeln!(buffer, "  {}", self.description.paint_right()).unwrap();
+		if self.explanation != self.description {
+			writeln!(buffer, "but it does not satisfy:").unwrap();
+			writeln!(buffer, "  {}", self.explanation.paint_right().bold()).unwrap();
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
@@ -0,0 +1,45 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use super::CheckExpression;
+use super::write_expanded_value;
+use crate::theme::Themed;
+
+/// A failed call to a well-known boolean method, like `result.is_ok()`.
+pub struct MethodCall<'a> {
+	/// The full expression.
+	pub expression: &'a str,
+
+	/// The receiver of the method.
+	pub receiver: &'a dyn Debug,
+
+	/// The arguments of the method.
+	pub args: &'a [&'a dyn Debug],
+}
+
+#[rustfmt::skip]
+impl CheckExpression for MethodCall<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{}", Themed::paint_left(self.expression)).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with receiver:").unwrap();
+		write_expanded_value(buffer, self.receiver, crate::theme::left());
+		match self.args {
+			[] => (),
+			[arg] => {
+				writeln!(buffer, "with argument:").unwrap();
+				write_expanded_value(buffer, arg, crate::theme::right());
+			},
+			args => {
+				writeln!(buffer, "with arguments:").unwrap();
+				for arg in args {
+					write_expanded_value(buffer, arg, crate::theme::right());
+				}
+			},
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
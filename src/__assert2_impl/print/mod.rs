@@ -2,11 +2,113 @@ use std::fmt::Debug;
 use yansi::Paint;
 use std::fmt::Write;
 
-mod diff;
-use self::diff::{MultiLineDiff, SingleLineDiff};
+mod affix;
+pub use self::affix::AffixEq;
 
-mod options;
-use self::options::{AssertOptions, ExpansionFormat};
+mod all_ok;
+pub use self::all_ok::AllOk;
+
+mod backtrace;
+
+mod bit_diff;
+pub use self::bit_diff::BitDiff;
+
+mod bytes;
+pub use self::bytes::BytesEq;
+
+mod comparison_chain;
+pub use self::comparison_chain::ComparisonChain;
+
+use crate::diff::{MultiLineDiff, SingleLineDiff};
+use crate::theme::Themed;
+
+mod disjunction;
+pub use self::disjunction::Disjunction;
+
+mod distinct;
+pub use self::distinct::AllDistinct;
+
+mod downcast;
+pub use self::downcast::DowncastFailed;
+
+mod error_chain;
+pub use self::error_chain::ErrChain;
+
+mod fields;
+pub use self::fields::{Field, FieldsEq};
+
+mod format;
+pub(crate) use self::format::format_debug;
+
+mod golden;
+pub use self::golden::{FileEq, SnapshotEq};
+
+mod iter_eq;
+pub use self::iter_eq::IterEq;
+
+mod len;
+pub use self::len::LenEq;
+
+mod let_chain;
+pub use self::let_chain::{format_binding, LetChain};
+
+mod link;
+
+mod map;
+pub use self::map::MapEq;
+
+mod matcher;
+pub use self::matcher::MatcherExpr;
+
+mod method_call;
+pub use self::method_call::MethodCall;
+
+mod negation;
+pub use self::negation::Negation;
+
+mod poll;
+pub use self::poll::PollExpr;
+
+mod recv;
+pub use self::recv::Recv;
+
+#[cfg(feature = "regex")]
+mod regex;
+#[cfg(feature = "regex")]
+pub use self::regex::RegexMatch;
+
+mod set;
+pub use self::set::{SetEq, SetRelation};
+
+mod sort;
+
+mod source;
+
+mod structural;
+pub use self::structural::FieldDiffs;
+
+mod tap;
+
+mod text;
+
+mod timing;
+pub use self::timing::CompletesWithin;
+
+mod tuple;
+use self::tuple::write_tuple_elements;
+
+mod unordered;
+pub use self::unordered::UnorderedEq;
+
+mod values;
+pub use self::values::WithValues;
+
+mod variant;
+use self::variant::{is_similar, mismatched_variant};
+
+pub(crate) mod options;
+use self::options::{AssertOptions, CrateDefaults, ExpansionFormat, ExpansionLayout, ReportFormat};
+use crate::__assert2_impl::site::format_count;
 
 pub struct FailedCheck<'a, T> {
 	pub macro_name: &'a str,
@@ -16,6 +118,12 @@ pub struct FailedCheck<'a, T> {
 	pub custom_msg: Option<std::fmt::Arguments<'a>>,
 	pub expression: T,
 	pub fragments: &'a [(&'a str, &'a str)],
+
+	/// The build time defaults for the options of the crate that contains the check.
+	pub defaults: Option<&'static str>,
+
+	/// The execution counter of the assertion site.
+	pub site: &'a crate::__assert2_impl::site::SiteCounter,
 }
 
 pub trait CheckExpression {
@@ -29,6 +137,31 @@ pub struct BinaryOp<'a, Left, Right> {
 	pub operator: &'a str,
 	pub left_expr: &'a str,
 	pub right_expr: &'a str,
+
+	/// The names of the types of the left and right operand.
+	pub left_type: &'a str,
+	pub right_type: &'a str,
+
+	/// The addresses that the left and right operand point to, if they are references.
+	pub left_address: Option<*const ()>,
+	pub right_address: Option<*const ()>,
+
+	/// The text of the left and right operand, if they are strings.
+	pub left_text: Option<&'a str>,
+	pub right_text: Option<&'a str>,
+
+	/// The contents of the left and right operand, if they are byte buffers.
+	pub left_bytes: Option<&'a [u8]>,
+	pub right_bytes: Option<&'a [u8]>,
+}
+
+/// A precomputed expansion of a value, printed verbatim instead of the `Debug` output.
+pub struct RawExpansion(pub String);
+
+impl Debug for RawExpansion {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.0)
+	}
 }
 
 pub struct BooleanExpr<'a> {
@@ -42,42 +175,182 @@ pub struct MatchExpr<'a, Value> {
 	pub expression: &'a str,
 }
 
+/// Write a single value with the configured expansion format, indented by two spaces.
+///
+/// Every line is terminated by a line break, including the last one.
+pub(crate) fn write_expanded_value(buffer: &mut String, value: &dyn Debug, color: yansi::Color) {
+	let [value] = AssertOptions::get().expand.expand_all([value]);
+	let value = value.fg(color).to_string();
+	for line in value.lines() {
+		writeln!(buffer, "  {line}").unwrap();
+	}
+}
+
+thread_local! {
+	/// The number of active [`Silence`] guards for the current thread.
+	static SILENCED: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Scope guard that suppresses printing of failed checks on the current thread.
+pub struct Silence {
+	_private: (),
+}
+
+impl Silence {
+	/// Suppress printing of failed checks until the guard is dropped.
+	pub fn enter() -> Self {
+		SILENCED.with(|x| x.set(x.get() + 1));
+		Self { _private: () }
+	}
+
+	/// Check if printing of failed checks is currently suppressed.
+	pub fn is_active() -> bool {
+		SILENCED.with(|x| x.get() > 0)
+	}
+}
+
+impl Drop for Silence {
+	fn drop(&mut self) {
+		SILENCED.with(|x| x.set(x.get() - 1));
+	}
+}
+
 impl<'a, T: CheckExpression> FailedCheck<'a, T> {
+	/// Render the failed check and print it, unless printing is suppressed by a [`Silence`] guard.
+	pub fn print(&self) -> crate::Failure {
+		self.print_with_header("Assertion failed".red().bold(), false)
+	}
+
+	/// Render the failed check as a warning and print it, unless printing is suppressed by a [`Silence`] guard.
+	pub fn print_warning(&self) -> crate::Failure {
+		self.print_with_header("Assertion warning".yellow().bold(), true)
+	}
+
+	fn print_with_header(&self, header: yansi::Painted<&str>, warning: bool) -> crate::Failure {
+		let passed = self.site.record_failure();
+		let options = {
+			let _defaults = CrateDefaults::enter(self.defaults);
+			AssertOptions::get()
+		};
+		let (failure, report) = self.render(header, passed);
+		if !warning {
+			crate::__assert2_impl::set_last_failure(&failure);
+		}
+		if !Silence::is_active() {
+			if !warning {
+				crate::junit::record(&failure);
+				crate::summary::record(&failure, options.summary);
+			}
+			crate::sarif::record(&failure, warning);
+			if let Some(handler) = crate::output::failure_handler() {
+				handler(&failure);
+			} else {
+				match options.report {
+					ReportFormat::Human if options.dedup => crate::output::write_deduplicated_report(self.site, &report, options.stream),
+					ReportFormat::Human => crate::output::write_report(&report, options.stream),
+					ReportFormat::Tap => crate::output::write_report(&tap::render(tap::next_number(), &failure, warning), options.stream),
+				}
+			}
+		}
+		failure
+	}
+
+	/// Render the failed check.
+	///
+	/// Returns the structured failure and the full report, including color codes if colors are enabled.
 	#[rustfmt::skip]
-	pub fn print(&self) {
+	fn render(&self, header: yansi::Painted<&str>, passed: usize) -> (crate::Failure, String) {
+		// Apply the crate defaults and make sure the color settings are initialized before formatting anything.
+		let _defaults = CrateDefaults::enter(self.defaults);
+		let options = AssertOptions::get();
+
 		let mut print_message = String::new();
-		writeln!(&mut print_message, "{msg} at {file}:{line}:{column}:",
-			msg    = "Assertion failed".red().bold(),
+		let mut location = format!("{file}:{line}:{column}",
 			file   = self.file.bold(),
 			line   = self.line,
 			column = self.column,
-		).unwrap();
+		);
+		if options.links {
+			location = link::hyperlink(&location, self.file, self.line, self.column);
+		}
+		writeln!(&mut print_message, "{header} at {location}:").unwrap();
 		write!(&mut print_message, "  {name}{open} ",
-			name = Paint::magenta(self.macro_name),
-			open = Paint::magenta("!("),
+			name = Themed::paint_name(self.macro_name),
+			open = Themed::paint_name("!("),
 		).unwrap();
-		self.expression.write_expression(&mut print_message);
-		writeln!(&mut print_message, " {}", Paint::magenta(")")).unwrap();
+		let mut expression = String::new();
+		self.expression.write_expression(&mut expression);
+		print_message.push_str(&expression);
+		writeln!(&mut print_message, " {}", Themed::paint_name(")")).unwrap();
+		if options.source {
+			source::write_source(&mut print_message, self.file, self.line, self.column, &crate::failure::strip_ansi(&expression));
+		}
 		if !self.fragments.is_empty() {
 			writeln!(&mut print_message, "with:").unwrap();
 			for (name, expansion) in self.fragments {
 				writeln!(
 					&mut print_message,
 					"  {} {} {}",
-					Paint::magenta(name), Paint::blue("=").bold(),
+					Themed::paint_name(name), Themed::paint_operator("=").bold(),
 					expansion
 				).unwrap();
 			}
 		}
+		let run_context = crate::context::run_context();
+		if !run_context.is_empty() {
+			writeln!(&mut print_message, "with run context:").unwrap();
+			for (key, value) in &run_context {
+				writeln!(
+					&mut print_message,
+					"  {} {} {}",
+					Themed::paint_name(key), Themed::paint_operator("=").bold(),
+					value
+				).unwrap();
+			}
+		}
+		let checkpoints = crate::__assert2_impl::checkpoint::take();
+		if !checkpoints.is_empty() {
+			writeln!(&mut print_message, "with checkpoints:").unwrap();
+			for checkpoint in &checkpoints {
+				writeln!(
+					&mut print_message,
+					"  {} {} [{}{}]",
+					Themed::paint_name(checkpoint.name), Themed::paint_operator("=").bold(),
+					checkpoint.items.join(", "),
+					if checkpoint.truncated { ", ..." } else { "" },
+				).unwrap();
+			}
+		}
+		if options.count {
+			writeln!(&mut print_message, "with history:").unwrap();
+			writeln!(&mut print_message, "  passed {} before failing",
+				Paint::bold(&format_args!("{} {}", format_count(passed), if passed == 1 { "time" } else { "times" })),
+			).unwrap();
+		}
 		self.expression.write_expansion(&mut print_message);
 		writeln!(&mut print_message, ).unwrap();
 		if let Some(msg) = self.custom_msg {
 			writeln!(&mut print_message, "with message:").unwrap();
 			writeln!(&mut print_message, "  {}", msg.bold()).unwrap();
 		}
+		if options.backtrace {
+			backtrace::write_backtrace(&mut print_message);
+		}
 		writeln!(&mut print_message).unwrap();
 
-		eprint!("{}", print_message);
+		// Remove sensitive data from everything that leaves the process.
+		let expression = crate::redact::redact(expression);
+		let print_message = crate::redact::redact(print_message);
+		let failure = crate::Failure::new(
+			self.macro_name,
+			self.file,
+			self.line,
+			self.column,
+			&expression,
+			self.custom_msg.map(|x| crate::redact::redact(x.to_string())),
+			&print_message,
+		);
+		(failure, print_message)
 	}
 }
 
@@ -85,54 +358,153 @@ impl<'a, T: CheckExpression> FailedCheck<'a, T> {
 impl<Left: Debug, Right: Debug> CheckExpression for BinaryOp<'_, Left, Right> {
 	fn write_expression(&self, print_message: &mut  String) {
 		write!(print_message, "{left} {op} {right}",
-			left  = Paint::cyan(self.left_expr),
-			op    = Paint::blue(self.operator).bold(),
-			right = Paint::yellow(self.right_expr),
+			left  = Themed::paint_left(self.left_expr),
+			op    = Themed::paint_operator(self.operator).bold(),
+			right = Themed::paint_right(self.right_expr),
 		).unwrap();
 	}
 
 	fn write_expansion(&self, print_message: &mut String) {
 		let style = AssertOptions::get();
 
+		if style.types {
+			writeln!(print_message, "with types:").unwrap();
+			writeln!(print_message, "  {}: {}", self.left_expr, Themed::paint_left(self.left_type)).unwrap();
+			writeln!(print_message, "  {}: {}", self.right_expr, Themed::paint_right(self.right_type)).unwrap();
+		}
+
+		if self.operator == "==" {
+			write_tuple_elements(print_message, &format_debug(self.left, false), &format_debug(self.right, false));
+		}
+
+		if style.text && self.write_text(print_message) {
+			return
+		}
+
+		if let (Some(left), Some(right)) = (self.left_bytes, self.right_bytes) {
+			bytes::write_byte_buffers(print_message, left, right, self.operator);
+			return
+		}
+
 		if !style.expand.force_pretty() {
-			let left = format!("{:?}", self.left);
-			let right = format!("{:?}", self.right);
+			let left = format_debug(self.left, false);
+			let right = format_debug(self.right, false);
 			if style.expand.force_compact() || ExpansionFormat::is_compact_good(&[&left, &right]) {
 				writeln!(print_message, "with expansion:").unwrap();
 				let diff = SingleLineDiff::new(&left, &right);
-				print_message.push_str("  ");
-				diff.write_left(print_message);
-				write!(print_message, " {} ", Paint::blue(self.operator)).unwrap();
-				diff.write_right(print_message);
+				match style.layout {
+					ExpansionLayout::Inline => {
+						print_message.push_str("  ");
+						diff.write_left(print_message);
+						write!(print_message, " {} ", Themed::paint_operator(self.operator)).unwrap();
+						diff.write_right(print_message);
+					},
+					ExpansionLayout::Aligned => {
+						write!(print_message, "  {}  ", Themed::paint_left("left:")).unwrap();
+						diff.write_left(print_message);
+						write!(print_message, "\n  {} ", Themed::paint_right("right:")).unwrap();
+						diff.write_right(print_message);
+					},
+				}
 				if left == right {
-					if self.operator == "==" {
-						write!(print_message, "\n{}", "Note: Left and right compared as unequal, but the Debug output of left and right is identical!".red()).unwrap();
-					} else {
-						write!(print_message, "\n{}", "Note: Debug output of left and right is identical.".bold()).unwrap();
-					}
+					print_message.push('\n');
+					self.write_identical_note(print_message, style.types, &left);
 				}
 				return
 			}
 		}
 
 		// Compact expansion was disabled or not compact enough, so go full-on pretty debug format.
-		let left = format!("{:#?}", self.left);
-		let right = format!("{:#?}", self.right);
+		let left = format_debug(self.left, true);
+		let right = format_debug(self.right, true);
 		writeln!(print_message, "with diff:").unwrap();
 		MultiLineDiff::new(&left, &right)
 			.write_interleaved(print_message);
+		if left == right {
+			self.write_identical_note(print_message, style.types, &left);
+		}
+	}
+}
+
+#[rustfmt::skip]
+impl<Left: Debug, Right: Debug> BinaryOp<'_, Left, Right> {
+	/// Write string operands that contain line breaks as raw text with line numbers.
+	///
+	/// If both sides are strings and the operator is `==`, write a line diff of the raw text instead.
+	/// Returns false without writing anything if neither side is a multi-line string.
+	fn write_text(&self, print_message: &mut String) -> bool {
+		let is_multi_line = |text: Option<&str>| text.map_or(false, |text| text.contains('\n'));
+		if !is_multi_line(self.left_text) && !is_multi_line(self.right_text) {
+			return false;
+		}
+
+		if let (Some(left), Some(right), "==") = (self.left_text, self.right_text, self.operator) {
+			writeln!(print_message, "with text diff:").unwrap();
+			MultiLineDiff::new(left, right)
+				.write_interleaved(print_message);
+			return true;
+		}
+
+		writeln!(print_message, "with text:").unwrap();
+		match self.left_text {
+			Some(text) => {
+				writeln!(print_message, "  {}:", self.left_expr).unwrap();
+				text::write_numbered_lines(print_message, text, crate::theme::left());
+			},
+			None => writeln!(print_message, "  {}: {}", self.left_expr, Themed::paint_left(&format_debug(self.left, false))).unwrap(),
+		}
+		match self.right_text {
+			Some(text) => {
+				writeln!(print_message, "  {}:", self.right_expr).unwrap();
+				text::write_numbered_lines(print_message, text, crate::theme::right());
+			},
+			None => writeln!(print_message, "  {}: {}", self.right_expr, Themed::paint_right(&format_debug(self.right, false))).unwrap(),
+		}
+		// Remove last newline.
+		print_message.pop();
+		true
+	}
+
+	/// Explain a failed comparison of values with identical `Debug` output.
+	///
+	/// For `==`, print the types and the addresses of references, since those are not visible in the `Debug` output.
+	fn write_identical_note(&self, print_message: &mut String, types_printed: bool, debug: &str) {
+		if self.operator != "==" {
+			write!(print_message, "{}", "Note: Debug output of left and right is identical.".bold()).unwrap();
+			return;
+		}
+		write!(print_message, "{}", "Note: Left and right compared as unequal, but the Debug output of left and right is identical!".red()).unwrap();
+		if !types_printed {
+			write!(print_message, "\n  left type:  {}", Themed::paint_left(self.left_type)).unwrap();
+			write!(print_message, "\n  right type: {}", Themed::paint_right(self.right_type)).unwrap();
+		}
+		if let Some(address) = self.left_address {
+			write!(print_message, "\n  left address:  {}", Themed::paint_left(&format_args!("{address:p}"))).unwrap();
+		}
+		if let Some(address) = self.right_address {
+			write!(print_message, "\n  right address: {}", Themed::paint_right(&format_args!("{address:p}"))).unwrap();
+		}
+		if debug.contains("NaN") {
+			write!(print_message, "\nhint: `NaN` is not equal to anything, not even to itself").unwrap();
+		} else if self.left_address.is_some() && self.left_address == self.right_address {
+			write!(print_message, "\nhint: both sides are the same value, so its `PartialEq` implementation is not reflexive (like for `NaN`)").unwrap();
+		} else if self.left_type != self.right_type {
+			write!(print_message, "\nhint: the types differ, so check the `PartialEq` implementation between them").unwrap();
+		} else {
+			write!(print_message, "\nhint: the values may differ in state that is not shown by their `Debug` implementation").unwrap();
+		}
 	}
 }
 
 #[rustfmt::skip]
 impl CheckExpression for BooleanExpr<'_> {
 	fn write_expression(&self, print_message: &mut  String) {
-		write!(print_message, "{}", Paint::cyan(self.expression)).unwrap();
+		write!(print_message, "{}", Themed::paint_left(self.expression)).unwrap();
 	}
 
 	fn write_expansion(&self, print_message: &mut String) {
 		writeln!(print_message, "with expansion:").unwrap();
-		write!(print_message, "  {:?}", false.cyan()).unwrap();
+		write!(print_message, "  {:?}", false.paint_left()).unwrap();
 	}
 }
 
@@ -140,22 +512,25 @@ impl CheckExpression for BooleanExpr<'_> {
 impl<Value: Debug> CheckExpression for MatchExpr<'_, Value> {
 	fn write_expression(&self, buffer: &mut String) {
 		if self.print_let {
-			write!(buffer, "{} ", Paint::blue("let").bold()).unwrap();
+			write!(buffer, "{} ", Themed::paint_operator("let").bold()).unwrap();
 		}
 		write!(buffer, "{pat} {eq} {expr}",
-			pat  = Paint::cyan(self.pattern),
-			eq   = Paint::blue("=").bold(),
-			expr = Paint::yellow(self.expression),
+			pat  = Themed::paint_left(self.pattern),
+			eq   = Themed::paint_operator("=").bold(),
+			expr = Themed::paint_right(self.expression),
 		).unwrap();
 	}
 
 	fn write_expansion(&self, print_message: &mut String) {
-		writeln!(print_message, "with expansion:").unwrap();
-		let [value] = AssertOptions::get().expand.expand_all([&self.value]);
-		let message = value.yellow().to_string();
-		for line in message.lines() {
-			writeln!(print_message, "  {line}").unwrap();
+		let value = format!("{:?}", self.value);
+		if let Some((expected, actual)) = mismatched_variant(self.pattern, &value) {
+			writeln!(print_message, "actual variant: {}", Themed::paint_right(actual)).unwrap();
+			if is_similar(expected, actual) {
+				writeln!(print_message, "hint: did you mean {} instead of {}?", Themed::paint_right(actual), Themed::paint_left(expected)).unwrap();
+			}
 		}
+		writeln!(print_message, "with expansion:").unwrap();
+		write_expanded_value(print_message, self.value, crate::theme::right());
 		// Remove last newline.
 		print_message.pop();
 	}
@@ -0,0 +1,29 @@
+use std::fmt::Write;
+
+use super::CheckExpression;
+use crate::theme::Themed;
+
+/// A failed negated expression: `!inner`.
+pub struct Negation<'a> {
+	/// The full expression.
+	pub expression: &'a str,
+
+	/// The operands of the inner expression, with their `Debug` output.
+	pub operands: &'a [(&'a str, &'a str)],
+}
+
+#[rustfmt::skip]
+impl CheckExpression for Negation<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{}", Themed::paint_left(self.expression)).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with operands:").unwrap();
+		for (name, value) in self.operands {
+			writeln!(buffer, "  {} {} {}", name, Themed::paint_operator("=").bold(), Themed::paint_left(value)).unwrap();
+		}
+		writeln!(buffer, "with expansion:").unwrap();
+		write!(buffer, "  {}{:?}", Themed::paint_operator("!").bold(), true.paint_left()).unwrap();
+	}
+}
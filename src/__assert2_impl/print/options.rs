@@ -1,3 +1,10 @@
+use super::format_debug;
+use crate::diff::DiffAlgorithm;
+use crate::theme::ThemePreset;
+
+/// The default number of identical lines shown around each change in a multi-line diff.
+const DEFAULT_DIFF_CONTEXT: usize = 3;
+
 /// End-user configurable options for `assert2`.
 #[derive(Copy, Clone)]
 pub struct AssertOptions {
@@ -6,15 +13,76 @@ pub struct AssertOptions {
 
 	/// If true, use colors in the output.
 	pub color: bool,
+
+	/// If true, print how many times the assertion passed before it failed.
+	pub count: bool,
+
+	/// If true, print values that implement both `Debug` and `Display` with their `Display` implementation.
+	pub display: bool,
+
+	/// The maximum length in bytes of a formatted value, if any.
+	pub max_len: Option<usize>,
+
+	/// The number of identical lines to show around each change in a multi-line diff, or `None` to show all lines.
+	pub diff_context: Option<usize>,
+
+	/// The algorithm used for multi-line diffs.
+	pub diff_algorithm: DiffAlgorithm,
+
+	/// If true, print the types of the operands of comparisons.
+	pub types: bool,
+
+	/// The format for integer operands of comparisons.
+	pub integers: IntegerFormat,
+
+	/// The colors supported by the terminal.
+	pub palette: ColorPalette,
+
+	/// The built-in theme for the colors of the output.
+	pub theme: ThemePreset,
+
+	/// The width of the terminal in columns, if overridden.
+	pub width: Option<usize>,
+
+	/// The format of the reports of failed assertions.
+	pub report: ReportFormat,
+
+	/// The stream to print the reports of failed assertions to.
+	pub stream: OutputStream,
+
+	/// If true, print a summary of all failed assertions when the process exits.
+	pub summary: bool,
+
+	/// If true, count identical failures of the same assertion instead of printing them again.
+	pub dedup: bool,
+
+	/// If true, print a backtrace of the user code that led to a failed assertion.
+	pub backtrace: bool,
+
+	/// If true, print the source lines around a failed assertion.
+	pub source: bool,
+
+	/// If true, print string operands that contain line breaks as raw text with line numbers.
+	pub text: bool,
+
+	/// If true, sort the entries of sets and maps in formatted values.
+	pub sort: bool,
+
+	/// The layout of the compact expansion of comparisons.
+	pub layout: ExpansionLayout,
+
+	/// If true, print the location of a failed assertion as a hyperlink to the source file.
+	pub links: bool,
 }
 
 impl AssertOptions {
-	/// Get the global options for `assert2`.
+	/// Get the options for `assert2`.
 	///
 	/// The default format is `ExpansionFormat::Auto`.
 	/// This can be overridden by adding the `pretty` or `compact` option to the `ASSERT2` environment variable.
 	///
-	/// By default, colored output is enabled if `stderr` is conntected to a terminal.
+	/// By default, colored output is enabled if `stderr` is conntected to a terminal,
+	/// unless the `TERM` environment variable says that it is a dumb terminal.
 	/// If the `CLICOLOR` environment variable is set to `0`, colored output is disabled by default.
 	/// If the `CLICOLOR_FORCE` environment variable is set to something other than `0`,
 	/// color is enabled by default, even if `stderr` is not connected to a terminal.
@@ -24,63 +92,353 @@ impl AssertOptions {
 	/// Whitespace around the comma is ignored.
 	/// For example: `ASSERT2=color,pretty` to force colored output and the pretty debug format.
 	///
+	/// The `count` option adds the number of times the assertion passed before it failed to the output.
+	/// It can be disabled again with `no-count`.
+	///
+	/// The `display` option prints values that implement both `Debug` and `Display` with `Display`.
+	/// The `debug` option restores the default of using `Debug`.
+	///
+	/// The `max-len=N` option truncates formatted values that are longer than `N` bytes, keeping the start and the end.
+	/// It can be disabled again with `no-max-len`.
+	///
+	/// The `diff-context=N` option sets the number of identical lines to show around each change in a multi-line diff.
+	/// Longer runs of identical lines are folded.
+	/// With `diff-context=all`, all lines are shown.
+	///
+	/// The `diff=patience` option selects the patience diff algorithm for multi-line diffs,
+	/// and `diff=lcs` selects the default longest common subsequence algorithm.
+	///
+	/// The `types` option prints the type of each operand of a comparison.
+	/// It can be disabled again with `no-types`.
+	///
+	/// The `hex` and `binary` options print integer operands of comparisons in hexadecimal or binary.
+	/// The `decimal` option restores the default of printing them in decimal.
+	///
+	/// The `palette=16`, `palette=256` and `palette=truecolor` options set the colors supported by the terminal.
+	/// By default, the palette is detected from the `COLORTERM` and `TERM` environment variables.
+	/// With 256 colors or true colors, the differences in a diff are highlighted with a soft background color.
+	///
+	/// The `theme=colorblind` option selects colors that can be told apart with the common forms of color blindness,
+	/// and underlines highlighted differences. The `theme=default` option selects the default colors.
+	///
+	/// The `width=N` option sets the width of the terminal used to lay out the output,
+	/// instead of the `COLUMNS` environment variable. With `width=auto`, the `COLUMNS` environment variable is used again.
+	///
+	/// The `report=tap` option prints failed assertions as `not ok` lines of the Test Anything Protocol,
+	/// with the details in a YAML diagnostics block. The `report=human` option restores the default report.
+	///
+	/// The `output=stdout` option prints failed assertions to `stdout` instead of `stderr`,
+	/// and `output=stderr` restores the default.
+	/// Colors are enabled by default if the selected stream is connected to a terminal.
+	///
+	/// The `summary` option prints a summary of all failed assertions when the process exits (see [`print_summary()`](crate::print_summary)).
+	/// It can be disabled again with `no-summary`.
+	///
+	/// By default, identical failures of the same assertion, like a `check!()` in a loop, are only printed once,
+	/// followed by the number of identical failures that were not printed.
+	/// The `no-dedup` option prints every failure, and `dedup` restores the default.
+	///
+	/// The `backtrace` option prints a backtrace under the failure, without the frames of the standard library and the test harness.
+	/// It can be disabled again with `no-backtrace`.
+	///
+	/// The `source` option prints the source lines around a failed assertion, with a caret under the failed expression.
+	/// It can be disabled again with `no-source`.
+	///
+	/// The `text` option prints string operands of comparisons that contain line breaks as raw text with line numbers,
+	/// instead of a single line with escaped line breaks. If both sides are strings, a line diff of the raw text is printed for `==`.
+	/// It can be disabled again with `no-text`.
+	///
+	/// The `sort` option sorts the entries of sets and maps in formatted values,
+	/// so that a different iteration order of a `HashMap` or `HashSet` does not show up as a difference.
+	/// It can be disabled again with `no-sort`.
+	///
+	/// The `layout=aligned` option prints the compact expansion of a comparison on two lines,
+	/// with the left and right value after aligned `left:` and `right:` labels.
+	/// The `layout=inline` option restores the default of printing both values on one line, separated by the operator.
+	///
+	/// The `links` option prints the location of a failed assertion as an OSC 8 hyperlink to the source file,
+	/// and `no-links` prints it as plain text.
+	/// By default, hyperlinks are used if the selected stream is connected to a terminal that is known to support them.
+	///
+	/// The crate that contains the failed assertion can set defaults for these options at build time,
+	/// with the `ASSERT2_DEFAULTS` environment variable (see [`CrateDefaults`]).
+	/// The runtime environment variables take precedence over the crate defaults,
+	/// except for the terminal detection of the clicolors specification.
 	pub fn get() -> AssertOptions {
+		let env = EnvOptions::get();
+		let defaults = PartialOptions::parse(CRATE_DEFAULTS.with(|x| x.get()).unwrap_or(""));
+
+		let style = AssertOptions {
+			expand: env.options.expand.or(defaults.expand).unwrap_or(ExpansionFormat::Auto),
+			color: crate::color::color_override()
+				.or(env.options.color)
+				.or(env.clicolors)
+				.or(defaults.color)
+				.unwrap_or(env.is_terminal),
+			count: env.options.count.or(defaults.count).unwrap_or(false),
+			display: env.options.display.or(defaults.display).unwrap_or(false),
+			max_len: env.options.max_len.or(defaults.max_len).flatten(),
+			diff_context: env.options.diff_context.or(defaults.diff_context).unwrap_or(Some(DEFAULT_DIFF_CONTEXT)),
+			diff_algorithm: env.options.diff_algorithm.or(defaults.diff_algorithm).unwrap_or(DiffAlgorithm::Lcs),
+			types: env.options.types.or(defaults.types).unwrap_or(false),
+			integers: env.options.integers.or(defaults.integers).unwrap_or(IntegerFormat::Decimal),
+			palette: env.options.palette.or(defaults.palette).unwrap_or(env.palette),
+			theme: env.options.theme.or(defaults.theme).unwrap_or(ThemePreset::Default),
+			width: env.options.width.or(defaults.width).flatten(),
+			report: env.options.report.or(defaults.report).unwrap_or(ReportFormat::Human),
+			stream: env.options.stream.or(defaults.stream).unwrap_or(OutputStream::Stderr),
+			summary: env.options.summary.or(defaults.summary).unwrap_or(false),
+			dedup: env.options.dedup.or(defaults.dedup).unwrap_or(true),
+			backtrace: env.options.backtrace.or(defaults.backtrace).unwrap_or(false),
+			source: env.options.source.or(defaults.source).unwrap_or(false),
+			text: env.options.text.or(defaults.text).unwrap_or(false),
+			sort: env.options.sort.or(defaults.sort).unwrap_or(false),
+			layout: env.options.layout.or(defaults.layout).unwrap_or(ExpansionLayout::Inline),
+			links: env.options.links.or(defaults.links).unwrap_or(env.is_terminal && env.hyperlinks),
+		};
+		if style.color {
+			yansi::whenever(yansi::Condition::ALWAYS)
+		} else {
+			yansi::whenever(yansi::Condition::NEVER)
+		}
+		style
+	}
+}
+
+thread_local! {
+	/// The build time defaults of the crate that contains the assertion that is being rendered.
+	static CRATE_DEFAULTS: std::cell::Cell<Option<&'static str>> = const { std::cell::Cell::new(None) };
+}
+
+/// Scope guard that applies the build time defaults of a crate to the options on the current thread.
+///
+/// The defaults are taken from the `ASSERT2_DEFAULTS` environment variable
+/// when the crate that contains the assertion is compiled.
+/// It uses the same syntax as the `ASSERT2` environment variable.
+pub struct CrateDefaults {
+	previous: Option<&'static str>,
+}
+
+impl CrateDefaults {
+	/// Apply the build time defaults of a crate until the guard is dropped.
+	pub fn enter(defaults: Option<&'static str>) -> Self {
+		let previous = CRATE_DEFAULTS.with(|x| x.replace(defaults));
+		Self { previous }
+	}
+}
+
+impl Drop for CrateDefaults {
+	fn drop(&mut self) {
+		CRATE_DEFAULTS.with(|x| x.set(self.previous));
+	}
+}
+
+/// Options where each field may or may not be set.
+#[derive(Copy, Clone, Default)]
+struct PartialOptions {
+	expand: Option<ExpansionFormat>,
+	color: Option<bool>,
+	count: Option<bool>,
+	display: Option<bool>,
+	max_len: Option<Option<usize>>,
+	diff_context: Option<Option<usize>>,
+	diff_algorithm: Option<DiffAlgorithm>,
+	types: Option<bool>,
+	integers: Option<IntegerFormat>,
+	palette: Option<ColorPalette>,
+	theme: Option<ThemePreset>,
+	width: Option<Option<usize>>,
+	report: Option<ReportFormat>,
+	stream: Option<OutputStream>,
+	summary: Option<bool>,
+	dedup: Option<bool>,
+	backtrace: Option<bool>,
+	source: Option<bool>,
+	text: Option<bool>,
+	sort: Option<bool>,
+	layout: Option<ExpansionLayout>,
+	links: Option<bool>,
+}
+
+impl PartialOptions {
+	/// Parse a comma separated list of options.
+	fn parse(format: &str) -> Self {
+		let mut output = Self::default();
+		for word in format.split(',') {
+			let word = word.trim();
+			if word.eq_ignore_ascii_case("pretty") {
+				output.expand = Some(ExpansionFormat::Pretty);
+			} else if word.eq_ignore_ascii_case("compact") {
+				output.expand = Some(ExpansionFormat::Compact);
+			} else if word.eq_ignore_ascii_case("auto") {
+				output.expand = Some(ExpansionFormat::Auto);
+			} else if word.eq_ignore_ascii_case("color") {
+				output.color = Some(true);
+			} else if word.eq_ignore_ascii_case("no-color") {
+				output.color = Some(false);
+			} else if word.eq_ignore_ascii_case("count") {
+				output.count = Some(true);
+			} else if word.eq_ignore_ascii_case("no-count") {
+				output.count = Some(false);
+			} else if word.eq_ignore_ascii_case("display") {
+				output.display = Some(true);
+			} else if word.eq_ignore_ascii_case("debug") {
+				output.display = Some(false);
+			} else if word.eq_ignore_ascii_case("no-max-len") {
+				output.max_len = Some(None);
+			} else if let Some(max_len) = word.strip_prefix("max-len=") {
+				if let Ok(max_len) = max_len.trim().parse() {
+					output.max_len = Some(Some(max_len));
+				}
+			} else if let Some(context) = word.strip_prefix("diff-context=") {
+				let context = context.trim();
+				if context.eq_ignore_ascii_case("all") {
+					output.diff_context = Some(None);
+				} else if let Ok(context) = context.parse() {
+					output.diff_context = Some(Some(context));
+				}
+			} else if word.eq_ignore_ascii_case("diff=patience") {
+				output.diff_algorithm = Some(DiffAlgorithm::Patience);
+			} else if word.eq_ignore_ascii_case("diff=lcs") {
+				output.diff_algorithm = Some(DiffAlgorithm::Lcs);
+			} else if word.eq_ignore_ascii_case("types") {
+				output.types = Some(true);
+			} else if word.eq_ignore_ascii_case("no-types") {
+				output.types = Some(false);
+			} else if word.eq_ignore_ascii_case("hex") {
+				output.integers = Some(IntegerFormat::Hex);
+			} else if word.eq_ignore_ascii_case("binary") {
+				output.integers = Some(IntegerFormat::Binary);
+			} else if word.eq_ignore_ascii_case("decimal") {
+				output.integers = Some(IntegerFormat::Decimal);
+			} else if let Some(palette) = word.strip_prefix("palette=") {
+				let palette = palette.trim();
+				if palette == "16" {
+					output.palette = Some(ColorPalette::Basic);
+				} else if palette == "256" {
+					output.palette = Some(ColorPalette::Fixed);
+				} else if palette.eq_ignore_ascii_case("truecolor") {
+					output.palette = Some(ColorPalette::TrueColor);
+				}
+			} else if word.eq_ignore_ascii_case("theme=colorblind") {
+				output.theme = Some(ThemePreset::Colorblind);
+			} else if word.eq_ignore_ascii_case("theme=default") {
+				output.theme = Some(ThemePreset::Default);
+			} else if let Some(width) = word.strip_prefix("width=") {
+				let width = width.trim();
+				if width.eq_ignore_ascii_case("auto") {
+					output.width = Some(None);
+				} else if let Ok(width) = width.parse() {
+					output.width = Some(Some(width));
+				}
+			} else if word.eq_ignore_ascii_case("report=tap") {
+				output.report = Some(ReportFormat::Tap);
+			} else if word.eq_ignore_ascii_case("report=human") {
+				output.report = Some(ReportFormat::Human);
+			} else if word.eq_ignore_ascii_case("output=stdout") {
+				output.stream = Some(OutputStream::Stdout);
+			} else if word.eq_ignore_ascii_case("output=stderr") {
+				output.stream = Some(OutputStream::Stderr);
+			} else if word.eq_ignore_ascii_case("summary") {
+				output.summary = Some(true);
+			} else if word.eq_ignore_ascii_case("no-summary") {
+				output.summary = Some(false);
+			} else if word.eq_ignore_ascii_case("dedup") {
+				output.dedup = Some(true);
+			} else if word.eq_ignore_ascii_case("no-dedup") {
+				output.dedup = Some(false);
+			} else if word.eq_ignore_ascii_case("backtrace") {
+				output.backtrace = Some(true);
+			} else if word.eq_ignore_ascii_case("no-backtrace") {
+				output.backtrace = Some(false);
+			} else if word.eq_ignore_ascii_case("source") {
+				output.source = Some(true);
+			} else if word.eq_ignore_ascii_case("no-source") {
+				output.source = Some(false);
+			} else if word.eq_ignore_ascii_case("text") {
+				output.text = Some(true);
+			} else if word.eq_ignore_ascii_case("no-text") {
+				output.text = Some(false);
+			} else if word.eq_ignore_ascii_case("sort") {
+				output.sort = Some(true);
+			} else if word.eq_ignore_ascii_case("no-sort") {
+				output.sort = Some(false);
+			} else if word.eq_ignore_ascii_case("layout=aligned") {
+				output.layout = Some(ExpansionLayout::Aligned);
+			} else if word.eq_ignore_ascii_case("layout=inline") {
+				output.layout = Some(ExpansionLayout::Inline);
+			} else if word.eq_ignore_ascii_case("links") {
+				output.links = Some(true);
+			} else if word.eq_ignore_ascii_case("no-links") {
+				output.links = Some(false);
+			}
+		}
+		output
+	}
+}
+
+/// The options taken from the runtime environment.
+#[derive(Copy, Clone)]
+struct EnvOptions {
+	/// The options from the `ASSERT2` environment variable.
+	options: PartialOptions,
+
+	/// The color setting from the clicolors environment variables, if any.
+	clicolors: Option<bool>,
+
+	/// If true, the selected output stream is connected to a terminal that supports styled output.
+	is_terminal: bool,
+
+	/// The palette detected from the `COLORTERM` and `TERM` environment variables.
+	palette: ColorPalette,
+
+	/// If true, the terminal is known to support hyperlinks.
+	hyperlinks: bool,
+}
+
+impl EnvOptions {
+	/// Get the options from the runtime environment, parsing them on first use.
+	fn get() -> Self {
 		use std::sync::RwLock;
 
-		static STYLE: RwLock<Option<AssertOptions>> = RwLock::new(None);
+		static OPTIONS: RwLock<Option<EnvOptions>> = RwLock::new(None);
 		loop {
 			// If it's already initialized, just return it.
-			if let Some(style) = *STYLE.read().unwrap() {
-				return style;
+			if let Some(options) = *OPTIONS.read().unwrap() {
+				return options;
 			}
 
-			// Style wasn't set yet, so try to get a write lock to initialize the style.
-			match STYLE.try_write() {
-				// If we fail to get a write lock, another thread is already initializing the style,
+			// Options weren't parsed yet, so try to get a write lock to initialize them.
+			match OPTIONS.try_write() {
+				// If we fail to get a write lock, another thread is already initializing the options,
 				// so we just loop back to the start of the function and try the read lock again.
 				Err(_) => continue,
 
-				// If we get the write lock it is up to use to initialize the style.
-				Ok(mut style) => {
-					let style = style.get_or_insert_with(AssertOptions::from_env);
-					if style.color {
-						yansi::whenever(yansi::Condition::ALWAYS)
-					} else {
-						yansi::whenever(yansi::Condition::NEVER)
-					}
-					return *style;
-				}
+				// If we get the write lock it is up to use to initialize the options.
+				Ok(mut options) => return *options.get_or_insert_with(EnvOptions::from_env),
 			}
 		}
 	}
 
-	/// Parse the options from the `ASSERT2` environment variable.
+	/// Parse the options from the environment variables.
 	fn from_env() -> Self {
 		// If there is no valid `ASSERT2` environment variable, default to an empty string.
 		let format = std::env::var_os("ASSERT2");
 		let format = format.as_ref().and_then(|x| x.to_str()).unwrap_or("");
 
-		// Start with the defaults.
-		let mut output = Self {
-			expand: ExpansionFormat::Auto,
-			color: should_color(),
+		use is_terminal::IsTerminal;
+		let options = PartialOptions::parse(format);
+		let is_terminal = match options.stream {
+			Some(OutputStream::Stdout) => std::io::stdout().is_terminal(),
+			Some(OutputStream::Stderr) | None => std::io::stderr().is_terminal(),
 		};
-
-		// And modify them based on the options in the environment variables.
-		for word in format.split(',') {
-			let word = word.trim();
-			if word.eq_ignore_ascii_case("pretty") {
-				output.expand = ExpansionFormat::Pretty;
-			} else if word.eq_ignore_ascii_case("compact") {
-				output.expand = ExpansionFormat::Compact;
-			} else if word.eq_ignore_ascii_case("color") {
-				output.color = true;
-			} else if word.eq_ignore_ascii_case("no-color") {
-				output.color = false;
-			}
+		Self {
+			options,
+			clicolors: clicolors(),
+			is_terminal: is_terminal && supports_styles(&std::env::var("TERM").unwrap_or_default()),
+			palette: detect_palette(),
+			hyperlinks: hyperlinks_from_env(|name| std::env::var(name).ok()),
 		}
-
-		output
 	}
 }
 
@@ -114,12 +472,12 @@ impl ExpansionFormat {
 	/// Expand all items according to the style.
 	pub fn expand_all<const N: usize>(self, values: [&dyn std::fmt::Debug; N]) -> [String; N] {
 		if !self.force_pretty() {
-			let expanded = values.map(|x| format!("{x:?}"));
+			let expanded = values.map(|x| format_debug(x, false));
 			if self.force_compact() || Self::is_compact_good(&expanded) {
 				return expanded;
 			}
 		}
-		values.map(|x| format!("{x:#?}"))
+		values.map(|x| format_debug(x, true))
 	}
 
 	/// Heuristicly determine if a compact debug representation is good for all expanded items.
@@ -138,8 +496,105 @@ impl ExpansionFormat {
 	}
 }
 
+/// The format for integer operands of comparisons.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum IntegerFormat {
+	/// Print integers in decimal, with their normal `Debug` implementation.
+	Decimal,
+
+	/// Print integers in hexadecimal, padded to the width of their type.
+	Hex,
+
+	/// Print integers in binary, padded to the width of their type.
+	Binary,
+}
+
+/// The colors supported by the terminal.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ColorPalette {
+	/// Only the 16 basic colors.
+	Basic,
+
+	/// The 256 color palette.
+	Fixed,
+
+	/// True colors with 8 bits per component.
+	TrueColor,
+}
+
+/// The layout of the compact expansion of comparisons.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ExpansionLayout {
+	/// Print both values on one line, separated by the operator: `left == right`.
+	Inline,
+
+	/// Print each value on its own line, after aligned `left:` and `right:` labels.
+	Aligned,
+}
+
+/// The format of the reports of failed assertions.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ReportFormat {
+	/// The default report, meant to be read by humans.
+	Human,
+
+	/// A `not ok` line of the Test Anything Protocol, with the details in a YAML diagnostics block.
+	Tap,
+}
+
+/// The stream to print the reports of failed assertions to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OutputStream {
+	/// Print to `stderr`.
+	Stderr,
+
+	/// Print to `stdout`.
+	Stdout,
+}
+
+/// Check if a terminal supports styled output, based on the value of the `TERM` environment variable.
+///
+/// Dumb terminals, like the output panes of some editors, print escape codes as garbage.
+/// An empty value is accepted, since many terminals on Windows do not set `TERM` at all.
+fn supports_styles(term: &str) -> bool {
+	term != "dumb"
+}
+
+/// Detect the palette of the terminal from the `COLORTERM` and `TERM` environment variables.
+fn detect_palette() -> ColorPalette {
+	let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+	let term = std::env::var("TERM").unwrap_or_default();
+	palette_from_env(&colorterm, &term)
+}
+
+/// Determine the palette from the values of the `COLORTERM` and `TERM` environment variables.
+fn palette_from_env(colorterm: &str, term: &str) -> ColorPalette {
+	if colorterm.eq_ignore_ascii_case("truecolor") || colorterm.eq_ignore_ascii_case("24bit") {
+		ColorPalette::TrueColor
+	} else if term.contains("256color") {
+		ColorPalette::Fixed
+	} else {
+		ColorPalette::Basic
+	}
+}
+
+/// Check if the environment variables show a terminal that is known to support OSC 8 hyperlinks.
+fn hyperlinks_from_env(var: impl Fn(&str) -> Option<String>) -> bool {
+	let term_program = var("TERM_PROGRAM").unwrap_or_default();
+	let term = var("TERM").unwrap_or_default();
+	let vte_version = var("VTE_VERSION").and_then(|x| x.parse::<u32>().ok()).unwrap_or(0);
+	["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper"].contains(&term_program.as_str())
+		|| ["xterm-kitty", "alacritty", "foot"].iter().any(|x| term.starts_with(x))
+		|| vte_version >= 5000
+		|| var("WT_SESSION").is_some()
+		|| var("KONSOLE_VERSION").is_some()
+}
+
 /// Check if the clicolors spec thinks we should use colors.
-fn should_color() -> bool {
+///
+/// Returns `None` if none of the environment variables are set,
+/// in which case colors should be used if the output is going to a terminal.
+fn clicolors() -> Option<bool> {
 	use std::ffi::OsStr;
 
 	/// Check if an environment variable has a false-like value.
@@ -157,13 +612,186 @@ fn should_color() -> bool {
 
 	#[allow(clippy::if_same_then_else)] // shut up clippy
 	if std::env::var_os("NO_COLOR").map(is_true).unwrap_or_default() {
-		false
+		Some(false)
 	} else if std::env::var_os("CLICOLOR").map(is_false).unwrap_or_default() {
-		false
+		Some(false)
 	} else if std::env::var_os("CLICOLOR_FORCE").map(is_true).unwrap_or_default() {
-		true
+		Some(true)
 	} else {
-		use is_terminal::IsTerminal;
-		std::io::stderr().is_terminal()
+		None
 	}
 }
+
+#[test]
+fn test_parse_partial_options() {
+	use crate::assert;
+	let options = PartialOptions::parse("");
+	assert!(options.expand.is_none());
+	assert!(options.color.is_none());
+	assert!(options.count.is_none());
+
+	let options = PartialOptions::parse("pretty, no-color");
+	assert!(options.expand == Some(ExpansionFormat::Pretty));
+	assert!(options.color == Some(false));
+
+	let options = PartialOptions::parse("compact,auto,bogus,COLOR");
+	assert!(options.expand == Some(ExpansionFormat::Auto));
+	assert!(options.color == Some(true));
+
+	let options = PartialOptions::parse("count");
+	assert!(options.count == Some(true));
+	let options = PartialOptions::parse("count,no-count");
+	assert!(options.count == Some(false));
+
+	let options = PartialOptions::parse("display");
+	assert!(options.display == Some(true));
+	assert!(options.max_len.is_none());
+
+	let options = PartialOptions::parse("max-len=4096, debug");
+	assert!(options.display == Some(false));
+	assert!(options.max_len == Some(Some(4096)));
+	let options = PartialOptions::parse("max-len=4096,no-max-len");
+	assert!(options.max_len == Some(None));
+	let options = PartialOptions::parse("max-len=lots");
+	assert!(options.max_len.is_none());
+
+	let options = PartialOptions::parse("diff-context=5");
+	assert!(options.diff_context == Some(Some(5)));
+	let options = PartialOptions::parse("diff-context=ALL");
+	assert!(options.diff_context == Some(None));
+	let options = PartialOptions::parse("diff-context=-1");
+	assert!(options.diff_context.is_none());
+
+	let options = PartialOptions::parse("diff=patience");
+	assert!(options.diff_algorithm == Some(DiffAlgorithm::Patience));
+	let options = PartialOptions::parse("diff=patience,diff=lcs");
+	assert!(options.diff_algorithm == Some(DiffAlgorithm::Lcs));
+	assert!(options.types.is_none());
+
+	let options = PartialOptions::parse("types");
+	assert!(options.types == Some(true));
+	let options = PartialOptions::parse("types, no-types");
+	assert!(options.types == Some(false));
+	assert!(options.integers.is_none());
+
+	let options = PartialOptions::parse("hex");
+	assert!(options.integers == Some(IntegerFormat::Hex));
+	let options = PartialOptions::parse("hex,BINARY");
+	assert!(options.integers == Some(IntegerFormat::Binary));
+	let options = PartialOptions::parse("binary, decimal");
+	assert!(options.integers == Some(IntegerFormat::Decimal));
+	assert!(options.palette.is_none());
+
+	let options = PartialOptions::parse("palette=256");
+	assert!(options.palette == Some(ColorPalette::Fixed));
+	let options = PartialOptions::parse("palette=TrueColor, palette=16");
+	assert!(options.palette == Some(ColorPalette::Basic));
+	let options = PartialOptions::parse("palette=88");
+	assert!(options.palette.is_none());
+	assert!(options.theme.is_none());
+
+	let options = PartialOptions::parse("theme=colorblind");
+	assert!(options.theme == Some(ThemePreset::Colorblind));
+	let options = PartialOptions::parse("theme=colorblind,theme=default");
+	assert!(options.theme == Some(ThemePreset::Default));
+	assert!(options.width.is_none());
+
+	let options = PartialOptions::parse("width=200");
+	assert!(options.width == Some(Some(200)));
+	let options = PartialOptions::parse("width=200, width=auto");
+	assert!(options.width == Some(None));
+	let options = PartialOptions::parse("width=wide");
+	assert!(options.width.is_none());
+	assert!(options.report.is_none());
+
+	let options = PartialOptions::parse("report=tap");
+	assert!(options.report == Some(ReportFormat::Tap));
+	let options = PartialOptions::parse("report=tap, report=human");
+	assert!(options.report == Some(ReportFormat::Human));
+	assert!(options.stream.is_none());
+
+	let options = PartialOptions::parse("output=stdout");
+	assert!(options.stream == Some(OutputStream::Stdout));
+	let options = PartialOptions::parse("output=stdout, output=stderr");
+	assert!(options.stream == Some(OutputStream::Stderr));
+	assert!(options.summary.is_none());
+
+	let options = PartialOptions::parse("summary");
+	assert!(options.summary == Some(true));
+	let options = PartialOptions::parse("summary, no-summary");
+	assert!(options.summary == Some(false));
+	assert!(options.dedup.is_none());
+
+	let options = PartialOptions::parse("no-dedup");
+	assert!(options.dedup == Some(false));
+	let options = PartialOptions::parse("no-dedup, dedup");
+	assert!(options.dedup == Some(true));
+	assert!(options.backtrace.is_none());
+
+	let options = PartialOptions::parse("backtrace");
+	assert!(options.backtrace == Some(true));
+	let options = PartialOptions::parse("backtrace, no-backtrace");
+	assert!(options.backtrace == Some(false));
+	assert!(options.source.is_none());
+
+	let options = PartialOptions::parse("source");
+	assert!(options.source == Some(true));
+	let options = PartialOptions::parse("source, no-source");
+	assert!(options.source == Some(false));
+	assert!(options.text.is_none());
+
+	let options = PartialOptions::parse("text");
+	assert!(options.text == Some(true));
+	let options = PartialOptions::parse("text, no-text");
+	assert!(options.text == Some(false));
+	assert!(options.sort.is_none());
+
+	let options = PartialOptions::parse("sort");
+	assert!(options.sort == Some(true));
+	let options = PartialOptions::parse("sort, no-sort");
+	assert!(options.sort == Some(false));
+	assert!(options.layout.is_none());
+
+	let options = PartialOptions::parse("layout=aligned");
+	assert!(options.layout == Some(ExpansionLayout::Aligned));
+	let options = PartialOptions::parse("layout=aligned, layout=inline");
+	assert!(options.layout == Some(ExpansionLayout::Inline));
+	assert!(options.links.is_none());
+
+	let options = PartialOptions::parse("links");
+	assert!(options.links == Some(true));
+	let options = PartialOptions::parse("links, no-links");
+	assert!(options.links == Some(false));
+}
+
+#[test]
+fn test_supports_styles() {
+	use crate::assert;
+	assert!(supports_styles("xterm-256color"));
+	assert!(supports_styles(""));
+	assert!(!supports_styles("dumb"));
+}
+
+#[test]
+fn test_hyperlinks_from_env() {
+	use crate::assert;
+	let env = |vars: &'static [(&'static str, &'static str)]| {
+		move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+	};
+	assert!(hyperlinks_from_env(env(&[("TERM_PROGRAM", "WezTerm")])));
+	assert!(hyperlinks_from_env(env(&[("TERM", "xterm-kitty")])));
+	assert!(hyperlinks_from_env(env(&[("VTE_VERSION", "6800")])));
+	assert!(hyperlinks_from_env(env(&[("WT_SESSION", "")])));
+	assert!(!hyperlinks_from_env(env(&[("VTE_VERSION", "4200")])));
+	assert!(!hyperlinks_from_env(env(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "Apple_Terminal")])));
+}
+
+#[test]
+fn test_palette_from_env() {
+	use crate::assert;
+	assert!(palette_from_env("truecolor", "xterm") == ColorPalette::TrueColor);
+	assert!(palette_from_env("24bit", "") == ColorPalette::TrueColor);
+	assert!(palette_from_env("", "xterm-256color") == ColorPalette::Fixed);
+	assert!(palette_from_env("", "xterm") == ColorPalette::Basic);
+	assert!(palette_from_env("", "") == ColorPalette::Basic);
+}
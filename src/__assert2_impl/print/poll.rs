@@ -0,0 +1,25 @@
+use std::fmt::{Debug, Write};
+
+use super::CheckExpression;
+use super::write_expanded_value;
+use crate::theme::Themed;
+
+/// A `Poll` value with the wrong variant.
+pub struct PollExpr<'a, Value> {
+	pub value: &'a Value,
+	pub expression: &'a str,
+}
+
+#[rustfmt::skip]
+impl<Value: Debug> CheckExpression for PollExpr<'_, Value> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{}", Themed::paint_left(self.expression)).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with expansion:").unwrap();
+		write_expanded_value(buffer, self.value, crate::theme::right());
+		// Remove last newline.
+		buffer.pop();
+	}
+}
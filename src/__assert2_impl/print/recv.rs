@@ -0,0 +1,38 @@
+use std::fmt::Write;
+use std::time::Duration;
+use yansi::Paint;
+
+use super::CheckExpression;
+use super::timing::humanize;
+use crate::__assert2_impl::recv::RecvError;
+use crate::theme::Themed;
+
+/// A failed attempt to receive a value from a channel.
+pub struct Recv<'a> {
+	pub error: RecvError,
+	pub timeout: Duration,
+	pub receiver_expr: &'a str,
+	pub timeout_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for Recv<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{receiver}, timeout = {timeout}",
+			receiver = Themed::paint_left(self.receiver_expr),
+			timeout  = Themed::paint_right(self.timeout_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with expansion:").unwrap();
+		match self.error {
+			RecvError::Timeout => {
+				write!(buffer, "  nothing received within {}", Themed::paint_right(&humanize(self.timeout))).unwrap();
+			},
+			RecvError::Disconnected => {
+				write!(buffer, "  {}", Paint::red("channel disconnected before a value was received")).unwrap();
+			},
+		}
+	}
+}
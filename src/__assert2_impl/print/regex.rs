@@ -0,0 +1,57 @@
+use std::fmt::Write;
+use yansi::Paint;
+
+use super::CheckExpression;
+use crate::__assert2_impl::regex::RegexMismatch;
+use crate::theme::Themed;
+
+/// A failed match of a string against a regex.
+pub struct RegexMatch<'a> {
+	pub text: &'a str,
+	pub pattern: &'a str,
+	pub mismatch: &'a RegexMismatch,
+	pub text_expr: &'a str,
+	pub pattern_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for RegexMatch<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{text}, {pattern}",
+			text    = Themed::paint_left(self.text_expr),
+			pattern = Themed::paint_right(self.pattern_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with text:").unwrap();
+		writeln!(buffer, "  {}", Themed::paint_left(&format_args!("{:?}", self.text))).unwrap();
+		writeln!(buffer, "with pattern:").unwrap();
+		write!(buffer, "  {}", Themed::paint_right(self.pattern)).unwrap();
+		match self.mismatch {
+			RegexMismatch::Invalid(error) => {
+				write!(buffer, "\n{}", "Note: the pattern is not a valid regex:".red()).unwrap();
+				for line in error.lines() {
+					write!(buffer, "\n  {line}").unwrap();
+				}
+			},
+			RegexMismatch::NoMatch(None) => (),
+			RegexMismatch::NoMatch(Some(partial)) => {
+				let (matched_pattern, remaining_pattern) = self.pattern.split_at(partial.pattern_len);
+				let matched_text = &self.text[partial.matched.clone()];
+				let remaining_text = &self.text[partial.matched.end..];
+				writeln!(buffer, "\nwith longest partial match:").unwrap();
+				writeln!(buffer, "  {} {} {}",
+					Themed::paint_right(matched_pattern),
+					"matches".dim(),
+					Themed::paint_left(&format_args!("{matched_text:?}")),
+				).unwrap();
+				write!(buffer, "  {} {} {}",
+					Themed::paint_right(remaining_pattern).bold(),
+					"does not match".red(),
+					Themed::paint_left(&format_args!("{remaining_text:?}")).bold(),
+				).unwrap();
+			},
+		}
+	}
+}
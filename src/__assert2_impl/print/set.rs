@@ -0,0 +1,86 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::theme::Themed;
+
+/// A failed subset or superset relation between two collections.
+pub struct SetRelation<'a, T> {
+	/// The elements that violate the relation.
+	pub missing: &'a [&'a T],
+
+	/// If true, left should be a subset of right, otherwise left should be a superset of right.
+	pub subset: bool,
+
+	pub left_expr: &'a str,
+	pub right_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl<T: Debug> CheckExpression for SetRelation<'_, T> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{left}, {right}",
+			left  = Themed::paint_left(self.left_expr),
+			right = Themed::paint_right(self.right_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		if self.subset {
+			writeln!(buffer, "elements of left missing from right ({}):", self.missing.len()).unwrap();
+		} else {
+			writeln!(buffer, "elements of right missing from left ({}):", self.missing.len()).unwrap();
+		}
+		for item in self.missing {
+			let item = format_debug(item, false);
+			if self.subset {
+				writeln!(buffer, "  {}", item.paint_left()).unwrap();
+			} else {
+				writeln!(buffer, "  {}", item.paint_right()).unwrap();
+			}
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
+
+/// A failed equality check of two sets.
+pub struct SetEq<'a, T> {
+	/// The elements that only occur in the left collection.
+	pub only_left: &'a [&'a T],
+
+	/// The elements that only occur in the right collection.
+	pub only_right: &'a [&'a T],
+
+	pub left_expr: &'a str,
+	pub right_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl<T: Debug> CheckExpression for SetEq<'_, T> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{} {} {}",
+			Themed::paint_left(self.left_expr),
+			Themed::paint_operator("==").bold(),
+			Themed::paint_right(self.right_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		if !self.only_left.is_empty() {
+			writeln!(buffer, "only in left ({}):", self.only_left.len()).unwrap();
+			for item in self.only_left {
+				writeln!(buffer, "  {}", format_debug(item, false).paint_left()).unwrap();
+			}
+		}
+		if !self.only_right.is_empty() {
+			writeln!(buffer, "only in right ({}):", self.only_right.len()).unwrap();
+			for item in self.only_right {
+				writeln!(buffer, "  {}", format_debug(item, false).paint_right()).unwrap();
+			}
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
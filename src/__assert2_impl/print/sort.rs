@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+
+/// The maximum length of a character literal in `Debug` output, like `'\u{10ffff}'`.
+const MAX_CHAR_LITERAL: usize = 12;
+
+/// Sort the entries of sets and maps in `Debug` output, so that their order does not depend on hashing.
+///
+/// A set or map is a pair of braces that is not preceded by the name of a struct or enum variant.
+/// Entries are sorted recursively in natural order, so that numbers are ordered by their value.
+/// If the brackets in the output are not balanced, the remainder of the output is left untouched.
+pub(crate) fn sort_unordered(text: &str) -> String {
+	let bytes = text.as_bytes();
+	let mut output = String::with_capacity(text.len());
+	let mut copied = 0;
+	let mut i = 0;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'"' | b'\'' => i = skip_quoted(bytes, i),
+			open @ (b'{' | b'[' | b'(') => {
+				let Some(close) = find_closing(bytes, i) else {
+					break;
+				};
+				output.push_str(&text[copied..=i]);
+				let inner = sort_unordered(&text[i + 1..close]);
+				if open == b'{' && is_collection(&text[..i]) {
+					write_sorted_entries(&mut output, &inner);
+				} else {
+					output.push_str(&inner);
+				}
+				copied = close;
+				i = close + 1;
+			},
+			_ => i += 1,
+		}
+	}
+	output.push_str(&text[copied..]);
+	output
+}
+
+/// Check if an opening brace after `before` starts a set or map, rather than a struct.
+fn is_collection(before: &str) -> bool {
+	match before.trim_end().chars().last() {
+		None => true,
+		Some(c) => "([{,:".contains(c),
+	}
+}
+
+/// Get the index after the string or character literal that starts at `start`.
+///
+/// A single quote that is not closed shortly after is not a character literal, but an apostrophe.
+fn skip_quoted(bytes: &[u8], start: usize) -> usize {
+	let quote = bytes[start];
+	let mut i = start + 1;
+	while i < bytes.len() {
+		if quote == b'\'' && i - start > MAX_CHAR_LITERAL {
+			return start + 1;
+		}
+		match bytes[i] {
+			b'\\' => i += 2,
+			c if c == quote => return i + 1,
+			_ => i += 1,
+		}
+	}
+	if quote == b'\'' {
+		start + 1
+	} else {
+		bytes.len()
+	}
+}
+
+/// Find the index of the bracket that closes the bracket at `open`.
+fn find_closing(bytes: &[u8], open: usize) -> Option<usize> {
+	let mut depth = 0;
+	let mut i = open;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'"' | b'\'' => {
+				i = skip_quoted(bytes, i);
+				continue;
+			},
+			b'{' | b'[' | b'(' => depth += 1,
+			b'}' | b']' | b')' => {
+				depth -= 1;
+				if depth == 0 {
+					return Some(i);
+				}
+			},
+			_ => (),
+		}
+		i += 1;
+	}
+	None
+}
+
+/// Split the contents of a pair of brackets at the commas that are not nested in other brackets.
+fn split_entries(text: &str) -> Vec<&str> {
+	let bytes = text.as_bytes();
+	let mut entries = Vec::new();
+	let mut depth = 0usize;
+	let mut start = 0;
+	let mut i = 0;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'"' | b'\'' => {
+				i = skip_quoted(bytes, i);
+				continue;
+			},
+			b'{' | b'[' | b'(' => depth += 1,
+			b'}' | b']' | b')' => depth = depth.saturating_sub(1),
+			b',' if depth == 0 => {
+				entries.push(&text[start..i]);
+				start = i + 1;
+			},
+			_ => (),
+		}
+		i += 1;
+	}
+	entries.push(&text[start..]);
+	entries
+}
+
+/// Write the entries of a set or map in sorted order, keeping the whitespace around them.
+fn write_sorted_entries(output: &mut String, inner: &str) {
+	if inner.trim().is_empty() {
+		output.push_str(inner);
+		return;
+	}
+
+	let mut pieces = split_entries(inner);
+	// The pretty format has a trailing comma, followed by a line break.
+	let trailing = match pieces.last() {
+		Some(last) if last.trim().is_empty() => pieces.pop(),
+		_ => None,
+	};
+
+	let mut entries: Vec<&str> = pieces.iter().map(|piece| piece.trim()).collect();
+	entries.sort_by(|a, b| natural_cmp(a, b));
+	for (i, (piece, entry)) in pieces.iter().zip(entries).enumerate() {
+		if i > 0 {
+			output.push(',');
+		}
+		output.push_str(&piece[..piece.len() - piece.trim_start().len()]);
+		output.push_str(entry);
+		output.push_str(&piece[piece.trim_end().len()..]);
+	}
+	if let Some(trailing) = trailing {
+		output.push(',');
+		output.push_str(trailing);
+	}
+}
+
+/// Compare two strings, comparing runs of digits by their numeric value.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+	let (mut a, mut b) = (a, b);
+	loop {
+		match (a.chars().next(), b.chars().next()) {
+			(None, None) => return Ordering::Equal,
+			(None, Some(_)) => return Ordering::Less,
+			(Some(_), None) => return Ordering::Greater,
+			(Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+				let end_a = a.find(|c: char| !c.is_ascii_digit()).unwrap_or(a.len());
+				let end_b = b.find(|c: char| !c.is_ascii_digit()).unwrap_or(b.len());
+				let number_a = a[..end_a].trim_start_matches('0');
+				let number_b = b[..end_b].trim_start_matches('0');
+				let ordering = number_a.len().cmp(&number_b.len()).then_with(|| number_a.cmp(number_b));
+				if ordering != Ordering::Equal {
+					return ordering;
+				}
+				a = &a[end_a..];
+				b = &b[end_b..];
+			},
+			(Some(x), Some(y)) => {
+				if x != y {
+					return x.cmp(&y);
+				}
+				a = &a[x.len_utf8()..];
+				b = &b[y.len_utf8()..];
+			},
+		}
+	}
+}
+
+#[test]
+fn test_sort_unordered() {
+	use crate::assert;
+	assert!(sort_unordered("{3, 1, 2}") == "{1, 2, 3}");
+	assert!(sort_unordered("{10, 9}") == "{9, 10}");
+	assert!(sort_unordered("{\"b\": 1, \"a\": 2}") == "{\"a\": 2, \"b\": 1}");
+	assert!(sort_unordered("Foo { b: 1, a: 2 }") == "Foo { b: 1, a: 2 }");
+	assert!(sort_unordered("Foo { set: {2, 1}, list: [2, 1] }") == "Foo { set: {1, 2}, list: [2, 1] }");
+	assert!(sort_unordered("Some({\"b, }\", \"a\"})") == "Some({\"a\", \"b, }\"})");
+	assert!(sort_unordered("{'}', ','}") == "{',', '}'}");
+	assert!(sort_unordered("{[2, 1], [1, 2]}") == "{[1, 2], [2, 1]}");
+	assert!(sort_unordered("{}") == "{}");
+	assert!(sort_unordered("{2, 1") == "{2, 1");
+	assert!(sort_unordered("it's {2, 1}") == "it's {2, 1}");
+	assert!(sort_unordered("{\n    2,\n    1,\n}") == "{\n    1,\n    2,\n}");
+	assert!(sort_unordered("{\n    \"b\": {\n        2,\n        1,\n    },\n    \"a\": {},\n}") == "{\n    \"a\": {},\n    \"b\": {\n        1,\n        2,\n    },\n}");
+}
@@ -0,0 +1,116 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::theme::Themed;
+
+/// The number of lines to show before and after the line of the assertion.
+const CONTEXT_LINES: usize = 1;
+
+/// Write the source lines around a failed assertion, with a caret under the failed expression.
+///
+/// Nothing is written if the source file can not be found.
+pub(super) fn write_source(buffer: &mut String, file: &str, line: u32, column: u32, expression: &str) {
+	let Some(source) = find_source_file(file).and_then(|path| std::fs::read_to_string(path).ok()) else {
+		return;
+	};
+	if let Some(snippet) = snippet(&source, line as usize, column as usize, expression) {
+		writeln!(buffer, "with source:").unwrap();
+		buffer.push_str(&snippet);
+	}
+}
+
+/// Find a source file from the path given by `file!()`.
+///
+/// The path is relative to the directory where the compiler was run,
+/// which is the workspace root, while tests run in the directory of the package.
+/// So look for the file in the parent directories of the current directory too.
+pub(super) fn find_source_file(file: &str) -> Option<PathBuf> {
+	let path = Path::new(file);
+	if path.is_absolute() {
+		return Some(path.into());
+	}
+	let current_dir = std::env::current_dir().ok()?;
+	current_dir.ancestors().map(|dir| dir.join(path)).find(|path| path.is_file())
+}
+
+/// Render the lines around `line`, with a caret under the expression.
+///
+/// The caret underlines the expression if its text is found after `column`.
+/// On nightly, the macros get the exact source text, so it is always found.
+/// On stable, the expression is reformatted by `stringify!()` and may not match the source.
+/// Then a single caret points at the start of the assertion instead.
+fn snippet(source: &str, line: usize, column: usize, expression: &str) -> Option<String> {
+	let lines: Vec<&str> = source.lines().collect();
+	let index = line.checked_sub(1)?;
+	let line_text = *lines.get(index)?;
+	let first = index.saturating_sub(CONTEXT_LINES);
+	let last = (index + CONTEXT_LINES).min(lines.len() - 1);
+	let number_width = (last + 1).to_string().len();
+
+	let start = byte_offset(line_text, column);
+	let expression = expression.lines().next().unwrap_or("");
+	let (caret_start, caret_len) = match line_text[start..].find(expression) {
+		Some(offset) if !expression.is_empty() => (start + offset, expression.chars().count()),
+		_ => (start, 1),
+	};
+	// Keep tabs in the indentation of the caret, so it lines up with the source line.
+	let indent: String = line_text[..caret_start].chars().map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+
+	let mut output = String::new();
+	for (i, text) in lines[first..=last].iter().enumerate() {
+		writeln!(output, "  {:>number_width$} | {text}", first + i + 1).unwrap();
+		if first + i == index {
+			writeln!(output, "  {:number_width$} | {indent}{}", "", Themed::paint_operator(&"^".repeat(caret_len))).unwrap();
+		}
+	}
+	Some(output)
+}
+
+/// Get the byte offset of a column in a line.
+///
+/// Columns start at 1, and are counted like the compiler does for `Location::caller()`: a tab counts as four columns.
+fn byte_offset(line: &str, column: usize) -> usize {
+	let mut width = 0;
+	for (i, c) in line.char_indices() {
+		if width + 1 >= column {
+			return i;
+		}
+		width += if c == '\t' { 4 } else { 1 };
+	}
+	line.len()
+}
+
+#[test]
+fn test_byte_offset() {
+	use crate::assert;
+	assert!(byte_offset("abc", 1) == 0);
+	assert!(byte_offset("abc", 3) == 2);
+	assert!(byte_offset("abc", 9) == 3);
+	assert!(byte_offset("\tabc", 5) == 1);
+	assert!(byte_offset("\t\tabc", 10) == 3);
+}
+
+#[test]
+fn test_snippet() {
+	use crate::assert;
+	let snippet = |source, line, column, expression| snippet(source, line, column, expression).map(|x| crate::failure::strip_ansi(&x));
+	let source = "fn test() {\n\tlet a = 1;\n\tcheck!(a == 2);\n}\n";
+	assert!(snippet(source, 3, 5, "a == 2").as_deref() == Some(concat!(
+		"  2 | \tlet a = 1;\n",
+		"  3 | \tcheck!(a == 2);\n",
+		"    | \t       ^^^^^^\n",
+		"  4 | }\n",
+	)));
+	assert!(snippet(source, 3, 5, "a  ==  2").as_deref() == Some(concat!(
+		"  2 | \tlet a = 1;\n",
+		"  3 | \tcheck!(a == 2);\n",
+		"    | \t^\n",
+		"  4 | }\n",
+	)));
+	assert!(snippet(source, 1, 1, "x").as_deref() == Some(concat!(
+		"  1 | fn test() {\n",
+		"    | ^\n",
+		"  2 | \tlet a = 1;\n",
+	)));
+	assert!(snippet(source, 9, 1, "x").is_none());
+}
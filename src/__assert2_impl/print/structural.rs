@@ -0,0 +1,52 @@
+use std::fmt::Write;
+
+use super::CheckExpression;
+use crate::FieldDiff;
+use crate::theme::Themed;
+
+/// A failed equality check with the fields that differ, if the values could be compared field by field.
+pub struct FieldDiffs<Check> {
+	/// The failed check.
+	pub check: Check,
+
+	/// The fields that differ, or `None` if the values could not be compared field by field.
+	pub diffs: Option<Vec<FieldDiff>>,
+}
+
+impl<Check> FieldDiffs<Check> {
+	/// Get the fields that differ, if they can be shown instead of the normal expansion.
+	///
+	/// If the outermost values differ as a whole or no field differs, the normal expansion is more useful.
+	fn fields(&self) -> Option<&[FieldDiff]> {
+		let diffs = self.diffs.as_deref()?;
+		if diffs.is_empty() || diffs.iter().any(|diff| diff.path.is_empty()) {
+			None
+		} else {
+			Some(diffs)
+		}
+	}
+}
+
+#[rustfmt::skip]
+impl<Check: CheckExpression> CheckExpression for FieldDiffs<Check> {
+	fn write_expression(&self, buffer: &mut String) {
+		self.check.write_expression(buffer);
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		let Some(fields) = self.fields() else {
+			return self.check.write_expansion(buffer);
+		};
+		writeln!(buffer, "with field differences:").unwrap();
+		for diff in fields {
+			writeln!(buffer, "  {}: {} {} {}",
+				diff.path,
+				Themed::paint_left(&diff.left),
+				Themed::paint_operator("!=").bold(),
+				Themed::paint_right(&diff.right),
+			).unwrap();
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
@@ -0,0 +1,97 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of the last test point printed in the TAP format.
+static LAST_NUMBER: AtomicUsize = AtomicUsize::new(0);
+
+/// Get the number for the next test point printed in the TAP format.
+///
+/// Test points are numbered in the order they are printed, starting at 1.
+pub fn next_number() -> usize {
+	LAST_NUMBER.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+/// Render a failed assertion as a `not ok` line of the Test Anything Protocol.
+///
+/// The details of the failure follow in an indented YAML diagnostics block.
+pub fn render(number: usize, failure: &crate::Failure, warning: bool) -> String {
+	let mut output = String::new();
+	let description = format!("{}!( {} )", failure.macro_name(), failure.expression());
+	writeln!(output, "not ok {number} - {}", escape_description(&description)).unwrap();
+	writeln!(output, "  ---").unwrap();
+	writeln!(output, "  severity: {}", if warning { "warning" } else { "fail" }).unwrap();
+	writeln!(output, "  at:").unwrap();
+	writeln!(output, "    file: {}", quote(failure.file())).unwrap();
+	writeln!(output, "    line: {}", failure.line()).unwrap();
+	writeln!(output, "    column: {}", failure.column()).unwrap();
+	if let Some(message) = failure.message() {
+		writeln!(output, "  message: {}", quote(message)).unwrap();
+	}
+	writeln!(output, "  report: |2").unwrap();
+	for line in failure.report().trim_end().lines() {
+		if line.is_empty() {
+			writeln!(output).unwrap();
+		} else {
+			writeln!(output, "    {line}").unwrap();
+		}
+	}
+	writeln!(output, "  ...").unwrap();
+	output
+}
+
+/// Escape the description of a test point.
+///
+/// A `#` would start a directive and a line break would end the test point.
+fn escape_description(description: &str) -> String {
+	description.replace('\\', "\\\\").replace('#', "\\#").replace('\n', " ")
+}
+
+/// Quote a string as a double quoted YAML scalar.
+fn quote(value: &str) -> String {
+	let mut output = String::with_capacity(value.len() + 2);
+	output.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => output.push_str("\\\""),
+			'\\' => output.push_str("\\\\"),
+			'\n' => output.push_str("\\n"),
+			'\t' => output.push_str("\\t"),
+			c if c.is_control() => write!(output, "\\u{:04x}", c as u32).unwrap(),
+			c => output.push(c),
+		}
+	}
+	output.push('"');
+	output
+}
+
+#[test]
+fn test_render_tap() {
+	use crate::assert;
+	let failure = crate::Failure::new(
+		"check",
+		"src/lib.rs",
+		12,
+		3,
+		"a # b == 2",
+		Some("the \"answer\"".into()),
+		"Assertion failed at src/lib.rs:12:3:\n  check!( a # b == 2 )\n\nwith message:\n  the \"answer\"\n\n",
+	);
+	assert!(render(4, &failure, false) == concat!(
+		"not ok 4 - check!( a \\# b == 2 )\n",
+		"  ---\n",
+		"  severity: fail\n",
+		"  at:\n",
+		"    file: \"src/lib.rs\"\n",
+		"    line: 12\n",
+		"    column: 3\n",
+		"  message: \"the \\\"answer\\\"\"\n",
+		"  report: |2\n",
+		"    Assertion failed at src/lib.rs:12:3:\n",
+		"      check!( a # b == 2 )\n",
+		"\n",
+		"    with message:\n",
+		"      the \"answer\"\n",
+		"  ...\n",
+	));
+	assert!(render(5, &failure, true).contains("  severity: warning\n"));
+}
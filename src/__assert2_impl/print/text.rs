@@ -0,0 +1,29 @@
+use std::fmt::Write;
+use yansi::Paint;
+
+/// Write a string verbatim with line numbers, indented by four spaces.
+///
+/// Every line is terminated by a line break, including the last one.
+pub(crate) fn write_numbered_lines(buffer: &mut String, text: &str, color: yansi::Color) {
+	let count = text.split('\n').count();
+	let width = count.to_string().len();
+	for (i, line) in text.split('\n').enumerate() {
+		let number = format!("{:>width$} |", i + 1);
+		writeln!(buffer, "    {} {}", number.dim(), line.fg(color)).unwrap();
+	}
+}
+
+#[test]
+fn test_write_numbered_lines() {
+	use crate::assert;
+	let numbered = |text: &str| {
+		let mut buffer = String::new();
+		write_numbered_lines(&mut buffer, text, yansi::Color::Red);
+		crate::failure::strip_ansi(&buffer)
+	};
+	assert!(numbered("a\n\"b\"\n") == "    1 | a\n    2 | \"b\"\n    3 | \n");
+
+	let buffer = numbered(&"x\n".repeat(10));
+	assert!(buffer.starts_with("     1 | x\n"));
+	assert!(buffer.ends_with("    10 | x\n    11 | \n"));
+}
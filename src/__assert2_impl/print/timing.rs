@@ -0,0 +1,66 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use super::CheckExpression;
+use crate::theme::Themed;
+
+/// A code block that took longer than its time budget.
+pub struct CompletesWithin<'a> {
+	pub budget: Duration,
+	pub elapsed: Duration,
+	pub budget_expr: &'a str,
+	pub block_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl CheckExpression for CompletesWithin<'_> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{budget}, {block}",
+			budget = Themed::paint_right(self.budget_expr),
+			block  = Themed::paint_left(self.block_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		writeln!(buffer, "with expansion:").unwrap();
+		write!(buffer, "  elapsed {elapsed} {op} budget {budget} ({over} over budget)",
+			elapsed = Themed::paint_left(&humanize(self.elapsed)),
+			op      = Themed::paint_operator(">"),
+			budget  = Themed::paint_right(&humanize(self.budget)),
+			over    = humanize(self.elapsed.saturating_sub(self.budget)),
+		).unwrap();
+	}
+}
+
+/// Format a duration for humans, using the largest unit that keeps the number above one.
+pub fn humanize(duration: Duration) -> String {
+	let nanos = duration.as_nanos();
+	let (value, unit) = if nanos >= 60_000_000_000 {
+		let secs = duration.as_secs();
+		return format!("{}m {}s", secs / 60, secs % 60);
+	} else if nanos >= 1_000_000_000 {
+		(nanos as f64 / 1e9, "s")
+	} else if nanos >= 1_000_000 {
+		(nanos as f64 / 1e6, "ms")
+	} else if nanos >= 1_000 {
+		(nanos as f64 / 1e3, "µs")
+	} else {
+		(nanos as f64, "ns")
+	};
+
+	let value = format!("{value:.2}");
+	let value = value.trim_end_matches('0').trim_end_matches('.');
+	format!("{value}{unit}")
+}
+
+#[test]
+fn test_humanize() {
+	use crate::assert;
+	assert!(humanize(Duration::from_nanos(0)) == "0ns");
+	assert!(humanize(Duration::from_nanos(999)) == "999ns");
+	assert!(humanize(Duration::from_micros(20)) == "20µs");
+	assert!(humanize(Duration::from_micros(1500)) == "1.5ms");
+	assert!(humanize(Duration::from_millis(100)) == "100ms");
+	assert!(humanize(Duration::from_millis(153_256)) == "2m 33s");
+	assert!(humanize(Duration::from_millis(1_234)) == "1.23s");
+}
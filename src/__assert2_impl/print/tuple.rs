@@ -0,0 +1,101 @@
+use std::fmt::Write;
+use yansi::Paint;
+
+use crate::diff::SingleLineDiff;
+use crate::theme::Themed;
+
+/// Write a per-element comparison if both sides are tuples with the same number of elements.
+///
+/// The inputs must be the compact `Debug` output of the values.
+/// Nothing is written if the inputs do not look like tuples of equal length.
+pub fn write_tuple_elements(buffer: &mut String, left: &str, right: &str) {
+	let (Some(left), Some(right)) = (split_tuple(left), split_tuple(right)) else {
+		return;
+	};
+	if left.len() != right.len() {
+		return;
+	}
+
+	writeln!(buffer, "with tuple elements:").unwrap();
+	for (i, (left, right)) in left.iter().zip(&right).enumerate() {
+		let index = format!(".{i}");
+		if left == right {
+			writeln!(buffer, "  {} {}", Themed::paint_name(&index), "equal".dim()).unwrap();
+		} else {
+			let diff = SingleLineDiff::new(left, right);
+			write!(buffer, "  {} {}: ", Themed::paint_name(&index), "differs".red().bold()).unwrap();
+			diff.write_left(buffer);
+			write!(buffer, " {} ", Themed::paint_operator("!=")).unwrap();
+			diff.write_right(buffer);
+			buffer.push('\n');
+		}
+	}
+}
+
+/// Split the compact `Debug` output of a tuple into the `Debug` output of the elements.
+///
+/// Returns `None` if the input does not look like a tuple with at least one element.
+fn split_tuple(debug: &str) -> Option<Vec<&str>> {
+	let inner = debug.strip_prefix('(')?.strip_suffix(')')?;
+	if inner.is_empty() {
+		return None;
+	}
+
+	let mut elements = Vec::new();
+	let mut depth = 0usize;
+	let mut quote = None;
+	let mut escaped = false;
+	let mut start = 0;
+	for (i, c) in inner.char_indices() {
+		if let Some(q) = quote {
+			if escaped {
+				escaped = false;
+			} else if c == '\\' {
+				escaped = true;
+			} else if c == q {
+				quote = None;
+			}
+			continue;
+		}
+		match c {
+			'"' | '\'' => quote = Some(c),
+			'(' | '[' | '{' => depth += 1,
+			')' | ']' | '}' => depth = depth.checked_sub(1)?,
+			',' if depth == 0 => {
+				elements.push(inner[start..i].trim());
+				start = i + 1;
+			},
+			_ => (),
+		}
+	}
+	if depth != 0 || quote.is_some() {
+		return None;
+	}
+
+	let last = inner[start..].trim();
+	if !last.is_empty() {
+		elements.push(last);
+	} else if elements.is_empty() {
+		return None;
+	}
+
+	// A single element without trailing comma is not a tuple, just parentheses.
+	if elements.len() == 1 && !inner.trim_end().ends_with(',') {
+		return None;
+	}
+	Some(elements)
+}
+
+#[test]
+fn test_split_tuple() {
+	use crate::assert;
+	assert!(split_tuple("(3, Some(4))") == Some(vec!["3", "Some(4)"]));
+	assert!(split_tuple("(1,)") == Some(vec!["1"]));
+	assert!(split_tuple("(\"a, b\", ',', [1, 2], Foo { x: (1, 2) })") == Some(vec!["\"a, b\"", "','", "[1, 2]", "Foo { x: (1, 2) }"]));
+	assert!(split_tuple("('\\'', \"\\\"(\")") == Some(vec!["'\\''", "\"\\\"(\""]));
+	assert!(split_tuple("()") == None);
+	assert!(split_tuple("(1)") == None);
+	assert!(split_tuple("Foo(1, 2)") == None);
+	assert!(split_tuple("(1, 2") == None);
+	assert!(split_tuple("(1), (2)") == None);
+}
@@ -0,0 +1,41 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::theme::Themed;
+
+/// A failed order-insensitive comparison of two collections.
+pub struct UnorderedEq<'a, T> {
+	pub only_left: &'a [&'a T],
+	pub only_right: &'a [&'a T],
+	pub left_expr: &'a str,
+	pub right_expr: &'a str,
+}
+
+#[rustfmt::skip]
+impl<T: Debug> CheckExpression for UnorderedEq<'_, T> {
+	fn write_expression(&self, buffer: &mut String) {
+		write!(buffer, "{left}, {right}",
+			left  = Themed::paint_left(self.left_expr),
+			right = Themed::paint_right(self.right_expr),
+		).unwrap();
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		if !self.only_left.is_empty() {
+			writeln!(buffer, "missing from right ({}):", self.only_left.len()).unwrap();
+			for item in self.only_left {
+				writeln!(buffer, "  {}", format_debug(item, false).paint_left()).unwrap();
+			}
+		}
+		if !self.only_right.is_empty() {
+			writeln!(buffer, "missing from left ({}):", self.only_right.len()).unwrap();
+			for item in self.only_right {
+				writeln!(buffer, "  {}", format_debug(item, false).paint_right()).unwrap();
+			}
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+}
@@ -0,0 +1,35 @@
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use super::CheckExpression;
+use super::format_debug;
+use crate::theme::Themed;
+
+/// A failed check with the values of the leaf operands of its expression.
+pub struct WithValues<'a, Check> {
+	/// The failed check.
+	pub check: Check,
+
+	/// The leaf operands with their values, or `None` if the value could not be captured.
+	pub values: &'a [(&'a str, Option<&'a dyn Debug>)],
+}
+
+#[rustfmt::skip]
+impl<Check: CheckExpression> CheckExpression for WithValues<'_, Check> {
+	fn write_expression(&self, buffer: &mut String) {
+		self.check.write_expression(buffer);
+	}
+
+	fn write_expansion(&self, buffer: &mut String) {
+		self.check.write_expansion(buffer);
+		let mut values = self.values.iter()
+			.filter_map(|(name, value)| Some((name, (*value)?)))
+			.peekable();
+		if values.peek().is_some() {
+			write!(buffer, "\nwith values:").unwrap();
+			for (name, value) in values {
+				write!(buffer, "\n  {} {} {}", name, Themed::paint_operator("=").bold(), format_debug(value, false).paint_left()).unwrap();
+			}
+		}
+	}
+}
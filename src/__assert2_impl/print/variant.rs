@@ -0,0 +1,104 @@
+/// Get the name of the enum variant at the start of a pattern or `Debug` output.
+///
+/// The name is the last segment of the leading path, like `Err` for `Err(..)` or `Off` for `Mode::Off`.
+/// Only names starting with an uppercase letter and followed by nothing, a tuple or a struct body are considered variants,
+/// so bindings, literals and other patterns are ignored.
+pub(crate) fn variant_name(input: &str) -> Option<&str> {
+	let input = input.trim_start().trim_start_matches('&').trim_start();
+	let end = input
+		.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':' || c == ' '))
+		.unwrap_or(input.len());
+	let (path, rest) = input.split_at(end);
+	if !(rest.is_empty() || rest.starts_with('(') || rest.starts_with('{')) {
+		return None;
+	}
+	let name = path.rsplit("::").next()?.trim();
+	if name.starts_with(|c: char| c.is_uppercase()) && !name.contains(' ') {
+		Some(name)
+	} else {
+		None
+	}
+}
+
+/// Get the expected and the actual variant if a value did not match a pattern because it has a different variant.
+///
+/// Or-patterns can expect multiple variants, so they are ignored.
+pub(crate) fn mismatched_variant<'a, 'b>(pattern: &'a str, value: &'b str) -> Option<(&'a str, &'b str)> {
+	if pattern.contains('|') {
+		return None;
+	}
+	let expected = variant_name(pattern)?;
+	let actual = variant_name(value)?;
+	if actual == expected {
+		None
+	} else {
+		Some((expected, actual))
+	}
+}
+
+/// Check if two different variant names are similar enough to be easily confused.
+///
+/// The names are similar if the edit distance is at most a third of the length of the longest name.
+pub(crate) fn is_similar(expected: &str, actual: &str) -> bool {
+	let longest = expected.chars().count().max(actual.chars().count());
+	edit_distance(expected, actual) * 3 <= longest
+}
+
+/// Compute the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+	let b: Vec<char> = b.chars().collect();
+	let mut previous: Vec<usize> = (0..=b.len()).collect();
+	let mut current = vec![0; b.len() + 1];
+	for (i, a) in a.chars().enumerate() {
+		current[0] = i + 1;
+		for (j, &b) in b.iter().enumerate() {
+			let substitute = previous[j] + usize::from(a != b);
+			current[j + 1] = substitute.min(previous[j + 1] + 1).min(current[j] + 1);
+		}
+		std::mem::swap(&mut previous, &mut current);
+	}
+	previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::assert;
+
+	#[test]
+	fn test_variant_name() {
+		assert!(variant_name("Err(_)") == Some("Err"));
+		assert!(variant_name("Err(\"boom\")") == Some("Err"));
+		assert!(variant_name("None") == Some("None"));
+		assert!(variant_name("Mode::On { level: 1 }") == Some("On"));
+		assert!(variant_name("std :: result :: Result :: Ok (x)") == Some("Ok"));
+		assert!(variant_name("&Some(x)") == Some("Some"));
+		assert!(variant_name("x") == None);
+		assert!(variant_name("1") == None);
+		assert!(variant_name("[1, 2]") == None);
+	}
+
+	#[test]
+	fn test_mismatched_variant() {
+		assert!(mismatched_variant("Ok(_)", "Err(\"boom\")") == Some(("Ok", "Err")));
+		assert!(mismatched_variant("Some(1)", "Some(2)") == None);
+		assert!(mismatched_variant("x", "Err(1)") == None);
+		assert!(mismatched_variant("Some(_) | None", "Other") == None);
+	}
+
+	#[test]
+	fn test_edit_distance() {
+		assert!(edit_distance("", "") == 0);
+		assert!(edit_distance("Ok", "") == 2);
+		assert!(edit_distance("Connected", "Disconnected") == 4);
+		assert!(edit_distance("Kitten", "Sitting") == 3);
+	}
+
+	#[test]
+	fn test_is_similar() {
+		assert!(is_similar("Connected", "Disconnected"));
+		assert!(is_similar("Enable", "Enabled"));
+		assert!(!is_similar("Ok", "Err"));
+		assert!(!is_similar("Some", "None"));
+	}
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+/// The reason that no value was received from a channel.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RecvError {
+	/// No value was received before the timeout expired.
+	Timeout,
+
+	/// All senders were dropped without sending a value.
+	Disconnected,
+}
+
+/// Receive a value from a channel that can block with a timeout.
+pub trait RecvTimeout {
+	type Item;
+
+	fn __assert2_recv(&self, timeout: Duration) -> Result<Self::Item, RecvError>;
+}
+
+/// Receive a value from a channel that needs a mutable reference to receive.
+pub trait RecvMut {
+	type Item;
+
+	fn __assert2_recv(&mut self, timeout: Duration) -> Result<Self::Item, RecvError>;
+}
+
+impl<T> RecvTimeout for std::sync::mpsc::Receiver<T> {
+	type Item = T;
+
+	fn __assert2_recv(&self, timeout: Duration) -> Result<T, RecvError> {
+		use std::sync::mpsc::RecvTimeoutError;
+		self.recv_timeout(timeout).map_err(|e| match e {
+			RecvTimeoutError::Timeout => RecvError::Timeout,
+			RecvTimeoutError::Disconnected => RecvError::Disconnected,
+		})
+	}
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T> RecvTimeout for crossbeam_channel::Receiver<T> {
+	type Item = T;
+
+	fn __assert2_recv(&self, timeout: Duration) -> Result<T, RecvError> {
+		use crossbeam_channel::RecvTimeoutError;
+		self.recv_timeout(timeout).map_err(|e| match e {
+			RecvTimeoutError::Timeout => RecvError::Timeout,
+			RecvTimeoutError::Disconnected => RecvError::Disconnected,
+		})
+	}
+}
+
+#[cfg(feature = "tokio")]
+impl<T> RecvMut for tokio::sync::mpsc::Receiver<T> {
+	type Item = T;
+
+	fn __assert2_recv(&mut self, timeout: Duration) -> Result<T, RecvError> {
+		poll_until(timeout, || self.try_recv())
+	}
+}
+
+#[cfg(feature = "tokio")]
+impl<T> RecvMut for tokio::sync::mpsc::UnboundedReceiver<T> {
+	type Item = T;
+
+	fn __assert2_recv(&mut self, timeout: Duration) -> Result<T, RecvError> {
+		poll_until(timeout, || self.try_recv())
+	}
+}
+
+/// Repeatedly try to receive a value from a `tokio` channel until the timeout expires.
+///
+/// This does not need a runtime, but it does block the current thread.
+#[cfg(feature = "tokio")]
+fn poll_until<T>(timeout: Duration, mut try_recv: impl FnMut() -> Result<T, tokio::sync::mpsc::error::TryRecvError>) -> Result<T, RecvError> {
+	use tokio::sync::mpsc::error::TryRecvError;
+	let start = std::time::Instant::now();
+	loop {
+		match try_recv() {
+			Ok(value) => return Ok(value),
+			Err(TryRecvError::Disconnected) => return Err(RecvError::Disconnected),
+			Err(TryRecvError::Empty) => (),
+		}
+		let elapsed = start.elapsed();
+		if elapsed >= timeout {
+			return Err(RecvError::Timeout);
+		}
+		std::thread::sleep(Duration::from_millis(1).min(timeout - elapsed));
+	}
+}
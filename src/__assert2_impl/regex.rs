@@ -0,0 +1,62 @@
+/// The reason why a string did not match a regex.
+pub enum RegexMismatch {
+	/// The regex could not be compiled.
+	Invalid(String),
+
+	/// The string did not match the regex.
+	NoMatch(Option<PartialMatch>),
+}
+
+/// The longest prefix of a regex that matches the string.
+pub struct PartialMatch {
+	/// The length of the matching prefix of the regex in bytes.
+	pub pattern_len: usize,
+
+	/// The part of the string matched by the prefix of the regex.
+	pub matched: std::ops::Range<usize>,
+}
+
+/// Check if a string matches a regex.
+pub fn check(text: &str, pattern: &str) -> Result<(), RegexMismatch> {
+	let regex = regex::Regex::new(pattern)
+		.map_err(|e| RegexMismatch::Invalid(e.to_string()))?;
+	if regex.is_match(text) {
+		Ok(())
+	} else {
+		Err(RegexMismatch::NoMatch(partial_match(text, pattern)))
+	}
+}
+
+/// Find the longest prefix of a regex that matches the string.
+///
+/// The regex is only split at the top level, so this only works for a regex that is a concatenation of smaller parts.
+/// Returns `None` if no prefix of the regex matches.
+fn partial_match(text: &str, pattern: &str) -> Option<PartialMatch> {
+	let ast = regex_syntax::ast::parse::Parser::new().parse(pattern).ok()?;
+	let regex_syntax::ast::Ast::Concat(concat) = &ast else {
+		return None;
+	};
+
+	// Try ever shorter prefixes of the regex, and return the first one that matches.
+	concat.asts.iter().rev().skip(1).find_map(|part| {
+		let pattern_len = part.span().end.offset;
+		let regex = regex::Regex::new(&pattern[..pattern_len]).ok()?;
+		let matched = regex.find(text)?.range();
+		Some(PartialMatch { pattern_len, matched })
+	})
+}
+
+#[test]
+fn test_partial_match() {
+	use crate::{assert, let_assert};
+	let_assert!(Some(partial) = partial_match("foo bar", r"^foo \d+$"));
+	assert!(partial.pattern_len == 5);
+	assert!(partial.matched == (0..4));
+
+	let_assert!(Some(partial) = partial_match("id: abc", r"id: [a-z]+ \(\d\)"));
+	assert!(partial.pattern_len == 10);
+	assert!(partial.matched == (0..7));
+
+	assert!(let None = partial_match("foo", r"bar"));
+	assert!(let None = partial_match("foo", r"bar|baz"));
+}
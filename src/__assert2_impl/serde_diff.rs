@@ -0,0 +1,151 @@
+use serde_value::Value;
+use std::fmt::Write;
+
+use super::structural::field_path;
+use crate::FieldDiff;
+
+/// Placeholder for a field or element that only exists on one side.
+const MISSING: &str = "<missing>";
+
+/// Serialize both values and compare the serialized trees.
+///
+/// Returns `None` if either value fails to serialize, or if either value is not a struct or map.
+/// Other values, like sequences of numbers or bytes, are better shown with their normal expansion.
+pub fn diff<L: serde::Serialize + ?Sized, R: serde::Serialize + ?Sized>(left: &L, right: &R) -> Option<Vec<FieldDiff>> {
+	let left = serde_value::to_value(left).ok()?;
+	let right = serde_value::to_value(right).ok()?;
+	if !is_map(&left) || !is_map(&right) {
+		return None;
+	}
+	let mut diffs = Vec::new();
+	diff_values(&left, &right, "", &mut diffs);
+	Some(diffs)
+}
+
+/// Add the differences between two serialized values to `diffs`.
+fn diff_values(left: &Value, right: &Value, path: &str, diffs: &mut Vec<FieldDiff>) {
+	match (unwrap_newtype(left), unwrap_newtype(right)) {
+		(Value::Option(Some(left)), Value::Option(Some(right))) => diff_values(left, right, path, diffs),
+		(Value::Seq(left), Value::Seq(right)) => {
+			for i in 0..left.len().max(right.len()) {
+				let path = format!("{path}[{i}]");
+				match (left.get(i), right.get(i)) {
+					(Some(left), Some(right)) => diff_values(left, right, &path, diffs),
+					(left, right) => diffs.push(one_sided(path, left, right)),
+				}
+			}
+		},
+		(Value::Map(left), Value::Map(right)) => {
+			for (key, left_value) in left {
+				let path = key_path(path, key);
+				match right.get(key) {
+					Some(right_value) => diff_values(left_value, right_value, &path, diffs),
+					None => diffs.push(one_sided(path, Some(left_value), None)),
+				}
+			}
+			for (key, right_value) in right {
+				if !left.contains_key(key) {
+					diffs.push(one_sided(key_path(path, key), None, Some(right_value)));
+				}
+			}
+		},
+		(left, right) => {
+			if left != right {
+				diffs.push(FieldDiff {
+					path: path.into(),
+					left: render(left),
+					right: render(right),
+				});
+			}
+		},
+	}
+}
+
+/// Check if a serialized value is a struct or map, possibly wrapped in a newtype.
+fn is_map(value: &Value) -> bool {
+	matches!(unwrap_newtype(value), Value::Map(_))
+}
+
+/// Look through newtype wrappers, which do not add anything to the path.
+fn unwrap_newtype(value: &Value) -> &Value {
+	match value {
+		Value::Newtype(inner) => unwrap_newtype(inner),
+		value => value,
+	}
+}
+
+/// Get the path of a map entry.
+fn key_path(path: &str, key: &Value) -> String {
+	match key {
+		Value::String(key) => field_path(path, key),
+		key => format!("{path}[{}]", render(key)),
+	}
+}
+
+/// Create a difference for a field or element that only exists on one side.
+fn one_sided(path: String, left: Option<&Value>, right: Option<&Value>) -> FieldDiff {
+	FieldDiff {
+		path,
+		left: left.map_or_else(|| MISSING.into(), render),
+		right: right.map_or_else(|| MISSING.into(), render),
+	}
+}
+
+/// Render a serialized value in a compact, `Debug` like format.
+fn render(value: &Value) -> String {
+	let mut output = String::new();
+	write_value(&mut output, value);
+	output
+}
+
+fn write_value(output: &mut String, value: &Value) {
+	match value {
+		Value::Bool(x) => write!(output, "{x:?}").unwrap(),
+		Value::U8(x) => write!(output, "{x:?}").unwrap(),
+		Value::U16(x) => write!(output, "{x:?}").unwrap(),
+		Value::U32(x) => write!(output, "{x:?}").unwrap(),
+		Value::U64(x) => write!(output, "{x:?}").unwrap(),
+		Value::I8(x) => write!(output, "{x:?}").unwrap(),
+		Value::I16(x) => write!(output, "{x:?}").unwrap(),
+		Value::I32(x) => write!(output, "{x:?}").unwrap(),
+		Value::I64(x) => write!(output, "{x:?}").unwrap(),
+		Value::F32(x) => write!(output, "{x:?}").unwrap(),
+		Value::F64(x) => write!(output, "{x:?}").unwrap(),
+		Value::Char(x) => write!(output, "{x:?}").unwrap(),
+		Value::String(x) => write!(output, "{x:?}").unwrap(),
+		Value::Bytes(x) => write!(output, "{x:?}").unwrap(),
+		Value::Unit => output.push_str("()"),
+		Value::Option(None) => output.push_str("None"),
+		Value::Option(Some(x)) => {
+			output.push_str("Some(");
+			write_value(output, x);
+			output.push(')');
+		},
+		Value::Newtype(x) => write_value(output, x),
+		Value::Seq(items) => {
+			output.push('[');
+			for (i, item) in items.iter().enumerate() {
+				if i > 0 {
+					output.push_str(", ");
+				}
+				write_value(output, item);
+			}
+			output.push(']');
+		},
+		Value::Map(entries) => {
+			output.push('{');
+			for (i, (key, value)) in entries.iter().enumerate() {
+				if i > 0 {
+					output.push_str(", ");
+				}
+				match key {
+					Value::String(key) => output.push_str(key),
+					key => write_value(output, key),
+				}
+				output.push_str(": ");
+				write_value(output, value);
+			}
+			output.push('}');
+		},
+	}
+}
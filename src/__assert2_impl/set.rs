@@ -0,0 +1,48 @@
+/// Wrapper to select the right way to get the elements of a set-like value.
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+/// Get the elements of a collection that can be iterated by reference, like `HashSet`, `BTreeSet`, `Vec` or slices.
+pub trait IsCollection {
+	type Item;
+	fn __assert2_elements(&self) -> Vec<Self::Item>;
+}
+
+/// Get the elements of a cloneable iterator.
+pub trait IsIterator {
+	type Item;
+	fn __assert2_elements(&self) -> Vec<Self::Item>;
+}
+
+impl<'a, T: ?Sized> IsCollection for &Wrap<'a, T>
+where
+	&'a T: IntoIterator,
+{
+	type Item = <&'a T as IntoIterator>::Item;
+
+	fn __assert2_elements(&self) -> Vec<Self::Item> {
+		self.0.into_iter().collect()
+	}
+}
+
+impl<T: Iterator + Clone> IsIterator for Wrap<'_, T> {
+	type Item = T::Item;
+
+	fn __assert2_elements(&self) -> Vec<Self::Item> {
+		self.0.clone().collect()
+	}
+}
+
+/// Get the elements of `items` that do not occur in `set`.
+pub fn missing<'a, T: PartialEq>(items: &'a [T], set: &[T]) -> Vec<&'a T> {
+	items.iter()
+		.filter(|item| !set.contains(item))
+		.collect()
+}
+
+#[test]
+fn test_missing() {
+	use crate::assert;
+	assert!(missing(&[1, 2, 3], &[3, 2, 1, 0]).is_empty());
+	assert!(missing::<i32>(&[], &[]).is_empty());
+	assert!(missing(&[1, 2, 3, 4], &[2, 3]) == [&1, &4]);
+}
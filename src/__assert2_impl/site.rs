@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counter for the number of times an assertion site was executed.
+///
+/// Each expansion of an assertion macro has its own static counter.
+pub struct SiteCounter {
+	/// The number of times the assertion was executed.
+	executed: AtomicUsize,
+
+	/// The number of times the assertion failed.
+	failed: AtomicUsize,
+}
+
+impl SiteCounter {
+	/// Create a new counter.
+	#[allow(clippy::new_without_default)]
+	pub const fn new() -> Self {
+		Self {
+			executed: AtomicUsize::new(0),
+			failed: AtomicUsize::new(0),
+		}
+	}
+
+	/// Record an execution of the assertion site.
+	pub fn hit(&self) -> &Self {
+		self.executed.fetch_add(1, Ordering::Relaxed);
+		self
+	}
+
+	/// Record a failure of the assertion site.
+	///
+	/// Returns the number of times the assertion passed so far.
+	pub(crate) fn record_failure(&self) -> usize {
+		let failed = self.failed.fetch_add(1, Ordering::Relaxed) + 1;
+		self.executed.load(Ordering::Relaxed).saturating_sub(failed)
+	}
+}
+
+/// Format a count with a comma between each group of three digits.
+pub(crate) fn format_count(count: usize) -> String {
+	let digits = count.to_string();
+	let mut output = String::with_capacity(digits.len() + digits.len() / 3);
+	for (i, digit) in digits.chars().enumerate() {
+		if i > 0 && (digits.len() - i) % 3 == 0 {
+			output.push(',');
+		}
+		output.push(digit);
+	}
+	output
+}
+
+#[test]
+fn test_site_counter() {
+	use crate::assert;
+	let site = SiteCounter::new();
+	site.hit();
+	site.hit();
+	site.hit();
+	assert!(site.record_failure() == 2);
+	site.hit();
+	assert!(site.record_failure() == 2);
+}
+
+#[test]
+fn test_format_count() {
+	use crate::assert;
+	assert!(format_count(0) == "0");
+	assert!(format_count(999) == "999");
+	assert!(format_count(4182) == "4,182");
+	assert!(format_count(1234567) == "1,234,567");
+	assert!(format_count(100000) == "100,000");
+}
+
+#[test]
+fn test_print_passed_count() {
+	use crate::assert;
+	use crate::__assert2_impl::print::{BooleanExpr, FailedCheck, Silence};
+
+	static SITE: SiteCounter = SiteCounter::new();
+	let check = |count: &'static str| {
+		let _silence = Silence::enter();
+		FailedCheck {
+			macro_name: "check",
+			file: file!(),
+			line: line!(),
+			column: column!(),
+			custom_msg: None,
+			expression: BooleanExpr { expression: "false" },
+			fragments: &[],
+			defaults: Some(count),
+			site: SITE.hit(),
+		}.print()
+	};
+
+	SITE.hit();
+	SITE.hit();
+	let failure = check("count");
+	assert!(failure.report().contains("with history:\n  passed 2 times before failing\n"));
+	SITE.hit();
+	let failure = check("count");
+	assert!(failure.report().contains("passed 3 times before failing"));
+	let failure = check("no-count");
+	assert!(!failure.report().contains("with history:"));
+}
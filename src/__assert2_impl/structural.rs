@@ -0,0 +1,79 @@
+use std::fmt::Debug;
+
+use crate::{FieldDiff, StructuralDiff};
+
+/// Wrapper for the two sides of a comparison, to compare them field by field if possible using autoref specialization.
+pub struct Root<'a, L: ?Sized, R: ?Sized>(pub &'a L, pub &'a R);
+
+pub trait IsStructuralRoot {
+	fn __assert2_structural_diff(&self) -> Option<Vec<FieldDiff>>;
+}
+
+/// Compare the serialized values field by field, if the `serde` feature is enabled.
+///
+/// The trait always exists, so that the generated code does not depend on the enabled features.
+pub trait IsSerializeRoot {
+	fn __assert2_structural_diff(&self) -> Option<Vec<FieldDiff>>;
+}
+
+pub trait IsNotStructuralRoot {
+	fn __assert2_structural_diff(&self) -> Option<Vec<FieldDiff>> {
+		None
+	}
+}
+
+// Called as `(&&&Root(left, right)).__assert2_structural_diff()`, the implementations are found in this order:
+// by value, after one autoref and after two derefs.
+impl<T: StructuralDiff + ?Sized> IsStructuralRoot for &&Root<'_, T, T> {
+	fn __assert2_structural_diff(&self) -> Option<Vec<FieldDiff>> {
+		let mut diffs = Vec::new();
+		self.0.structural_diff(self.1, "", &mut diffs);
+		Some(diffs)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<L: serde::Serialize + ?Sized, R: serde::Serialize + ?Sized> IsSerializeRoot for &&&Root<'_, L, R> {
+	fn __assert2_structural_diff(&self) -> Option<Vec<FieldDiff>> {
+		super::serde_diff::diff(self.0, self.1)
+	}
+}
+
+impl<L: ?Sized, R: ?Sized> IsNotStructuralRoot for Root<'_, L, R> {}
+
+/// Wrapper for a field of two values, used by the derived `StructuralDiff` implementations.
+///
+/// Fields that implement `StructuralDiff` are compared field by field,
+/// other fields are compared with `PartialEq`.
+pub struct Field<'a, T: ?Sized>(pub &'a T, pub &'a T);
+
+pub trait IsStructuralField {
+	fn __assert2_field_diff(&self, path: &str, diffs: &mut Vec<FieldDiff>);
+}
+
+pub trait IsPartialEqField {
+	fn __assert2_field_diff(&self, path: &str, diffs: &mut Vec<FieldDiff>);
+}
+
+impl<T: StructuralDiff + ?Sized> IsStructuralField for &Field<'_, T> {
+	fn __assert2_field_diff(&self, path: &str, diffs: &mut Vec<FieldDiff>) {
+		self.0.structural_diff(self.1, path, diffs);
+	}
+}
+
+impl<T: PartialEq + Debug + ?Sized> IsPartialEqField for Field<'_, T> {
+	fn __assert2_field_diff(&self, path: &str, diffs: &mut Vec<FieldDiff>) {
+		if self.0 != self.1 {
+			diffs.push(FieldDiff::new(path, self.0, self.1));
+		}
+	}
+}
+
+/// Get the path of a field, given the path of the value that contains it.
+pub fn field_path(path: &str, field: &str) -> String {
+	if path.is_empty() {
+		field.into()
+	} else {
+		format!("{path}.{field}")
+	}
+}
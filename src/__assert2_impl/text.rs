@@ -0,0 +1,32 @@
+/// Wrapper to get the text of a string operand, using autoref specialization.
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+pub trait IsText<'a> {
+	fn __assert2_text(&self) -> Option<&'a str>;
+}
+
+pub trait IsNotText<'a> {
+	fn __assert2_text(&self) -> Option<&'a str> {
+		None
+	}
+}
+
+impl<'a, T: AsRef<str> + ?Sized> IsText<'a> for &Wrap<'a, T> {
+	fn __assert2_text(&self) -> Option<&'a str> {
+		Some(self.0.as_ref())
+	}
+}
+
+impl<'a, T: ?Sized> IsNotText<'a> for Wrap<'a, T> {}
+
+#[test]
+#[allow(clippy::needless_borrow)]
+fn test_text() {
+	use crate::assert;
+	let owned = String::from("a\nb");
+	let borrowed = "c";
+	assert!((&&Wrap(&owned)).__assert2_text() == Some("a\nb"));
+	assert!((&&Wrap(&borrowed)).__assert2_text() == Some("c"));
+	assert!((&&Wrap(&std::borrow::Cow::Borrowed("d"))).__assert2_text() == Some("d"));
+	assert!((&&Wrap(&5)).__assert2_text().is_none());
+}
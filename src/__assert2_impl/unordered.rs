@@ -0,0 +1,56 @@
+/// The elements that differ between two collections, compared as multisets.
+pub struct UnorderedDifference<'a, T> {
+	/// Elements that occur more often in the left collection than in the right.
+	pub only_left: Vec<&'a T>,
+
+	/// Elements that occur more often in the right collection than in the left.
+	pub only_right: Vec<&'a T>,
+}
+
+/// Compare two collections as multisets.
+///
+/// Every element of `left` is matched with at most one equal element of `right`.
+/// Returns `None` if all elements could be matched.
+///
+/// This only requires `PartialEq`, so it runs in quadratic time.
+pub fn difference<'a, T: PartialEq>(left: &'a [T], right: &'a [T]) -> Option<UnorderedDifference<'a, T>> {
+	let mut matched = vec![false; right.len()];
+	let mut only_left = Vec::new();
+	for item in left {
+		let found = right.iter()
+			.enumerate()
+			.position(|(i, other)| !matched[i] && item == other);
+		match found {
+			Some(i) => matched[i] = true,
+			None => only_left.push(item),
+		}
+	}
+
+	let only_right: Vec<_> = right.iter()
+		.zip(&matched)
+		.filter(|(_, &matched)| !matched)
+		.map(|(item, _)| item)
+		.collect();
+
+	if only_left.is_empty() && only_right.is_empty() {
+		None
+	} else {
+		Some(UnorderedDifference { only_left, only_right })
+	}
+}
+
+#[test]
+fn test_difference() {
+	use crate::{assert, let_assert};
+	assert!(let None = difference(&[1, 2, 3], &[3, 1, 2]));
+	assert!(let None = difference::<i32>(&[], &[]));
+	assert!(let None = difference(&[1, 1, 2], &[1, 2, 1]));
+
+	let_assert!(Some(diff) = difference(&[1, 1, 2, 4], &[1, 2, 3]));
+	assert!(diff.only_left == [&1, &4]);
+	assert!(diff.only_right == [&3]);
+
+	let_assert!(Some(diff) = difference(&[1], &[1, 1]));
+	assert!(diff.only_left.is_empty());
+	assert!(diff.only_right == [&1]);
+}
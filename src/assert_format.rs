@@ -0,0 +1,32 @@
+use std::fmt::Formatter;
+
+/// Trait to control how a value is rendered in the output of failed assertions.
+///
+/// If a type implements this trait, it is used instead of the `Debug` and `Display` implementations of the type.
+/// This lets you show large values in a shorter form or with a custom layout, without changing their `Debug` output.
+///
+/// The formatter has the alternate flag set (`f.alternate()`) when the pretty format is requested,
+/// in which case the output may span multiple lines.
+/// The output is colored and diffed in the same way as `Debug` output.
+///
+/// The trait is only used for the values that `assert2` prints itself, like the operands of a comparison.
+/// It is not used for fields of other values, since those are printed with their own `Debug` implementation.
+///
+/// ```
+/// # use assert2::{let_assert, try_check, AssertFormat};
+/// #[derive(Debug, PartialEq)]
+/// struct Blob(Vec<u8>);
+///
+/// impl AssertFormat for Blob {
+///     fn assert_fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+///         write!(f, "Blob({} bytes, starting with {:02x?})", self.0.len(), &self.0[..self.0.len().min(4)])
+///     }
+/// }
+///
+/// let_assert!(Err(failure) = try_check!(Blob(vec![1; 1000]) == Blob(vec![2; 1000])));
+/// assert!(failure.report().contains("Blob(1000 bytes, starting with [01, 01, 01, 01])"));
+/// ```
+pub trait AssertFormat {
+	/// Format the value for the output of a failed assertion.
+	fn assert_fmt(&self, f: &mut Formatter) -> std::fmt::Result;
+}
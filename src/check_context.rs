@@ -0,0 +1,105 @@
+use crate::Failure;
+
+/// A context that collects failed checks and fails the test when it is dropped.
+///
+/// A failed [`check!()`](crate::check) panics at the end of the scope it was used in,
+/// so a `check!()` in the body of a loop stops the loop after the first failing iteration.
+/// With [`check_ctx!()`](crate::check_ctx), the failure is recorded in a `CheckContext` instead,
+/// and the context panics once when it is dropped, after all iterations ran.
+///
+/// Each failure is printed when the check is evaluated, just like for `check!()`.
+/// The panic message of the context lists all recorded failures.
+/// Use [`into_failures()`](Self::into_failures) to take the failures out of the context and report them yourself, without panicking.
+///
+/// A context can be moved to other threads, so its failures are not counted by [`checkpoint!()`](crate::checkpoint).
+///
+/// ```should_panic
+/// use assert2::{check_ctx, CheckContext};
+///
+/// let mut ctx = CheckContext::new();
+/// for i in 0..10 {
+///     check_ctx!(ctx, i % 3 != 0, "bad value: {}", i);
+/// }
+/// // Panics here with "4 checks failed", after all values were checked.
+/// ```
+pub struct CheckContext {
+	/// The failed checks, in the order they were recorded.
+	failures: Vec<Failure>,
+
+	/// The location where the context was created.
+	location: &'static std::panic::Location<'static>,
+}
+
+impl CheckContext {
+	/// Create an empty context.
+	#[track_caller]
+	pub fn new() -> Self {
+		Self {
+			failures: Vec::new(),
+			location: std::panic::Location::caller(),
+		}
+	}
+
+	/// Record a failed check in the context.
+	///
+	/// This is used by [`check_ctx!()`](crate::check_ctx), but failures from [`try_check!()`](crate::try_check) can be recorded too.
+	pub fn add_failure(&mut self, failure: Failure) {
+		self.failures.push(failure);
+	}
+
+	/// Get the failed checks recorded so far.
+	pub fn failures(&self) -> &[Failure] {
+		&self.failures
+	}
+
+	/// Check if no failed checks were recorded.
+	pub fn is_ok(&self) -> bool {
+		self.failures.is_empty()
+	}
+
+	/// Take the failed checks out of the context, so that it does not panic when dropped.
+	pub fn into_failures(mut self) -> Vec<Failure> {
+		std::mem::take(&mut self.failures)
+	}
+
+	/// Get the message for the panic when the context is dropped.
+	fn panic_message(&self) -> String {
+		let mut message = match self.failures.len() {
+			1 => String::from("1 check failed"),
+			count => format!("{count} checks failed"),
+		};
+		message.push_str(&format!(" in context created at {}:", self.location));
+		for failure in &self.failures {
+			message.push_str("\n  ");
+			message.push_str(&crate::__assert2_impl::describe_failure(failure));
+		}
+		message
+	}
+}
+
+impl Default for CheckContext {
+	#[track_caller]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl std::fmt::Debug for CheckContext {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("CheckContext")
+			.field("failures", &self.failures.len())
+			.field("location", &self.location)
+			.finish()
+	}
+}
+
+impl Drop for CheckContext {
+	fn drop(&mut self) {
+		if self.failures.is_empty() {
+			return;
+		}
+		if crate::__assert2_impl::should_panic() {
+			panic!("{}", self.panic_message());
+		}
+	}
+}
@@ -0,0 +1,54 @@
+/// Extension trait to record the items flowing through an iterator at a named checkpoint.
+///
+/// When an assertion fails, the items recorded at each checkpoint while evaluating the assertion are printed.
+/// This lets you inspect the intermediate results of long iterator chains.
+/// Only the first few items of each checkpoint are shown.
+///
+/// Items are only recorded while an assertion is being evaluated,
+/// so checkpoints can be left in place without overhead in other code.
+///
+/// ```should_panic
+/// # use assert2::{check, InspectCheckpoint};
+/// let items = [1, 2, 3, 4, 5, 6];
+/// check!(items.iter().filter(|x| *x % 2 == 0).inspect_checkpoint("after filter").count() == 2);
+/// ```
+pub trait InspectCheckpoint: Iterator + Sized {
+	/// Record the items of this iterator at a checkpoint with the given name.
+	fn inspect_checkpoint(self, name: &'static str) -> Checkpoint<Self>;
+}
+
+impl<I> InspectCheckpoint for I
+where
+	I: Iterator,
+	I::Item: std::fmt::Debug,
+{
+	fn inspect_checkpoint(self, name: &'static str) -> Checkpoint<Self> {
+		Checkpoint { inner: self, name }
+	}
+}
+
+/// Iterator adaptor that records all items at a checkpoint.
+///
+/// See [`InspectCheckpoint`] for more details.
+pub struct Checkpoint<I> {
+	inner: I,
+	name: &'static str,
+}
+
+impl<I> Iterator for Checkpoint<I>
+where
+	I: Iterator,
+	I::Item: std::fmt::Debug,
+{
+	type Item = I::Item;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.inner.next()?;
+		crate::__assert2_impl::checkpoint::record(self.name, &item);
+		Some(item)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
@@ -0,0 +1,42 @@
+use std::sync::RwLock;
+use std::time::Instant;
+
+/// A function that reports the current time.
+pub type TimeSource = fn() -> Instant;
+
+static SOURCE: RwLock<Option<TimeSource>> = RwLock::new(None);
+
+/// Set the function used to get the current time.
+///
+/// The time source is used by time based assertions, like [`assert_completes_within!`](macro.assert_completes_within.html)
+/// and [`assert_eventually!`](macro.assert_eventually.html), to measure elapsed time.
+/// By default, [`Instant::now()`] is used.
+///
+/// You can use this function to install a fake clock, to get deterministic results and output in your own tests.
+/// Note that [`assert_eventually!`](macro.assert_eventually.html) will never time out if the time source does not advance.
+/// Pass `None` to restore the default behaviour.
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use std::sync::atomic::{AtomicU64, Ordering};
+/// fn fake_now() -> Instant {
+///     static BASE: std::sync::Mutex<Option<Instant>> = std::sync::Mutex::new(None);
+///     static TICKS: AtomicU64 = AtomicU64::new(0);
+///     let base = *BASE.lock().unwrap().get_or_insert_with(Instant::now);
+///     base + Duration::from_millis(10 * TICKS.fetch_add(1, Ordering::Relaxed))
+/// }
+/// assert2::set_time_source(Some(fake_now));
+/// # assert2::set_time_source(None);
+/// ```
+pub fn set_time_source(source: Option<TimeSource>) {
+	*SOURCE.write().unwrap_or_else(|e| e.into_inner()) = source;
+}
+
+/// Get the current time using the configured time source.
+pub(crate) fn now() -> Instant {
+	let source = *SOURCE.read().unwrap_or_else(|e| e.into_inner());
+	match source {
+		Some(source) => source(),
+		None => Instant::now(),
+	}
+}
@@ -0,0 +1,22 @@
+use std::sync::RwLock;
+
+static OVERRIDE: RwLock<Option<bool>> = RwLock::new(None);
+
+/// Force colored output on or off.
+///
+/// This takes precedence over all environment variables, including the `color` and `no-color` options in the `ASSERT2` environment variable.
+/// It is mainly useful to get identical output on every machine when you test the output of failed assertions.
+/// Pass `None` to restore the default behaviour.
+///
+/// ```
+/// assert2::set_color_override(Some(false));
+/// # assert2::set_color_override(None);
+/// ```
+pub fn set_color_override(color: Option<bool>) {
+	*OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = color;
+}
+
+/// Get the color override, if any.
+pub(crate) fn color_override() -> Option<bool> {
+	*OVERRIDE.read().unwrap_or_else(|e| e.into_inner())
+}
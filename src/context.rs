@@ -0,0 +1,48 @@
+use std::cell::RefCell;
+
+thread_local! {
+	/// The run context entries of the current thread.
+	static RUN_CONTEXT: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Add an entry to the run context of the current thread.
+///
+/// All entries in the run context are printed with every failed assertion on the current thread.
+/// This is useful to record information needed to reproduce a failure,
+/// such as the seed of a randomized test.
+///
+/// If an entry with the same key already exists, its value is replaced.
+///
+/// The run context is thread-local, so it is not shared between tests running in parallel.
+/// It is also not inherited by newly spawned threads.
+///
+/// ```
+/// # let seed = 0xdeadbeefu32;
+/// assert2::set_run_context("seed", format_args!("{seed:#x}"));
+/// ```
+pub fn set_run_context(key: impl Into<String>, value: impl std::fmt::Display) {
+	let key = key.into();
+	let value = value.to_string();
+	RUN_CONTEXT.with(|context| {
+		let mut context = context.borrow_mut();
+		match context.iter_mut().find(|(k, _)| *k == key) {
+			Some(entry) => entry.1 = value,
+			None => context.push((key, value)),
+		}
+	})
+}
+
+/// Remove an entry from the run context of the current thread.
+pub fn remove_run_context(key: &str) {
+	RUN_CONTEXT.with(|context| context.borrow_mut().retain(|(k, _)| k != key))
+}
+
+/// Remove all entries from the run context of the current thread.
+pub fn clear_run_context() {
+	RUN_CONTEXT.with(|context| context.borrow_mut().clear())
+}
+
+/// Get a copy of all entries in the run context of the current thread.
+pub(crate) fn run_context() -> Vec<(String, String)> {
+	RUN_CONTEXT.with(|context| context.borrow().clone())
+}
@@ -0,0 +1,639 @@
+//! The diff renderer used for failed assertions.
+//!
+//! You can use these types to render diffs in your own error messages,
+//! with the same look as the failed assertions of this crate.
+//!
+//! By default, colors are used according to the `ASSERT2` environment variable and the
+//! [clicolors specification](https://bixense.com/clicolors/), just like for failed assertions.
+//! Use `with_color(false)` to always write plain text.
+//!
+//! Invisible characters in the changed parts, like tabs, non-breaking spaces, zero-width characters and trailing spaces,
+//! are written as escape sequences (for example `\u{a0}`), so that differences in them can be seen.
+//!
+//! ```
+//! use assert2::diff::{MultiLineDiff, SingleLineDiff};
+//!
+//! let mut buffer = String::new();
+//! MultiLineDiff::new("foo\nbar", "foo\nbaz")
+//!     .with_color(false)
+//!     .write_interleaved(&mut buffer);
+//! assert!(buffer == "  foo\n< bar\n> baz");
+//!
+//! let left = "a\nb\nc\nd\ne\nf\ng";
+//! let right = "a\nb\nc\nd\ne\nf\nh";
+//! let mut buffer = String::new();
+//! MultiLineDiff::new(left, right)
+//!     .with_color(false)
+//!     .with_context(Some(2))
+//!     .write_interleaved(&mut buffer);
+//! assert!(buffer == "  … 4 identical lines …\n  e\n  f\n< g\n> h");
+//!
+//! let diff = SingleLineDiff::new("Hello world", "Hello moon").with_color(false);
+//! let mut left = String::new();
+//! diff.write_left(&mut left);
+//! assert!(left == "Hello world");
+//! ```
+
+use std::fmt::Write;
+use yansi::Paint;
+
+use crate::__assert2_impl::print::options::AssertOptions;
+use crate::theme::Themed;
+
+mod patience;
+
+/// The algorithm used to match the lines of a [`MultiLineDiff`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DiffAlgorithm {
+	/// Find the longest common subsequence of lines.
+	///
+	/// This shows the fewest changed lines, but it can match common lines like braces with the wrong block.
+	Lcs,
+
+	/// First match the lines that occur exactly once on both sides, then diff the regions in between.
+	///
+	/// This keeps moved or reordered blocks together, which often gives a more readable diff of large values.
+	Patience,
+}
+
+/// A line diff between two inputs.
+pub struct MultiLineDiff<'a> {
+	/// The actual diff results from the [`diff`] crate.
+	line_diffs: Vec<LineDiff<'a>>,
+
+	/// Write color codes to the output.
+	color: bool,
+
+	/// The number of identical lines to show around each change, or `None` to show all lines.
+	context: Option<usize>,
+}
+
+impl<'a> MultiLineDiff<'a> {
+	/// Create a new diff between a left and right input.
+	///
+	/// The algorithm is taken from the `diff` option of the `ASSERT2` environment variable.
+	pub fn new(left: &'a str, right: &'a str) -> Self {
+		Self::with_algorithm(left, right, AssertOptions::get().diff_algorithm)
+	}
+
+	/// Create a new diff between a left and right input using a specific algorithm.
+	pub fn with_algorithm(left: &'a str, right: &'a str, algorithm: DiffAlgorithm) -> Self {
+		let options = AssertOptions::get();
+		let diffs = match algorithm {
+			DiffAlgorithm::Lcs => diff::lines(left, right),
+			DiffAlgorithm::Patience => patience::lines(left, right),
+		};
+		Self {
+			line_diffs: LineDiff::from_diff(diffs),
+			color: options.color,
+			context: options.diff_context,
+		}
+	}
+
+	/// Enable or disable color codes in the output.
+	pub fn with_color(mut self, color: bool) -> Self {
+		self.color = color;
+		self
+	}
+
+	/// Set the number of identical lines to show around each change.
+	///
+	/// The default is taken from the `diff-context` option of the `ASSERT2` environment variable, which defaults to 3 lines.
+	///
+	/// Longer runs of identical lines are folded into a single line that says how many lines were left out.
+	/// Pass `None` to always show all lines.
+	/// If the inputs are identical, all lines are shown.
+	pub fn with_context(mut self, context: Option<usize>) -> Self {
+		self.context = context;
+		self
+	}
+
+	/// Write the left and right input interleaved with eachother, highlighting the differences between the two.
+	///
+	/// This does not write a line break after the last line.
+	pub fn write_interleaved(&self, buffer: &mut String) {
+		write_maybe_plain(buffer, self.color, |buffer| self.write_interleaved_colored(buffer));
+	}
+
+	fn write_interleaved_colored(&self, buffer: &mut String) {
+		let folded = self.folded_lines();
+		for (i, diff) in self.line_diffs.iter().enumerate() {
+			if let Some(range) = folded.iter().find(|range| range.contains(&i)) {
+				if range.start == i {
+					let count = range.len();
+					writeln!(buffer, "  {}", Paint::new(&format_args!("… {count} identical lines …")).primary().on_primary().dim()).unwrap();
+				}
+				continue;
+			}
+			match *diff {
+				LineDiff::LeftOnly(left) => {
+					let left = escape_invisible(left, true);
+					writeln!(buffer, "{}", Themed::paint_left(&format_args!("< {left}"))).unwrap();
+				},
+				LineDiff::RightOnly(right) => {
+					let right = escape_invisible(right, true);
+					writeln!(buffer, "{}", Themed::paint_right(&format_args!("> {right}"))).unwrap();
+				},
+				LineDiff::Different(left, right) => {
+					let diff = SingleLineDiff::new(left, right);
+					write!(buffer, "{} ", "<".paint(diff.left_highlights.normal)).unwrap();
+					diff.write_left(buffer);
+					write!(buffer, "\n{} ", ">".paint(diff.right_highlights.normal)).unwrap();
+					diff.write_right(buffer);
+					buffer.push('\n');
+				},
+				LineDiff::Equal(text) => {
+					writeln!(buffer, "  {}", text.primary().on_primary().dim()).unwrap();
+				},
+			}
+		}
+		// Remove last newline.
+		buffer.pop();
+	}
+
+	/// Get the ranges of identical lines that are too far from a change to be shown.
+	fn folded_lines(&self) -> Vec<std::ops::Range<usize>> {
+		let Some(context) = self.context else {
+			return Vec::new();
+		};
+		let is_equal = |diff: &LineDiff| matches!(diff, LineDiff::Equal(_));
+		if self.line_diffs.iter().all(is_equal) {
+			return Vec::new();
+		}
+
+		let mut folded = Vec::new();
+		let mut start = 0;
+		while start < self.line_diffs.len() {
+			if !is_equal(&self.line_diffs[start]) {
+				start += 1;
+				continue;
+			}
+			let end = self.line_diffs[start..].iter()
+				.position(|diff| !is_equal(diff))
+				.map_or(self.line_diffs.len(), |len| start + len);
+
+			// Keep the context after the previous change and before the next change.
+			let keep_before = if start == 0 { 0 } else { context };
+			let keep_after = if end == self.line_diffs.len() { 0 } else { context };
+
+			// Folding a single line would not make the output any shorter.
+			if end - start > keep_before + keep_after + 1 {
+				folded.push(start + keep_before..end - keep_after);
+			}
+			start = end;
+		}
+		folded
+	}
+}
+
+enum LineDiff<'a> {
+	// There is only a left line.
+	LeftOnly(&'a str),
+	// There is only a right line.
+	RightOnly(&'a str),
+	// There is a left and a right line, but they are different.
+	Different(&'a str, &'a str),
+	// There is a left and a right line, and they are equal.
+	Equal(&'a str),
+}
+
+impl<'a> LineDiff<'a> {
+	fn from_diff(diffs: Vec<diff::Result<&'a str>>) -> Vec<Self> {
+		let mut output = Vec::with_capacity(diffs.len());
+
+		let mut seen_left = 0;
+		for item in diffs {
+			match item {
+				diff::Result::Left(l) => {
+					output.push(LineDiff::LeftOnly(l));
+					seen_left += 1;
+				},
+				diff::Result::Right(r) => {
+					if let Some(last) = output.last_mut() {
+						match last {
+							// If we see exactly one left line followed by a right line,
+							// make it a `Self::Different` entry so we perform word diff later.
+							Self::LeftOnly(old_l) if seen_left == 1 => {
+								*last = Self::Different(old_l, r);
+								seen_left = 0;
+								continue;
+							},
+							// If we see another right line, turn the `Self::Different` back into individual lines.
+							// This way, we dont do word diffs when one left line was replaced by multiple right lines.
+							Self::Different(old_l, old_r) => {
+								let old_r = *old_r;
+								*last = Self::LeftOnly(old_l);
+								output.push(Self::RightOnly(old_r));
+								output.push(Self::RightOnly(r));
+								seen_left = 0;
+								continue;
+							},
+							// In other cases, just continue to the default behaviour of adding a `RightOnly` entry.
+							Self::LeftOnly(_) => (),
+							Self::RightOnly(_) => (),
+							Self::Equal(_) => (),
+						}
+					}
+					output.push(LineDiff::RightOnly(r));
+					seen_left = 0;
+				},
+				diff::Result::Both(l, _r) => {
+					output.push(Self::Equal(l));
+					seen_left = 0;
+				}
+			}
+		}
+
+		output
+	}
+}
+
+/// A character/word based diff between two single-line inputs.
+pub struct SingleLineDiff<'a> {
+	/// The left line.
+	left: &'a str,
+
+	/// The right line.
+	right: &'a str,
+
+	/// The highlighting for the left line.
+	left_highlights: Highlighter,
+
+	/// The highlighting for the right line.
+	right_highlights: Highlighter,
+
+	/// Write color codes to the output.
+	color: bool,
+}
+
+impl<'a> SingleLineDiff<'a> {
+	/// Create a new word diff between two input lines.
+	pub fn new(left: &'a str, right: &'a str) -> Self {
+		let left_words = Self::split_words(left);
+		let right_words = Self::split_words(right);
+		let diffs = diff::slice(&left_words, &right_words);
+
+		let mut left_highlights = Highlighter::new(crate::theme::left());
+		let mut right_highlights = Highlighter::new(crate::theme::right());
+		for diff in &diffs {
+			match diff {
+				diff::Result::Left(left) => {
+					left_highlights.push(left.len(), true);
+				},
+				diff::Result::Right(right) => {
+					right_highlights.push(right.len(), true);
+				},
+				diff::Result::Both(left, right) => {
+					left_highlights.push(left.len(), false);
+					right_highlights.push(right.len(), false);
+				}
+			}
+		}
+
+		Self {
+			left,
+			right,
+			left_highlights,
+			right_highlights,
+			color: AssertOptions::get().color,
+		}
+	}
+
+	/// Enable or disable color codes in the output.
+	pub fn with_color(mut self, color: bool) -> Self {
+		self.color = color;
+		self
+	}
+
+	/// Write the left line with highlighting.
+	///
+	/// This does not write a line break to the buffer.
+	pub fn write_left(&self, buffer: &mut String) {
+		write_maybe_plain(buffer, self.color, |buffer| self.left_highlights.write_highlighted(buffer, self.left));
+	}
+
+	/// Write the right line with highlighting.
+	///
+	/// This does not write a line break to the buffer.
+	pub fn write_right(&self, buffer: &mut String) {
+		write_maybe_plain(buffer, self.color, |buffer| self.right_highlights.write_highlighted(buffer, self.right));
+	}
+
+	/// Split an input line into individual words.
+	///
+	/// Punctuation like `/`, `.` and `-` is split into separate words, except for `::` which is kept together.
+	/// Identifiers are also split at camelCase boundaries, including the end of an acronym like in `HTTPServer`,
+	/// so that only the differing segment of a path, URL or identifier is highlighted.
+	fn split_words(mut input: &str) -> Vec<&str> {
+		/// Check if there should be a word break between character `a` and `b`, followed by `c`.
+		fn is_break_point(a: char, b: char, c: Option<char>) -> bool {
+			if a.is_alphabetic() {
+				if !b.is_alphabetic() || (a.is_lowercase() && !b.is_lowercase()) {
+					true
+				} else {
+					// Break before the last capital of an acronym that is followed by a camelCase word.
+					a.is_uppercase() && b.is_uppercase() && c.map_or(false, |c| c.is_lowercase())
+				}
+			} else if a.is_ascii_digit() {
+				!b.is_ascii_digit()
+			} else if a.is_whitespace() {
+				!b.is_whitespace()
+			} else {
+				!(a == ':' && b == ':')
+			}
+		}
+
+		let mut output = Vec::new();
+		while !input.is_empty() {
+			let mut chars = input.char_indices().peekable();
+			let mut split = input.len();
+			while let Some((_, a)) = chars.next() {
+				let Some(&(pos, b)) = chars.peek() else {
+					break;
+				};
+				let c = input[pos + b.len_utf8()..].chars().next();
+				if is_break_point(a, b, c) {
+					split = pos;
+					break;
+				}
+			}
+			let (head, tail) = input.split_at(split);
+			output.push(head);
+			input = tail;
+		}
+		output
+	}
+}
+
+/// Highlighter that incrementaly builds a range of alternating styles.
+struct Highlighter {
+	/// The ranges of alternating highlighting.
+	///
+	/// If the boolean is true, the range should be printed with the `highlight` style.
+	/// If the boolean is false, the range should be printed with the `normal` style.
+	ranges: Vec<(bool, std::ops::Range<usize>)>,
+
+	/// The total length of the highlighted ranges (in bytes, not characters or terminal cells).
+	total_highlighted: usize,
+
+	/// The style for non-highlighted words.
+	normal: yansi::Style,
+
+	/// The style for highlighted words.
+	highlight: yansi::Style,
+}
+
+impl Highlighter {
+	/// Create a new highlighter with the given color.
+	fn new(color: yansi::Color) -> Self {
+		let normal = yansi::Style::new().fg(color);
+		let highlight = crate::theme::highlight(color);
+		Self {
+			ranges: Vec::new(),
+			total_highlighted: 0,
+			normal,
+			highlight,
+		}
+	}
+
+	/// Push a range to the end of the highlighter.
+	fn push(&mut self, len: usize, highlight: bool) {
+		if highlight {
+			self.total_highlighted += len;
+		}
+		if let Some(last) = self.ranges.last_mut() {
+			if last.0 == highlight {
+				last.1.end += len;
+			} else {
+				let start = last.1.end;
+				self.ranges.push((highlight, start..start + len));
+			}
+		} else {
+			self.ranges.push((highlight, 0..len))
+		}
+	}
+
+	/// Write the data using the highlight ranges.
+	///
+	/// A long unchanged prefix or suffix is elided, keeping only some context around the changes.
+	/// Invisible characters in the changes are escaped.
+	fn write_highlighted(&self, buffer: &mut String, data: &str) {
+		let not_highlighted = data.len() - self.total_highlighted;
+		if not_highlighted < div_ceil(self.total_highlighted, 2) {
+			let data = if self.total_highlighted > 0 { escape_invisible(data, true) } else { data.into() };
+			write!(buffer, "{}", data.paint(self.normal)).unwrap();
+		} else {
+			let last = self.ranges.len() - 1;
+			for (i, (highlight, range)) in self.ranges.iter().cloned().enumerate() {
+				let trailing = range.end == data.len();
+				let piece = &data[range];
+				if highlight {
+					write!(buffer, "{}", escape_invisible(piece, trailing).paint(self.highlight)).unwrap();
+				} else if i == 0 && last > 0 {
+					write!(buffer, "{}", elide_start(piece).paint(self.normal)).unwrap();
+				} else if i == last && last > 0 {
+					write!(buffer, "{}", elide_end(piece).paint(self.normal)).unwrap();
+				} else {
+					write!(buffer, "{}", piece.paint(self.normal)).unwrap();
+				}
+			}
+		}
+	}
+}
+
+/// Write data with a single highlighted range, using the same style as the word diff.
+pub(crate) fn write_highlighted_range(buffer: &mut String, data: &str, range: std::ops::Range<usize>, color: yansi::Color) {
+	let mut highlighter = Highlighter::new(color);
+	highlighter.push(range.start, false);
+	highlighter.push(range.len(), true);
+	highlighter.push(data.len() - range.end, false);
+	highlighter.write_highlighted(buffer, data);
+}
+
+/// The minimum number of characters in an unchanged prefix or suffix before it is elided.
+const ELIDE_THRESHOLD: usize = 40;
+
+/// The number of characters of an elided prefix or suffix to keep next to the changes.
+const ELIDE_CONTEXT: usize = 10;
+
+/// Elide the start of an unchanged prefix if it is long, keeping the last few characters.
+fn elide_start(data: &str) -> std::borrow::Cow<'_, str> {
+	let len = data.chars().count();
+	if len < ELIDE_THRESHOLD {
+		return data.into();
+	}
+	let (start, _) = data.char_indices().nth(len - ELIDE_CONTEXT).unwrap();
+	format!("…{}", &data[start..]).into()
+}
+
+/// Elide the end of an unchanged suffix if it is long, keeping the first few characters.
+fn elide_end(data: &str) -> std::borrow::Cow<'_, str> {
+	if data.chars().count() < ELIDE_THRESHOLD {
+		return data.into();
+	}
+	let (end, _) = data.char_indices().nth(ELIDE_CONTEXT).unwrap();
+	format!("{}…", &data[..end]).into()
+}
+
+/// Check if a character is invisible or easily mistaken for a normal space when printed.
+fn is_invisible(c: char) -> bool {
+	if c == ' ' {
+		return false;
+	}
+	c.is_whitespace() || c.is_control() || matches!(c,
+		'\u{00ad}' | '\u{034f}' | '\u{180e}' | '\u{200b}'..='\u{200f}' | '\u{202a}'..='\u{202e}'
+		| '\u{2060}'..='\u{2064}' | '\u{2066}'..='\u{2069}' | '\u{feff}'
+	)
+}
+
+/// Replace invisible characters with escape sequences, so that differences in them can be seen.
+///
+/// If `trailing` is true, the text is at the end of a line and trailing spaces are escaped too.
+fn escape_invisible(data: &str, trailing: bool) -> std::borrow::Cow<'_, str> {
+	let trailing_spaces = if trailing { data.len() - data.trim_end_matches(' ').len() } else { 0 };
+	let body = &data[..data.len() - trailing_spaces];
+	if trailing_spaces == 0 && !body.chars().any(is_invisible) {
+		return data.into();
+	}
+
+	let mut output = String::with_capacity(data.len() + 16);
+	for c in body.chars() {
+		match c {
+			'\t' => output.push_str("\\t"),
+			'\r' => output.push_str("\\r"),
+			'\n' => output.push_str("\\n"),
+			c if is_invisible(c) => write!(output, "\\u{{{:x}}}", c as u32).unwrap(),
+			c => output.push(c),
+		}
+	}
+	for _ in 0..trailing_spaces {
+		output.push_str("\\u{20}");
+	}
+	output.into()
+}
+
+/// Run a write function, removing color codes from the output if `color` is false.
+fn write_maybe_plain(buffer: &mut String, color: bool, write: impl FnOnce(&mut String)) {
+	if color {
+		write(buffer);
+	} else {
+		let mut colored = String::new();
+		write(&mut colored);
+		buffer.push_str(&crate::failure::strip_ansi(&colored));
+	}
+}
+
+pub(crate) fn div_ceil(a: usize, b: usize) -> usize {
+	let d = a / b;
+	let r = a % b;
+	if r > 0 {
+		d + 1
+	} else {
+		d
+	}
+}
+
+#[test]
+fn test_div_ceil() {
+	use crate::assert;
+	assert!(div_ceil(0, 2) == 0);
+	assert!(div_ceil(1, 2) == 1);
+	assert!(div_ceil(2, 2) == 1);
+	assert!(div_ceil(3, 2) == 2);
+	assert!(div_ceil(4, 2) == 2);
+
+	assert!(div_ceil(20, 7) == 3);
+	assert!(div_ceil(21, 7) == 3);
+	assert!(div_ceil(22, 7) == 4);
+	assert!(div_ceil(27, 7) == 4);
+	assert!(div_ceil(28, 7) == 4);
+	assert!(div_ceil(29, 7) == 5);
+}
+
+#[test]
+fn test_elide_common_affixes() {
+	use crate::assert;
+	let diff = |left: &str, right: &str| {
+		let diff = SingleLineDiff::new(left, right).with_color(false);
+		let mut left = String::new();
+		let mut right = String::new();
+		diff.write_left(&mut left);
+		diff.write_right(&mut right);
+		(left, right)
+	};
+
+	let prefix = "/home/user/projects/some-long-project-name/target/";
+	let suffix = "/with/a/very/long/suffix/that/is/shared/by/both/sides";
+	let (left, right) = diff(&format!("{prefix}debug{suffix}"), &format!("{prefix}release{suffix}"));
+	assert!(left == "…me/target/debug/with/a/ve…");
+	assert!(right == "…me/target/release/with/a/ve…");
+
+	// Short prefixes and suffixes are not elided.
+	let (left, right) = diff("\"Hello world!\"", "\"Hello moon!\"");
+	assert!(left == "\"Hello world!\"");
+	assert!(right == "\"Hello moon!\"");
+}
+
+#[test]
+fn test_fold_identical_lines() {
+	use crate::assert;
+	let diff = |left: &str, right: &str, context: Option<usize>| {
+		let mut buffer = String::new();
+		MultiLineDiff::new(left, right)
+			.with_color(false)
+			.with_context(context)
+			.write_interleaved(&mut buffer);
+		buffer
+	};
+
+	let left = (0..20).map(|i| i.to_string()).collect::<Vec<_>>().join("\n");
+	let right = left.replace("\n10\n", "\nten\n");
+	assert!(diff(&left, &right, Some(2)) == "  … 8 identical lines …\n  8\n  9\n< 10\n> ten\n  11\n  12\n  … 7 identical lines …");
+	assert!(diff(&left, &right, Some(0)) == "  … 10 identical lines …\n< 10\n> ten\n  … 9 identical lines …");
+	assert!(diff(&left, &right, None).lines().count() == 21);
+
+	// A single line is not folded, since the marker would take up a line too.
+	assert!(diff("a\nb\nc\nd", "a\nb\nc\nx", Some(2)) == "  a\n  b\n  c\n< d\n> x");
+	assert!(diff("a\nb\nc\nd", "a\nb\nc\nx", Some(1)) == "  … 2 identical lines …\n  c\n< d\n> x");
+
+	// Identical inputs are never folded.
+	assert!(diff(&left, &left, Some(2)).lines().count() == 20);
+}
+
+#[test]
+fn test_split_words() {
+	use crate::assert;
+	assert!(SingleLineDiff::split_words("src/foo/bar.rs") == ["src", "/", "foo", "/", "bar", ".", "rs"]);
+	assert!(SingleLineDiff::split_words("std::fmt::Debug") == ["std", "::", "fmt", "::", "Debug"]);
+	assert!(SingleLineDiff::split_words("https://example.com/a-b") == ["https", ":", "/", "/", "example", ".", "com", "/", "a", "-", "b"]);
+	assert!(SingleLineDiff::split_words("parseHTTPServer2") == ["parse", "HTTP", "Server", "2"]);
+	assert!(SingleLineDiff::split_words("HTTP  ok") == ["HTTP", "  ", "ok"]);
+}
+
+#[test]
+fn test_escape_invisible_characters() {
+	use crate::assert;
+	let diff = |left: &str, right: &str| {
+		let diff = SingleLineDiff::new(left, right).with_color(false);
+		let mut left = String::new();
+		let mut right = String::new();
+		diff.write_left(&mut left);
+		diff.write_right(&mut right);
+		(left, right)
+	};
+	assert!(diff("Hello\u{a0}world", "Hello world") == ("Hello\\u{a0}world".into(), "Hello world".into()));
+	assert!(diff("a\tb", "a b") == ("a\\tb".into(), "a b".into()));
+	assert!(diff("line\r", "line") == ("line\\r".into(), "line".into()));
+	assert!(diff("zero\u{200d}width", "zerowidth") == ("zero\\u{200d}width".into(), "zerowidth".into()));
+	assert!(diff("trailing  ", "trailing") == ("trailing\\u{20}\\u{20}".into(), "trailing".into()));
+
+	// Unchanged parts are printed as they are.
+	assert!(diff("a\u{a0}b c", "a\u{a0}b d") == ("a\u{a0}b c".into(), "a\u{a0}b d".into()));
+
+	let mut buffer = String::new();
+	MultiLineDiff::new("a\nb \nc", "a\nc\nd\t")
+		.with_color(false)
+		.with_context(None)
+		.write_interleaved(&mut buffer);
+	assert!(buffer == "  a\n< b\\u{20}\n  c\n> d\\t");
+}
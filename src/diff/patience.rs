@@ -0,0 +1,166 @@
+use diff::Result;
+use std::collections::HashMap;
+
+/// Compute the diff between the lines of two strings using the patience diff algorithm.
+///
+/// The algorithm first matches the lines that occur exactly once on both sides,
+/// and then recursively diffs the regions between those lines.
+/// Regions without such unique lines are diffed with the normal longest common subsequence algorithm.
+///
+/// The lines are split in the same way as by [`diff::lines()`].
+pub(super) fn lines<'a>(left: &'a str, right: &'a str) -> Vec<Result<&'a str>> {
+	let left = split_lines(left);
+	let right = split_lines(right);
+	let mut output = Vec::with_capacity(left.len().max(right.len()));
+	diff_range(&left, &right, &mut output);
+	output
+}
+
+/// Split text into lines, with an empty last line if the text ends with a line break.
+fn split_lines(text: &str) -> Vec<&str> {
+	let mut lines: Vec<_> = text.lines().collect();
+	if text.ends_with('\n') {
+		lines.push("");
+	}
+	lines
+}
+
+/// Diff a region of both inputs and add the results to `output`.
+fn diff_range<'a>(left: &[&'a str], right: &[&'a str], output: &mut Vec<Result<&'a str>>) {
+	let prefix = left.iter().zip(right).take_while(|(left, right)| left == right).count();
+	output.extend(left[..prefix].iter().zip(&right[..prefix]).map(|(left, right)| Result::Both(*left, *right)));
+	let (left, right) = (&left[prefix..], &right[prefix..]);
+
+	let suffix = left.iter().rev().zip(right.iter().rev()).take_while(|(left, right)| left == right).count();
+	let (left, left_suffix) = left.split_at(left.len() - suffix);
+	let (right, right_suffix) = right.split_at(right.len() - suffix);
+
+	let anchors = unique_anchors(left, right);
+	if anchors.is_empty() {
+		output.extend(diff::slice(left, right).into_iter().map(|item| match item {
+			Result::Left(left) => Result::Left(*left),
+			Result::Both(left, right) => Result::Both(*left, *right),
+			Result::Right(right) => Result::Right(*right),
+		}));
+	} else {
+		let (mut left_start, mut right_start) = (0, 0);
+		for (left_index, right_index) in anchors {
+			diff_range(&left[left_start..left_index], &right[right_start..right_index], output);
+			output.push(Result::Both(left[left_index], right[right_index]));
+			left_start = left_index + 1;
+			right_start = right_index + 1;
+		}
+		diff_range(&left[left_start..], &right[right_start..], output);
+	}
+
+	output.extend(left_suffix.iter().zip(right_suffix).map(|(left, right)| Result::Both(*left, *right)));
+}
+
+/// Find the longest sequence of lines that occur exactly once on both sides, in the same order on both sides.
+///
+/// Returns the indices of the lines in the left and right input.
+fn unique_anchors(left: &[&str], right: &[&str]) -> Vec<(usize, usize)> {
+	#[derive(Default)]
+	struct Occurrences {
+		left_count: usize,
+		left_index: usize,
+		right_count: usize,
+		right_index: usize,
+	}
+
+	let mut occurrences: HashMap<&str, Occurrences> = HashMap::new();
+	for (i, line) in left.iter().enumerate() {
+		let entry = occurrences.entry(line).or_default();
+		entry.left_count += 1;
+		entry.left_index = i;
+	}
+	for (i, line) in right.iter().enumerate() {
+		let entry = occurrences.entry(line).or_default();
+		entry.right_count += 1;
+		entry.right_index = i;
+	}
+
+	let mut unique: Vec<(usize, usize)> = occurrences.values()
+		.filter(|x| x.left_count == 1 && x.right_count == 1)
+		.map(|x| (x.left_index, x.right_index))
+		.collect();
+	unique.sort_unstable();
+	longest_increasing_subsequence(&unique)
+}
+
+/// Find the longest subsequence of pairs that is increasing in the second element.
+///
+/// The pairs must already be sorted by the first element.
+fn longest_increasing_subsequence(pairs: &[(usize, usize)]) -> Vec<(usize, usize)> {
+	// The index in `pairs` of the last element of the best subsequence of each length.
+	let mut tails: Vec<usize> = Vec::new();
+	// The index in `pairs` of the previous element in the best subsequence ending at each pair.
+	let mut previous: Vec<Option<usize>> = Vec::with_capacity(pairs.len());
+
+	for (i, &(_, right)) in pairs.iter().enumerate() {
+		let len = tails.partition_point(|&tail| pairs[tail].1 < right);
+		previous.push(len.checked_sub(1).map(|len| tails[len]));
+		if len == tails.len() {
+			tails.push(i);
+		} else {
+			tails[len] = i;
+		}
+	}
+
+	let mut output = Vec::with_capacity(tails.len());
+	let mut current = tails.last().copied();
+	while let Some(i) = current {
+		output.push(pairs[i]);
+		current = previous[i];
+	}
+	output.reverse();
+	output
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::assert;
+
+	fn render(diff: &[Result<&str>]) -> Vec<String> {
+		diff.iter().map(|item| match item {
+			Result::Left(left) => format!("-{left}"),
+			Result::Both(left, _) => format!(" {left}"),
+			Result::Right(right) => format!("+{right}"),
+		}).collect()
+	}
+
+	#[test]
+	fn test_longest_increasing_subsequence() {
+		assert!(longest_increasing_subsequence(&[]).is_empty());
+		assert!(longest_increasing_subsequence(&[(0, 3), (1, 1), (2, 2), (3, 0), (4, 4)]) == [(1, 1), (2, 2), (4, 4)]);
+	}
+
+	#[test]
+	fn test_patience_lines() {
+		assert!(render(&lines("a\nb\nc", "a\nb\nc")) == [" a", " b", " c"]);
+		assert!(render(&lines("a\nb\n", "a\nc")) == [" a", "-b", "-", "+c"]);
+
+		// The unique lines are matched first, so the braces are not matched with the wrong block.
+		let left = "fn a() {\n    1\n}\nfn b() {\n    2\n}";
+		let right = "fn b() {\n    2\n}\nfn a() {\n    1\n}";
+		let diff = render(&lines(left, right));
+		assert!(diff.iter().filter(|line| line.starts_with(' ')).count() == 3);
+		assert!(diff.iter().filter(|line| line.starts_with('-')).count() == 3);
+	}
+
+	#[test]
+	fn test_patience_matches_lcs_result() {
+		let left = "a\nb\nc\nd\ne\nb\nf";
+		let right = "a\nx\nc\nd\nb\nf\ng";
+		let rebuild = |diff: &[Result<&str>], keep_left: bool| -> Vec<String> {
+			diff.iter().filter_map(|item| match (item, keep_left) {
+				(Result::Left(x), true) | (Result::Right(x), false) | (Result::Both(x, _), _) => Some(x.to_string()),
+				_ => None,
+			}).collect()
+		};
+		let diff = lines(left, right);
+		assert!(rebuild(&diff, true).join("\n") == left);
+		assert!(rebuild(&diff, false).join("\n") == right);
+	}
+}
@@ -0,0 +1,142 @@
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+
+/// Helper trait to show trait objects in assertion failures.
+///
+/// This trait is implemented for all types that implement `Debug`.
+/// Add it as a supertrait to your own trait,
+/// and use [`impl_assert_dyn!()`](macro.impl_assert_dyn.html) to implement `Debug` (and optionally `PartialEq`) for the trait object.
+///
+/// The `Debug` implementation shows the concrete type name of the object, followed by the `Debug` output of the concrete type.
+///
+/// ```
+/// # use assert2::{check, impl_assert_dyn, AssertDyn, DynPartialEq};
+/// trait Plugin: AssertDyn + DynPartialEq {
+///     fn name(&self) -> &str;
+/// }
+/// impl_assert_dyn!(dyn Plugin, PartialEq);
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Echo;
+///
+/// impl Plugin for Echo {
+///     fn name(&self) -> &str {
+///         "echo"
+///     }
+/// }
+///
+/// let a: Box<dyn Plugin> = Box::new(Echo);
+/// let b: Box<dyn Plugin> = Box::new(Echo);
+/// check!(a == b);
+/// ```
+pub trait AssertDyn: Any {
+	/// Get the type name of the concrete type.
+	fn concrete_type_name(&self) -> &'static str;
+
+	/// Format the value using the `Debug` implementation of the concrete type.
+	fn concrete_debug(&self, f: &mut Formatter) -> std::fmt::Result;
+
+	/// Get the value as `&dyn Any`.
+	fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + Debug> AssertDyn for T {
+	fn concrete_type_name(&self) -> &'static str {
+		std::any::type_name::<T>()
+	}
+
+	fn concrete_debug(&self, f: &mut Formatter) -> std::fmt::Result {
+		Debug::fmt(self, f)
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+/// Helper trait to compare trait objects using the `PartialEq` implementation of the concrete type.
+///
+/// This trait is implemented for all types that implement `PartialEq`.
+/// Objects of different concrete types always compare as unequal.
+///
+/// Add this trait as a supertrait to your own trait and use `impl_assert_dyn!(dyn YourTrait, PartialEq)`
+/// to implement `PartialEq` for the trait object.
+pub trait DynPartialEq: Any {
+	/// Compare with another value using the `PartialEq` implementation of the concrete type.
+	fn dyn_eq(&self, other: &dyn Any) -> bool;
+}
+
+impl<T: Any + PartialEq> DynPartialEq for T {
+	fn dyn_eq(&self, other: &dyn Any) -> bool {
+		other.downcast_ref::<T>() == Some(self)
+	}
+}
+
+/// Implement `Debug` and optionally `PartialEq` for a trait object.
+///
+/// The trait must have [`AssertDyn`] as supertrait.
+/// The generated `Debug` implementation prints the concrete type name of the object
+/// and the `Debug` output of the concrete type.
+///
+/// To also implement `PartialEq`, you can pass `PartialEq` to use the implementation of the concrete types.
+/// In that case, the trait must also have [`DynPartialEq`] as supertrait.
+/// Alternatively, you can pass `PartialEq = comparator` to use your own comparison function.
+/// The comparator is called with two references to the trait object.
+///
+/// ```
+/// # use assert2::{assert, impl_assert_dyn, AssertDyn};
+/// trait Shape: AssertDyn {
+///     fn area(&self) -> f64;
+/// }
+/// impl_assert_dyn!(dyn Shape, PartialEq = |a, b| a.area() == b.area());
+///
+/// #[derive(Debug)]
+/// struct Square(f64);
+///
+/// #[derive(Debug)]
+/// struct Rectangle(f64, f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// impl Shape for Rectangle {
+///     fn area(&self) -> f64 {
+///         self.0 * self.1
+///     }
+/// }
+///
+/// let a: &dyn Shape = &Square(2.0);
+/// let b: &dyn Shape = &Rectangle(1.0, 4.0);
+/// assert!(a == b);
+/// ```
+#[macro_export]
+macro_rules! impl_assert_dyn {
+	(dyn $trait:path) => {
+		impl ::core::fmt::Debug for dyn $trait {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				::core::write!(f, "<{}> ", $crate::AssertDyn::concrete_type_name(self))?;
+				$crate::AssertDyn::concrete_debug(self, f)
+			}
+		}
+	};
+	(dyn $trait:path, PartialEq $(,)?) => {
+		$crate::impl_assert_dyn!(dyn $trait);
+		impl ::core::cmp::PartialEq for dyn $trait {
+			fn eq(&self, other: &Self) -> bool {
+				$crate::DynPartialEq::dyn_eq(self, $crate::AssertDyn::as_any(other))
+			}
+		}
+	};
+	(dyn $trait:path, PartialEq = $compare:expr $(,)?) => {
+		$crate::impl_assert_dyn!(dyn $trait);
+		impl ::core::cmp::PartialEq for dyn $trait {
+			fn eq(&self, other: &Self) -> bool {
+				let compare: fn(&Self, &Self) -> bool = $compare;
+				compare(self, other)
+			}
+		}
+	};
+}
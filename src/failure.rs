@@ -0,0 +1,125 @@
+/// A failed assertion.
+///
+/// This is returned by [`try_check!()`](macro.try_check.html) and [`try_assert!()`](macro.try_assert.html).
+/// The [`Display`](std::fmt::Display) implementation prints the full report without color codes,
+/// exactly as it would have been printed by [`check!()`](macro.check.html).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Failure {
+	/// Boxed to keep `Result<(), Failure>` small.
+	inner: Box<Inner>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Inner {
+	macro_name: String,
+	file: String,
+	line: u32,
+	column: u32,
+	expression: String,
+	message: Option<String>,
+	report: String,
+}
+
+impl Failure {
+	/// Create a new failure from rendered parts, stripping any color codes.
+	pub(crate) fn new(
+		macro_name: &str,
+		file: &str,
+		line: u32,
+		column: u32,
+		expression: &str,
+		message: Option<String>,
+		report: &str,
+	) -> Self {
+		let inner = Inner {
+			macro_name: macro_name.to_owned(),
+			file: file.to_owned(),
+			line,
+			column,
+			expression: strip_ansi(expression),
+			message,
+			report: strip_ansi(report),
+		};
+		Self { inner: Box::new(inner) }
+	}
+
+	/// The name of the macro that failed, without the exclamation mark.
+	pub fn macro_name(&self) -> &str {
+		&self.inner.macro_name
+	}
+
+	/// The file that contains the failed assertion.
+	pub fn file(&self) -> &str {
+		&self.inner.file
+	}
+
+	/// The line number of the failed assertion.
+	pub fn line(&self) -> u32 {
+		self.inner.line
+	}
+
+	/// The column number of the failed assertion.
+	pub fn column(&self) -> u32 {
+		self.inner.column
+	}
+
+	/// The asserted expression as written in the source code.
+	pub fn expression(&self) -> &str {
+		&self.inner.expression
+	}
+
+	/// The custom message of the assertion, if any.
+	pub fn message(&self) -> Option<&str> {
+		self.inner.message.as_deref()
+	}
+
+	/// The full report of the failure, without color codes.
+	pub fn report(&self) -> &str {
+		&self.inner.report
+	}
+}
+
+impl std::fmt::Display for Failure {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.inner.report.trim_end())
+	}
+}
+
+impl std::error::Error for Failure {}
+
+/// Remove ANSI escape sequences from a string.
+pub(crate) fn strip_ansi(input: &str) -> String {
+	let mut output = String::with_capacity(input.len());
+	let mut chars = input.chars().peekable();
+	while let Some(c) = chars.next() {
+		if c == '\x1b' && chars.peek() == Some(&'[') {
+			chars.next();
+			// Skip parameters and intermediate bytes up to and including the final byte.
+			for c in chars.by_ref() {
+				if ('\x40'..='\x7e').contains(&c) {
+					break;
+				}
+			}
+		} else if c == '\x1b' && chars.peek() == Some(&']') {
+			// Skip operating system commands, like hyperlinks, up to the BEL or ST terminator.
+			while let Some(c) = chars.next() {
+				if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+					break;
+				}
+			}
+		} else {
+			output.push(c);
+		}
+	}
+	output
+}
+
+#[test]
+fn test_strip_ansi() {
+	use crate::assert;
+	assert!(strip_ansi("plain") == "plain");
+	assert!(strip_ansi("\x1b[1;31mred\x1b[0m text") == "red text");
+	assert!(strip_ansi("\x1b[38;5;12mx\x1b[0m\x1b[m") == "x");
+	assert!(strip_ansi("\x1b]8;;file:///a.rs\x1b\\a.rs:1:2\x1b]8;;\x1b\\:") == "a.rs:1:2:");
+	assert!(strip_ansi("\x1b]8;;file:///a.rs\x07link\x1b]8;;\x07") == "link");
+}
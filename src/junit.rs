@@ -0,0 +1,97 @@
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// A failed assertion recorded for the JUnit report.
+struct Record {
+	/// The name of the thread that ran the assertion, which is the name of the test for the standard test harness.
+	thread: String,
+
+	/// The failed assertion.
+	failure: crate::Failure,
+}
+
+static RECORDS: Mutex<Vec<Record>> = Mutex::new(Vec::new());
+
+/// The environment variable with the path of the JUnit report.
+const PATH_VAR: &str = "ASSERT2_JUNIT";
+
+/// Record a failed assertion and write the JUnit report, if the `ASSERT2_JUNIT` environment variable is set.
+///
+/// The report is rewritten with all failures so far after every failure,
+/// so that it is complete even if the process is aborted by a panic.
+pub(crate) fn record(failure: &crate::Failure) {
+	let Some(path) = std::env::var_os(PATH_VAR) else {
+		return;
+	};
+	let path = path.to_string_lossy().replace("{pid}", &std::process::id().to_string());
+	let thread = std::thread::current().name().unwrap_or("main").to_owned();
+
+	let mut records = RECORDS.lock().unwrap_or_else(|e| e.into_inner());
+	records.push(Record { thread, failure: failure.clone() });
+	if let Err(e) = std::fs::write(&path, render(&records)) {
+		eprintln!("assert2: failed to write JUnit report to {path}: {e}");
+	}
+}
+
+/// Render the JUnit XML report for the recorded failures.
+fn render(records: &[Record]) -> String {
+	let mut output = String::new();
+	writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+	writeln!(output, r#"<testsuites name="assert2" tests="{0}" failures="{0}">"#, records.len()).unwrap();
+	writeln!(output, r#"  <testsuite name="assert2" tests="{0}" failures="{0}">"#, records.len()).unwrap();
+	for Record { thread, failure } in records {
+		let name = format!("{}!( {} ) at {}:{}:{}", failure.macro_name(), failure.expression(), failure.file(), failure.line(), failure.column());
+		let message = failure.message().unwrap_or("assertion failed");
+		writeln!(output, r#"    <testcase classname="{}" name="{}" file="{}" line="{}">"#,
+			escape(thread),
+			escape(&name),
+			escape(failure.file()),
+			failure.line(),
+		).unwrap();
+		writeln!(output, r#"      <failure message="{}" type="{}">{}</failure>"#,
+			escape(message),
+			escape(failure.macro_name()),
+			escape(failure.report().trim_end()),
+		).unwrap();
+		writeln!(output, "    </testcase>").unwrap();
+	}
+	writeln!(output, "  </testsuite>").unwrap();
+	writeln!(output, "</testsuites>").unwrap();
+	output
+}
+
+/// Escape text for use in XML attributes and text, dropping characters that XML does not allow.
+fn escape(text: &str) -> String {
+	let mut output = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'&' => output.push_str("&amp;"),
+			'<' => output.push_str("&lt;"),
+			'>' => output.push_str("&gt;"),
+			'"' => output.push_str("&quot;"),
+			'\'' => output.push_str("&apos;"),
+			'\n' => output.push_str("&#10;"),
+			'\t' => output.push_str("&#9;"),
+			c if c.is_control() => (),
+			c => output.push(c),
+		}
+	}
+	output
+}
+
+#[test]
+fn test_render_junit() {
+	use crate::assert;
+	let failure = crate::Failure::new("check", "src/lib.rs", 12, 3, "a < b", None, "Assertion failed at src/lib.rs:12:3:\n  check!( a < b )\n\n");
+	let records = [Record { thread: "tests::order".into(), failure }];
+	assert!(render(&records) == concat!(
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+		"<testsuites name=\"assert2\" tests=\"1\" failures=\"1\">\n",
+		"  <testsuite name=\"assert2\" tests=\"1\" failures=\"1\">\n",
+		"    <testcase classname=\"tests::order\" name=\"check!( a &lt; b ) at src/lib.rs:12:3\" file=\"src/lib.rs\" line=\"12\">\n",
+		"      <failure message=\"assertion failed\" type=\"check\">Assertion failed at src/lib.rs:12:3:&#10;  check!( a &lt; b )</failure>\n",
+		"    </testcase>\n",
+		"  </testsuite>\n",
+		"</testsuites>\n",
+	));
+}
@@ -105,6 +105,12 @@
 //! Ideally, `check` doesn't panic at all, but only signals that a test case has failed.
 //! If this becomes possible in the future, the `check` macro will change, so **you should not rely on `check` to panic**.
 //!
+//! The panic message names the failed assertion and its location, like `assertion failed: assert!( 1 + 1 == 3 ) at src/main.rs:2:5`,
+//! for test runners and IDEs that only show the panic message.
+//!
+//! To control when the failures of checks are collected and reported, use a [`CheckContext`] with [`check_ctx!()`].
+//! The context panics once when it is dropped, so a check in a loop does not stop the loop after the first failure.
+//!
 //! # Difference between stable and nightly.
 //! If available, the crate uses the `proc_macro_span` feature to get the original source code.
 //! On stable and beta, it falls back to stringifying the expression.
@@ -161,6 +167,24 @@
 //! # }
 //! ```
 //!
+//! If you only need references to the placeholders, `assert!(let ...)` itself also evaluates to them:
+//! `let (a, b) = assert!(let Some((a, b)) = &value);`.
+//!
+//! # Assertion helpers
+//!
+//! Failed assertions report the location of the macro.
+//! If you wrap assertions in a helper function that is shared by many tests,
+//! mark the helper with `#[track_caller]` to report the location of the caller instead, just like for panics:
+//! ```
+//! # use assert2::check;
+//! #[track_caller]
+//! fn check_sorted(values: &[i32]) {
+//!     check!(values.windows(2).all(|x| x[0] <= x[1]), "values are not sorted: {:?}", values);
+//! }
+//!
+//! check_sorted(&[1, 2, 3]);
+//! ```
+//!
 //! # Controlling the output format.
 //!
 //! As an end-user, you can influence the way that `assert2` formats failed assertions by changing the `ASSERT2` environment variable.
@@ -171,6 +195,58 @@
 //! * `compact`: Always use the compact `Debug` format for assertion messages (`{:?}`).
 //! * `no-color`: Disable colored output, even when the output is going to a terminal.
 //! * `color`: Enable colored output, even when the output is not going to a terminal.
+//! * `count`: Print how many times the assertion passed before it failed, which helps to see how deep into a long running loop or soak test a failure occurred.
+//! * `no-count`: Do not print how many times the assertion passed (default).
+//! * `display`: Print values that implement both `Debug` and `Display` with their `Display` implementation.
+//! * `debug`: Print values with their `Debug` implementation if they have one (default).
+//! * `max-len=N`: Truncate printed values that are longer than `N` bytes, keeping the start and the end and noting how many bytes were left out.
+//! * `no-max-len`: Do not truncate printed values (default).
+//! * `diff-context=N`: Show `N` identical lines around each change in a multi-line diff, and fold longer runs of identical lines (default: 3).
+//! * `diff-context=all`: Show all lines of a multi-line diff.
+//! * `diff=lcs`: Diff multi-line values by their longest common subsequence of lines (default).
+//! * `diff=patience`: Diff multi-line values with the patience algorithm, which keeps reordered blocks together.
+//! * `types`: Print the type of each operand of a comparison, which helps with generics, references and numeric literals.
+//! * `no-types`: Do not print the types of the operands (default).
+//! * `hex`: Print integer operands of comparisons in hexadecimal, padded to the width of their type.
+//! * `binary`: Print integer operands of comparisons in binary, padded to the width of their type.
+//! * `decimal`: Print integer operands of comparisons in decimal (default).
+//! * `palette=16`, `palette=256`, `palette=truecolor`: Set the colors supported by the terminal.
+//!   By default, this is detected from the `COLORTERM` and `TERM` environment variables.
+//!   With 256 colors or true colors, differences are highlighted with a soft background color instead of inverted colors.
+//! * `theme=colorblind`: Use blue and orange for the left and right side of comparisons, and underline highlighted differences.
+//! * `theme=default`: Use the default colors (default).
+//! * `width=N`: Lay out the output for a terminal that is `N` columns wide, instead of using the `COLUMNS` environment variable.
+//! * `width=auto`: Use the `COLUMNS` environment variable for the width of the terminal (default).
+//! * `report=tap`: Print failed assertions as `not ok` lines of the [Test Anything Protocol](https://testanything.org), with the details in a YAML diagnostics block.
+//! * `report=human`: Print failed assertions in the default format for humans (default).
+//! * `output=stdout`: Print failed assertions to `stdout`, so the test harness shows them with the other output of the failing test.
+//! * `output=stderr`: Print failed assertions to `stderr` (default).
+//! * `summary`: Print a table with the location and number of failures of every failed assertion when the process exits.
+//! * `no-summary`: Do not print a summary when the process exits (default).
+//! * `dedup`: Print identical failures of the same assertion only once, followed by the number of repeats (default).
+//! * `no-dedup`: Print every failure, even if it is identical to the previous failure of the same assertion.
+//! * `backtrace`: Print a backtrace of the user code under each failure, to see where a shared test helper was called from.
+//! * `no-backtrace`: Do not print a backtrace (default).
+//! * `source`: Print the source lines around the failed assertion, if the source file can be found.
+//!   The failed expression is underlined if it is found in the source line, which is always the case on nightly.
+//!   Otherwise, a caret points at the start of the assertion.
+//! * `no-source`: Do not print the source lines (default).
+//! * `text`: Print string operands of comparisons that contain line breaks as raw text with line numbers,
+//!   instead of a single line full of `\n` escapes. If both sides are strings, `==` prints a line diff of the raw text.
+//! * `no-text`: Print multi-line strings with their `Debug` implementation, like other values (default).
+//! * `sort`: Sort the entries of sets and maps in the output, like the ones of a `HashMap` or `HashSet`,
+//!   so that differences in iteration order do not show up in the diff. Entries are sorted by their `Debug` output.
+//! * `no-sort`: Print sets and maps in their iteration order (default).
+//! * `layout=aligned`: Print the compact expansion of a comparison on two lines, after aligned `left:` and `right:` labels.
+//!   This is easier to read than the inline layout once the values get longer.
+//! * `layout=inline`: Print the compact expansion of a comparison on one line, as `left == right` (default).
+//! * `links`: Print the location of a failed assertion as a hyperlink to the source file, using OSC 8 escape codes.
+//!   By default, this is enabled if the output goes to a terminal that is known to support hyperlinks.
+//!   The link target can be changed with the `ASSERT2_LINK` environment variable, which holds a URL template.
+//!   In the template, `{path}` is replaced by the absolute path of the source file, and `{line}` and `{column}` by the location.
+//!   For example, `ASSERT2_LINK='vscode://file{path}:{line}:{column}'` opens the assertion in Visual Studio Code.
+//!   The default template is `file://{path}`.
+//! * `no-links`: Print the location of a failed assertion as plain text.
 //!
 //! For example, you can run the following command to force the use of the compact `Debug` format with colored output:
 //! ```shell
@@ -183,20 +259,204 @@
 //!  * `NO_COLOR != 0` or `CLICOLOR == 0`: Write plain output without color codes.
 //!  * `CLICOLOR != 0`: Write colored output when the output is going to a terminal.
 //!  * `CLICOLOR_FORCE != 0`:  Write colored output even when it is not going to a terminal.
+//!
+//! Output to a terminal with `TERM=dumb` is treated like output that is not going to a terminal.
+//!
+//! ## Color themes
+//!
+//! The colors of the output can be changed with the `ASSERT2_THEME` environment variable,
+//! for example `ASSERT2_THEME=left=magenta,right=green`, or with [`set_theme()`].
+//! See [`Theme`] for the supported roles and colors.
+//!
+//! ## Crate defaults
+//!
+//! You can also set default options for all assertions in a crate at build time with the `ASSERT2_DEFAULTS` environment variable.
+//! It uses the same syntax as the `ASSERT2` environment variable, and it must be set when the crate containing the assertions is compiled.
+//! For example, in `.cargo/config.toml`:
+//! ```toml
+//! [env]
+//! ASSERT2_DEFAULTS = "pretty"
+//! ```
+//!
+//! Or from a build script:
+//! ```no_run
+//! println!("cargo:rustc-env=ASSERT2_DEFAULTS=pretty");
+//! ```
+//!
+//! The `ASSERT2` environment variable at runtime still overrides the crate defaults.
+//! The crate defaults for colored output also do not override the clicolors environment variables.
+//!
+//! ## Deterministic output
+//!
+//! If you snapshot the output of failed assertions in your own tests, you can remove the dependency on the environment with a few functions:
+//! * [`set_color_override()`] forces colored output on or off, regardless of environment variables.
+//! * [`set_terminal_size_provider()`] controls the terminal width used to lay out the output.
+//! * [`set_time_source()`] replaces the clock used by time based assertions like [`assert_completes_within!()`].
+//!
+//! ## Redacting sensitive data
+//!
+//! Failed assertions print the values involved, which can include secrets like access tokens.
+//! To keep them out of CI logs, register a redactor with [`add_redactor()`] or [`redact_after()`]:
+//! ```
+//! assert2::redact_after("Bearer ");
+//! assert2::add_redactor(|text| text.replace("hunter2", "*******"));
+//! # assert2::clear_redactors();
+//! ```
+//!
+//! ## JUnit reports
+//!
+//! CI systems usually only show that a test panicked, not which assertions failed.
+//! If the `ASSERT2_JUNIT` environment variable is set to a path, every failed [`check!()`] and [`assert!()`] is also recorded in a JUnit XML report at that path.
+//! Each failure is a separate test case, named after the assertion and grouped by the name of the test that ran it.
+//! The report is rewritten after every failure, so it is complete even when the process is aborted.
+//!
+//! Each test binary is a separate process that overwrites the report.
+//! Use `{pid}` in the path to write a separate report for each process, for example `ASSERT2_JUNIT=target/assert2-{pid}.xml`.
+//!
+//! ## SARIF logs
+//!
+//! Code scanning dashboards, like the one on GitHub, can annotate the source code with the results in a SARIF log.
+//! If the `ASSERT2_SARIF` environment variable is set to a path, every failed assertion is recorded in a SARIF log at that path,
+//! with the location of the assertion and the full report as message.
+//! Warnings from [`check!()`] with `level = warn` are recorded with the `warning` level.
+//! As with `ASSERT2_JUNIT`, the log is rewritten after every failure and `{pid}` in the path is replaced by the process ID.
+//!
+//! ## Android
+//!
+//! Output written to `stderr` is not visible on Android devices.
+//! With the `android-log` feature, the reports of failed assertions are written to logcat with the tag `assert2` instead.
+//! On other platforms, the feature has no effect.
 
 #[doc(hidden)]
 pub mod __assert2_impl;
 
+mod assert_format;
+pub use assert_format::AssertFormat;
+
+mod check_context;
+pub use check_context::CheckContext;
+
+mod checkpoint;
+pub use checkpoint::{Checkpoint, InspectCheckpoint};
+
+mod clock;
+pub use clock::{set_time_source, TimeSource};
+
+mod color;
+pub use color::set_color_override;
+
+mod context;
+pub use context::{clear_run_context, remove_run_context, set_run_context};
+
+pub mod diff;
+
+mod dyn_trait;
+pub use dyn_trait::{AssertDyn, DynPartialEq};
+
+mod failure;
+pub use failure::Failure;
+
+pub mod matchers;
+
+mod junit;
+
+mod output;
+pub use output::{buffer_output, set_failure_handler, silence, FailureHandler, OutputBuffer};
+
+mod panicking;
+pub use panicking::{set_panicking_policy, suppressed_failures, PanickingPolicy};
+
+mod redact;
+pub use redact::{add_redactor, clear_redactors, redact_after};
+
+mod sarif;
+
+mod structural_diff;
+pub use structural_diff::{FieldDiff, StructuralDiff};
+pub use assert2_macros::AssertDiff;
+
+mod summary;
+pub use summary::print_summary;
+
+mod terminal;
+pub use terminal::{set_terminal_size_provider, TerminalSize, TerminalSizeProvider};
+
+mod theme;
+pub use theme::{set_theme, Theme, ThemeColor};
+
 /// Assert that an expression evaluates to true or matches a pattern.
 ///
 /// Use a `let` expression to test an expression against a pattern: `assert!(let pattern = expr)`.
 /// For other tests, just give a boolean expression to the macro: `assert!(1 + 2 == 2)`.
 ///
+/// You can also combine `let` expressions and boolean expressions with `&&`: `assert!(let Some(x) = expr && x > 2)`.
+/// If a later part of such a chain fails, the values of the variables bound by earlier parts are printed too.
+///
+/// Comparisons can be chained like in mathematical notation: `assert!(0 < x <= 10)`.
+/// All operands are evaluated exactly once, from left to right, and the comparison that failed is printed with the values of all operands.
+///
+/// If all alternatives of `||` are false, like in `assert!(x == 1 || x > 10)`, the expansion of every alternative is printed.
+///
+/// An invocation of [`matches!()`] is checked as if it was a `let` expression, so the value is printed if it does not match the pattern.
+/// If the pattern expects a different enum variant than the value has, like `assert!(let Ok(_) = result)` for an `Err`,
+/// the actual variant is printed on a separate line before the full value.
+/// If the two variant names are similar, like `Connected` and `Disconnected`, a hint points out the difference.
+///
+/// If a side of a comparison is an arithmetic expression, like in `assert!(a + b == c * 2)`,
+/// the variables used in the arithmetic are printed too, as long as they implement `Copy`.
+/// Their values are copied before the comparison is evaluated.
+///
+/// For calls to `is_ok()`, `is_err()`, `is_some()` and `is_none()`, the receiver is printed, so you can see the actual error or value.
+/// For calls to `contains()`, `starts_with()`, `ends_with()` and `eq_ignore_ascii_case()`, the receiver and the argument are printed,
+/// as long as the argument is a reference or a literal, like in `assert!(text.contains("needle"))` or `assert!(list.contains(&item))`.
+///
+/// For a negated comparison or call, like `assert!(!set.contains(&x))`, the operands are printed.
+/// Only the receiver and arguments that are variables, fields or indices of them are printed for a call.
+/// This works for the methods listed above and for function calls where all arguments are references or literals,
+/// so that the operands are not moved by the call.
+///
+/// Values that do not implement `Debug` are printed with their `Display` implementation instead.
+/// If they implement neither, only their type name is printed.
+/// Implement [`AssertFormat`] to take full control over how a type is printed.
+///
+/// The expression may use the `?` operator to return early from the surrounding function, like `check!(parse(input)? == 10)`.
+/// If it does, the check is skipped and nothing is printed.
+///
+/// The expression may also use `.await` in async code.
+/// The macro does not hold anything across await points that would prevent the future from being [`Send`].
+///
+/// An assignment is rejected at compile time, since it is almost certainly a typo for `==`:
+/// ```compile_fail
+/// # use assert2::assert;
+/// let mut x = 1;
+/// assert!(x = 2);
+/// ```
+///
+/// A `let` expression with a pattern that can never fail to match, like `assert!(let x = foo())`, triggers a warning.
+///
 /// If the expression evaluates to false or if the pattern doesn't match,
 /// an assertion failure is printed and the macro panics instantly.
 ///
 /// Use [`check!`](macro.check.html) if you still want further checks to be executed.
 ///
+/// # Using the bindings of a pattern
+/// A single `let` expression evaluates to the variables bound by the pattern.
+/// A single variable is returned as is, multiple variables are returned as a tuple in the order they appear in the pattern.
+/// The expression is matched by reference, so the variables are references into the value.
+///
+/// ```
+/// # use assert2::assert;
+/// let value: Result<(i32, &str), ()> = Ok((10, "foo"));
+/// let (number, name) = assert!(let Ok((number, name)) = &value);
+/// assert!(*number + 1 == 11);
+/// assert!(*name == "foo");
+/// let number = assert!(let Ok((number, _)) = &value);
+/// assert!(*number == 10);
+/// ```
+///
+/// If the pattern binds variables, a failed assertion always panics, even if the thread is already panicking and the configured [`PanickingPolicy`] says otherwise.
+/// To move values out of the expression instead of borrowing them, use [`let_assert!`](macro.let_assert.html).
+///
 /// # Custom messages
 /// You can pass additional arguments to the macro.
 /// These will be used to print a custom message in addition to the normal message.
@@ -205,12 +465,50 @@ pub mod __assert2_impl;
 /// # use assert2::assert;
 /// assert!(3 * 4 == 12, "Oh no, math is broken! 1 + 1 == {}", 1 + 1);
 /// ```
+///
+/// # Custom expansions
+/// For comparisons, you can replace the `Debug` output of an operand by adding `; expand_left = ...` or `; expand_right = ...`
+/// after the expression and custom message.
+/// The value can be anything that implements `Display`, and it is only evaluated if the assertion fails.
+/// This is useful for types with an unhelpful `Debug` implementation that you can not change.
+///
+/// ```
+/// # use assert2::assert;
+/// # let a = std::net::Ipv4Addr::LOCALHOST;
+/// # let b = std::net::Ipv4Addr::LOCALHOST;
+/// assert!(a == b; expand_left = a.to_string(), expand_right = b.to_string());
+/// ```
+///
+/// To print both operands with their `Display` implementation when they have one, add `; format = display`.
+/// This overrides the `display` and `debug` options of the `ASSERT2` environment variable for this assertion,
+/// and `; format = debug` forces the `Debug` output instead.
+///
+/// ```
+/// # use assert2::assert;
+/// # let a = std::net::Ipv4Addr::LOCALHOST;
+/// # let b = std::net::Ipv4Addr::LOCALHOST;
+/// assert!(a == b; format = display);
+/// ```
+///
+/// Integer operands can be printed in hexadecimal or binary with `; format = hex` or `; format = binary`,
+/// which is useful when comparing flags, masks or hashes.
+/// This overrides the `hex`, `binary` and `decimal` options of the `ASSERT2` environment variable for this assertion,
+/// and `; format = decimal` forces the decimal output instead.
+/// For failed `==` and `!=` comparisons of integers, the bits that are set on only one side are printed too.
+///
+/// If both operands are byte buffers, like a `Vec<u8>`, a `&[u8]` or a `[u8; N]`,
+/// they are printed as a side-by-side hexdump followed by their lossy UTF-8 decoding,
+/// unless the format or expansion is overridden.
+///
+/// ```
+/// # use assert2::assert;
+/// # let flags = 0x41u8;
+/// assert!(flags & 0x40 == 0x40; format = hex);
+/// ```
 #[macro_export]
 macro_rules! assert {
 	($($tokens:tt)*) => {
-		if let Err(()) = $crate::__assert2_impl::check_impl!($crate, "assert", $($tokens)*) {
-			panic!("assertion failed");
-		}
+		$crate::__assert2_impl::assert_result($crate::__assert2_impl::check_impl!($crate, "assert", $($tokens)*))
 	}
 }
 
@@ -237,18 +535,107 @@ macro_rules! assert {
 /// # use assert2::check;
 /// check!(3 * 4 == 12, "Oh no, math is broken! 1 + 1 == {}", 1 + 1);
 /// ```
+///
+/// # Severity levels
+/// You can change the severity of a check by adding `; level = ...` after the expression and custom message.
+///
+/// * `warn`: print the failure as a warning, but never fail the test.
+/// * `error`: the default, print the failure and fail the test eventually.
+/// * `fatal`: print the failure and panic immediately, like [`assert!`](macro.assert.html).
+///
+/// ```
+/// # use assert2::check;
+/// check!(1 + 1 == 3, "math is broken"; level = warn);
+/// check!(2 * 2 == 4; level = fatal);
+/// ```
 #[macro_export]
 macro_rules! check {
 	($($tokens:tt)*) => {
 		let _guard = match $crate::__assert2_impl::check_impl!($crate, "check", $($tokens)*) {
 			Ok(_) => None,
 			Err(_) => {
-				Some($crate::__assert2_impl::FailGuard(|| panic!("check failed")))
+				let message = $crate::__assert2_impl::panic_message("check failed");
+				Some($crate::__assert2_impl::FailGuard::new(move || panic!("{}", message)))
 			},
 		};
 	}
 }
 
+/// Check if an expression evaluates to true or matches a pattern, and record a failure in a [`CheckContext`].
+///
+/// The first argument is the context, the remaining arguments are the same as for [`check!`](macro.check.html).
+/// A failure is printed immediately, but the context only panics when it is dropped,
+/// so that a loop can run all iterations and report every failure.
+///
+/// The context can be a local variable or a `&mut CheckContext`, so it can be passed to helper functions.
+/// Severity levels are not supported.
+///
+/// ```should_panic
+/// # use assert2::{check_ctx, CheckContext};
+/// fn check_row(ctx: &mut CheckContext, row: &[i32]) {
+///     check_ctx!(ctx, row.len() == 3, "{:?}", row);
+/// }
+///
+/// let mut ctx = CheckContext::new();
+/// for row in [vec![1, 2, 3], vec![4, 5], vec![6]] {
+///     check_row(&mut ctx, &row);
+/// }
+/// // Panics when `ctx` is dropped, listing both failures.
+/// ```
+#[macro_export]
+macro_rules! check_ctx {
+	($ctx:expr, $($tokens:tt)*) => {
+		if let ::core::result::Result::Err(failure) = $crate::__assert2_impl::check_impl!($crate, "check_ctx", $($tokens)*) {
+			$ctx.add_failure(failure);
+		}
+	}
+}
+
+/// Check if an expression evaluates to true or matches a pattern, and return the result.
+///
+/// This macro supports the same checks as [`check!`](macro.check.html),
+/// but instead of printing the failure and failing the test, it evaluates to a `Result<(), Failure>`.
+/// Nothing is printed, so you can decide yourself what to do with the [`Failure`].
+///
+/// ```
+/// # use assert2::try_check;
+/// let result = try_check!(1 + 1 == 3, "math is {}", "broken");
+/// let failure = result.unwrap_err();
+/// assert!(failure.expression() == "1 + 1 == 3");
+/// assert!(failure.message() == Some("math is broken"));
+/// ```
+#[macro_export]
+macro_rules! try_check {
+	($($tokens:tt)*) => {{
+		$crate::__assert2_try_check_impl!("try_check", $($tokens)*)
+	}}
+}
+
+/// Assert that an expression evaluates to true or matches a pattern, or return early with an error.
+///
+/// This macro supports the same checks as [`assert!`](macro.assert.html),
+/// but instead of panicking, it returns from the enclosing function with an error.
+/// The error is created from a [`Failure`] using [`From::from`], just like the `?` operator does.
+/// Nothing is printed.
+///
+/// ```
+/// # use assert2::{try_assert, Failure};
+/// fn check_answer(answer: i32) -> Result<(), Failure> {
+///     try_assert!(answer == 42);
+///     Ok(())
+/// }
+/// assert!(check_answer(42).is_ok());
+/// assert!(check_answer(41).is_err());
+/// ```
+#[macro_export]
+macro_rules! try_assert {
+	($($tokens:tt)*) => {
+		if let ::core::result::Result::Err(failure) = $crate::__assert2_try_check_impl!("try_assert", $($tokens)*) {
+			return ::core::result::Result::Err(::core::convert::From::from(failure));
+		}
+	}
+}
+
 /// Assert that an expression evaluates to true or matches a pattern.
 ///
 /// This macro supports the same checks as [`assert`](macro.assert.html), but they are only executed if debug assertions are enabled.
@@ -260,13 +647,65 @@ macro_rules! check {
 macro_rules! debug_assert {
 	($($tokens:tt)*) => {
 		if ::core::cfg!(debug_assertions) {
-			if let Err(()) = $crate::__assert2_impl::check_impl!($crate, "debug_assert", $($tokens)*) {
-				panic!("assertion failed");
+			if let Err(_) = $crate::__assert2_impl::check_impl!($crate, "debug_assert", $($tokens)*) {
+				$crate::__assert2_panic!("assertion failed");
 			}
 		}
 	}
 }
 
+/// Assert that an expression evaluates to true or matches a pattern, in a `const` context.
+///
+/// The normal [`assert!`](macro.assert.html) can not be used in a `const fn` or a `const` item,
+/// because printing the values of an expression is not possible during const evaluation.
+/// This macro supports the same syntax for boolean expressions and `let` expressions,
+/// but on failure it only panics with the stringified expression, just like [`std::assert!`].
+///
+/// An optional custom message can be given, but it must be a string literal without format arguments.
+///
+/// ```
+/// # use assert2::const_assert;
+/// const fn checked_div(a: u32, b: u32) -> u32 {
+///     const_assert!(b != 0, "division by zero");
+///     a / b
+/// }
+///
+/// const fn unwrap_or_panic(value: Option<u32>) -> u32 {
+///     const_assert!(let Some(_) = value);
+///     match value {
+///         Some(value) => value,
+///         None => 0,
+///     }
+/// }
+///
+/// const QUOTIENT: u32 = checked_div(10, 2);
+/// const VALUE: u32 = unwrap_or_panic(Some(QUOTIENT));
+/// assert!(VALUE == 5);
+/// ```
+#[macro_export]
+macro_rules! const_assert {
+	(let $pat:pat = $expr:expr $(,)?) => {
+		if !::core::matches!($expr, $pat) {
+			::core::panic!(::core::concat!("assertion failed: let ", ::core::stringify!($pat), " = ", ::core::stringify!($expr)));
+		}
+	};
+	(let $pat:pat = $expr:expr, $msg:literal $(,)?) => {
+		if !::core::matches!($expr, $pat) {
+			::core::panic!(::core::concat!("assertion failed: let ", ::core::stringify!($pat), " = ", ::core::stringify!($expr), ": ", $msg));
+		}
+	};
+	($expr:expr $(,)?) => {
+		if !$expr {
+			::core::panic!(::core::concat!("assertion failed: ", ::core::stringify!($expr)));
+		}
+	};
+	($expr:expr, $msg:literal $(,)?) => {
+		if !$expr {
+			::core::panic!(::core::concat!("assertion failed: ", ::core::stringify!($expr), ": ", $msg));
+		}
+	};
+}
+
 /// Assert that an expression matches a pattern.
 ///
 /// This is very similar to `assert!(let pattern = expression)`,
@@ -316,6 +755,39 @@ macro_rules! debug_assert {
 /// check!(e.to_string() == "invalid name: bogus name");
 /// # }
 /// ```
+///
+/// The pattern can be an or-pattern, as long as every alternative binds the same variables:
+/// ```
+/// # use assert2::{check, let_assert};
+/// let result: Result<i32, i32> = Err(10);
+/// let_assert!(Ok(value) | Err(value) = result);
+/// check!(value == 10);
+/// ```
+///
+/// # Matching through smart pointers
+/// Patterns can not look through a `Box`, `Rc` or `Arc` directly, so dereference the pointer in the expression.
+/// Borrow the dereferenced value with `&*` to bind references to the contents without moving out of the pointer,
+/// or use `.as_deref()` to turn an `Option` of a pointer into an `Option` of a reference:
+/// ```
+/// # use assert2::{check, let_assert};
+/// # use std::rc::Rc;
+/// # use std::sync::Arc;
+/// let shared = Arc::new(Some(String::from("hello")));
+/// let_assert!(Some(text) = &*shared);
+/// check!(text == "hello");
+///
+/// let cached: Option<Rc<str>> = Some("cached".into());
+/// let_assert!(Some("cached") = cached.as_deref());
+///
+/// // A `Box` can also be moved out of.
+/// let boxed = Box::new(Ok::<_, ()>(vec![1, 2]));
+/// let_assert!(Ok(list) = *boxed);
+/// check!(list == [1, 2]);
+/// ```
+///
+/// The pointer must outlive the bindings.
+/// As with a normal `let` statement, `&*Arc::new(value)` keeps the temporary pointer alive,
+/// but calling a method on a temporary pointer, like `Arc::new(value).as_ref()`, does not.
 #[macro_export]
 macro_rules! let_assert {
 	($($tokens:tt)*) => {
@@ -323,6 +795,1398 @@ macro_rules! let_assert {
 	}
 }
 
+/// Check if a value matches a pattern, and run a block with the bound variables if it does.
+///
+/// This is the non-fatal version of [`let_assert!`](macro.let_assert.html).
+/// If the pattern matches, the block after `=>` is executed with the variables bound by the pattern.
+/// If the pattern does not match, an assertion failure is printed and the block is skipped.
+/// Just like [`check!`](macro.check.html), the test fails at the end of the enclosing scope,
+/// so that further checks can still be executed.
+///
+/// You can also pass a custom message before the block.
+///
+/// ```
+/// # use assert2::{check, let_check};
+/// # fn parse(input: &str) -> Result<Vec<u32>, std::num::ParseIntError> {
+/// #   input.split(',').map(|x| x.parse()).collect()
+/// # }
+/// let_check!(Ok(numbers) = parse("1,2,3") => {
+///     check!(numbers.len() == 3);
+///     check!(numbers[0] == 1);
+/// });
+/// let_check!(Err(_) = parse("1,x"), "{:?} should not parse", "1,x" => {});
+/// ```
+#[macro_export]
+macro_rules! let_check {
+	($($tokens:tt)*) => {
+		$crate::__assert2_impl::let_check_impl!($crate, "let_check", $($tokens)*);
+	}
+}
+
+/// Panic if any [`check!`](macro.check.html) on the current thread has failed so far.
+///
+/// A failed `check!()` does not panic until the end of the scope it was used in.
+/// This macro lets you stop a test early, for example before running expensive code on state that is already known to be broken.
+/// It panics with a summary like `3 checks failed so far`, followed by the optional custom message.
+/// The failed checks themselves have already been printed when they were evaluated.
+///
+/// This macro is not related to [`InspectCheckpoint`], which records intermediate values of an iterator.
+///
+/// ```should_panic
+/// # use assert2::{check, checkpoint};
+/// let config = vec![1, 2, 3];
+/// check!(config.len() == 2);
+/// check!(config[0] == 0);
+/// checkpoint!("the configuration is {}", "broken");
+/// unreachable!("this is never reached");
+/// ```
+#[macro_export]
+macro_rules! checkpoint {
+	(@impl $suffix:literal, $message:expr) => {
+		match $crate::__assert2_impl::pending_failures() {
+			0 => (),
+			1 => $crate::__assert2_panic!(::core::concat!("1 check failed so far", $suffix), $message),
+			failed => $crate::__assert2_panic!(::core::concat!("{} checks failed so far", $suffix), failed, $message),
+		}
+	};
+	() => {
+		$crate::checkpoint!(@impl "{}", "")
+	};
+	($($fmt:tt)+) => {
+		$crate::checkpoint!(@impl ": {}", ::core::format_args!($($fmt)+))
+	};
+}
+
+/// Assert that two byte slices are equal.
+///
+/// The arguments can be anything that implements `AsRef<[u8]>`, such as `Vec<u8>`, `&[u8]`, byte arrays or strings.
+///
+/// If the bytes are not equal, a side-by-side hexdump of the first differing region is printed,
+/// with the differing bytes highlighted, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_bytes_eq;
+/// assert_bytes_eq!(b"Hello world!", "Hello world!");
+/// assert_bytes_eq!(vec![1, 2, 3], [1, 2, 3], "bytes should match");
+/// ```
+#[macro_export]
+macro_rules! assert_bytes_eq {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_bytes_eq!(@impl $left, $right, )
+	};
+	($left:expr, $right:expr, $($fmt:tt)+) => {
+		$crate::assert_bytes_eq!(@impl $left, $right, $($fmt)+)
+	};
+	(@impl $left:expr, $right:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$left, &$right) {
+			(left, right) => {
+				let left: &[u8] = ::core::convert::AsRef::as_ref(left);
+				let right: &[u8] = ::core::convert::AsRef::as_ref(right);
+				if left != right {
+					$crate::__assert2_fail!("assert_bytes_eq", site, $crate::__assert2_impl::print::BytesEq {
+						left,
+						right,
+						left_expr: $crate::__assert2_stringify!($left),
+						right_expr: $crate::__assert2_stringify!($right),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that two collections contain the same elements, regardless of order.
+///
+/// The arguments can be anything that can be iterated over by reference, such as a `Vec`, a slice or a `HashSet`.
+/// The collections are compared as multisets: each element must occur equally often on both sides.
+/// The elements only need to implement `PartialEq` and `Debug`.
+///
+/// If the collections differ, the elements missing from each side are printed, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_unordered_eq;
+/// assert_unordered_eq!(vec![1, 2, 3, 2], [2, 3, 2, 1]);
+/// assert_unordered_eq!(["a", "b"], vec!["b", "a"], "words should match");
+/// ```
+#[macro_export]
+macro_rules! assert_unordered_eq {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_unordered_eq!(@impl $left, $right, )
+	};
+	($left:expr, $right:expr, $($fmt:tt)+) => {
+		$crate::assert_unordered_eq!(@impl $left, $right, $($fmt)+)
+	};
+	(@impl $left:expr, $right:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$left, &$right) {
+			(left, right) => {
+				let left: ::std::vec::Vec<_> = ::core::iter::IntoIterator::into_iter(left).collect();
+				let right: ::std::vec::Vec<_> = ::core::iter::IntoIterator::into_iter(right).collect();
+				if let ::core::option::Option::Some(difference) = $crate::__assert2_impl::unordered::difference(&left, &right) {
+					$crate::__assert2_fail!("assert_unordered_eq", site, $crate::__assert2_impl::print::UnorderedEq {
+						only_left: &difference.only_left,
+						only_right: &difference.only_right,
+						left_expr: $crate::__assert2_stringify!($left),
+						right_expr: $crate::__assert2_stringify!($right),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that two collections contain the same elements, ignoring order and duplicates.
+///
+/// This accepts the same arguments as [`assert_subset!`](macro.assert_subset.html).
+///
+/// If the collections differ, only the symmetric difference is printed:
+/// the elements that only occur in the left collection and the elements that only occur in the right collection.
+/// Then the macro panics.
+///
+/// To also compare how often each element occurs, use [`assert_unordered_eq!`](macro.assert_unordered_eq.html).
+///
+/// ```
+/// # use assert2::assert_set_eq;
+/// # use std::collections::{BTreeSet, HashSet};
+/// let enabled: HashSet<_> = ["std", "color"].into_iter().collect();
+/// assert_set_eq!(enabled, ["color", "std"]);
+/// let sorted: BTreeSet<_> = enabled.iter().copied().collect();
+/// assert_set_eq!(sorted, enabled, "the {} should not change", "features");
+/// ```
+#[macro_export]
+macro_rules! assert_set_eq {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_set_eq!(@impl $left, $right, )
+	};
+	($left:expr, $right:expr, $($fmt:tt)+) => {
+		$crate::assert_set_eq!(@impl $left, $right, $($fmt)+)
+	};
+	(@impl $left:expr, $right:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$left, &$right) {
+			(left, right) => {
+				use $crate::__assert2_impl::set::{IsCollection, IsIterator};
+				let left = (&&$crate::__assert2_impl::set::Wrap(left)).__assert2_elements();
+				let right = (&&$crate::__assert2_impl::set::Wrap(right)).__assert2_elements();
+				let only_left = $crate::__assert2_impl::set::missing(&left, &right);
+				let only_right = $crate::__assert2_impl::set::missing(&right, &left);
+				if !only_left.is_empty() || !only_right.is_empty() {
+					$crate::__assert2_fail!("assert_set_eq", site, $crate::__assert2_impl::print::SetEq {
+						only_left: &only_left,
+						only_right: &only_right,
+						left_expr: $crate::__assert2_stringify!($left),
+						right_expr: $crate::__assert2_stringify!($right),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that all elements of the left collection are also in the right collection.
+///
+/// The arguments can be collections that can be iterated by reference, such as a `HashSet`, a `BTreeSet`, a `Vec` or a slice.
+/// They can also be iterators that implement `Clone`.
+/// The elements only need to implement `PartialEq` and `Debug`, and both sides must have the same element type.
+///
+/// If the left collection is not a subset of the right collection,
+/// the elements that are missing from the right collection are printed, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_subset;
+/// # use std::collections::HashSet;
+/// let allowed: HashSet<_> = [1, 2, 3, 4].into_iter().collect();
+/// assert_subset!([1, 3], allowed);
+/// assert_subset!(allowed.iter().filter(|x| **x > 2), allowed, "filter should not invent elements");
+/// ```
+#[macro_export]
+macro_rules! assert_subset {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_subset!(@impl $left, $right, )
+	};
+	($left:expr, $right:expr, $($fmt:tt)+) => {
+		$crate::assert_subset!(@impl $left, $right, $($fmt)+)
+	};
+	(@impl $left:expr, $right:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$left, &$right) {
+			(left, right) => {
+				use $crate::__assert2_impl::set::{IsCollection, IsIterator};
+				let left = (&&$crate::__assert2_impl::set::Wrap(left)).__assert2_elements();
+				let right = (&&$crate::__assert2_impl::set::Wrap(right)).__assert2_elements();
+				let missing = $crate::__assert2_impl::set::missing(&left, &right);
+				if !missing.is_empty() {
+					$crate::__assert2_fail!("assert_subset", site, $crate::__assert2_impl::print::SetRelation {
+						missing: &missing,
+						subset: true,
+						left_expr: $crate::__assert2_stringify!($left),
+						right_expr: $crate::__assert2_stringify!($right),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that all elements of the right collection are also in the left collection.
+///
+/// This accepts the same arguments as [`assert_subset!`](macro.assert_subset.html), with the roles of left and right swapped.
+///
+/// If the left collection is not a superset of the right collection,
+/// the elements that are missing from the left collection are printed, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_superset;
+/// # use std::collections::BTreeSet;
+/// let features: BTreeSet<_> = ["async", "std", "color"].into_iter().collect();
+/// assert_superset!(features, ["std", "color"]);
+/// ```
+#[macro_export]
+macro_rules! assert_superset {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_superset!(@impl $left, $right, )
+	};
+	($left:expr, $right:expr, $($fmt:tt)+) => {
+		$crate::assert_superset!(@impl $left, $right, $($fmt)+)
+	};
+	(@impl $left:expr, $right:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$left, &$right) {
+			(left, right) => {
+				use $crate::__assert2_impl::set::{IsCollection, IsIterator};
+				let left = (&&$crate::__assert2_impl::set::Wrap(left)).__assert2_elements();
+				let right = (&&$crate::__assert2_impl::set::Wrap(right)).__assert2_elements();
+				let missing = $crate::__assert2_impl::set::missing(&right, &left);
+				if !missing.is_empty() {
+					$crate::__assert2_fail!("assert_superset", site, $crate::__assert2_impl::print::SetRelation {
+						missing: &missing,
+						subset: false,
+						left_expr: $crate::__assert2_stringify!($left),
+						right_expr: $crate::__assert2_stringify!($right),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that some fields of a struct are equal to the expected values.
+///
+/// The second argument looks like a struct expression that ends with `..`.
+/// Only the listed fields are compared, so you don't have to construct a complete expected value.
+/// Each field must implement `PartialEq` with the expected value and `Debug`.
+///
+/// If any field differs, a table with the result for each listed field is printed, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_fields_eq;
+/// #[derive(Debug)]
+/// struct Pet {
+///     name: String,
+///     age: u32,
+///     kind: String,
+/// }
+/// let pet = Pet { name: "Scrappy".into(), age: 7, kind: "Bearded Collie".into() };
+/// assert_fields_eq!(pet, Pet { name: "Scrappy", age: 7, .. });
+/// assert_fields_eq!(pet, Pet { age: 7, .. }, "{} should be 7", pet.name);
+/// ```
+#[macro_export]
+macro_rules! assert_fields_eq {
+	($actual:expr, $ty:path { $($field:ident : $value:expr,)* .. } $(,)?) => {
+		$crate::assert_fields_eq!(@impl $actual, $ty { $($field: $value,)* }, )
+	};
+	($actual:expr, $ty:path { $($field:ident : $value:expr,)* .. }, $($fmt:tt)+) => {
+		$crate::assert_fields_eq!(@impl $actual, $ty { $($field: $value,)* }, $($fmt)+)
+	};
+	(@impl $actual:expr, $ty:path { $($field:ident : $value:expr,)* }, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match &$actual {
+			actual => {
+				// Make sure the listed type matches the actual value.
+				let $ty { .. } = actual;
+				match [$($crate::__assert2_impl::print::Field::new(::core::stringify!($field), &actual.$field, &$value)),*] {
+					fields => {
+						let fields: &[$crate::__assert2_impl::print::Field] = &fields;
+						if fields.iter().any(|field| !field.equal) {
+							$crate::__assert2_fail!("assert_fields_eq", site, $crate::__assert2_impl::print::FieldsEq {
+								fields: &fields,
+								actual_expr: $crate::__assert2_stringify!($actual),
+								expected_expr: $crate::__assert2_core_stringify!($ty { $($field: $value,)* .. }),
+							}, $($fmt)*);
+						}
+					}
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that an error or one of its sources has a specific type, and optionally matches a pattern.
+///
+/// The macro walks the chain of errors returned by [`Error::source()`](std::error::Error::source),
+/// starting with the error itself, and tries to downcast each error to the given type.
+/// If a pattern is given with `=>`, the downcasted error must also match the pattern.
+///
+/// The error can be any type that implements [`Error`](std::error::Error),
+/// or a type that implements `AsRef<dyn Error>`, like `Box<dyn Error>` or `anyhow::Error`.
+///
+/// If no error in the chain matches, the whole chain is printed, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_err_chain;
+/// # use std::error::Error;
+/// # use std::io;
+/// let error: Box<dyn Error> = Box::new(io::Error::new(io::ErrorKind::NotFound, "file not found"));
+/// assert_err_chain!(error, io::Error);
+/// assert_err_chain!(error, io::Error => e if e.kind() == io::ErrorKind::NotFound, "should be not found");
+/// ```
+#[macro_export]
+macro_rules! assert_err_chain {
+	($error:expr, $ty:ty $(,)?) => {
+		$crate::assert_err_chain!(@impl $error, $ty, _, ::core::option::Option::None, )
+	};
+	($error:expr, $ty:ty, $($fmt:tt)+) => {
+		$crate::assert_err_chain!(@impl $error, $ty, _, ::core::option::Option::None, $($fmt)+)
+	};
+	($error:expr, $ty:ty => $pat:pat $(if $guard:expr)? $(,)?) => {
+		$crate::assert_err_chain!(@impl $error, $ty, $pat $(if $guard)?, ::core::option::Option::Some($crate::__assert2_core_stringify!($pat $(if $guard)?)), )
+	};
+	($error:expr, $ty:ty => $pat:pat $(if $guard:expr)?, $($fmt:tt)+) => {
+		$crate::assert_err_chain!(@impl $error, $ty, $pat $(if $guard)?, ::core::option::Option::Some($crate::__assert2_core_stringify!($pat $(if $guard)?)), $($fmt)+)
+	};
+	(@impl $error:expr, $ty:ty, $pat:pat $(if $guard:expr)?, $pattern_str:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match &$error {
+			error => {
+				use $crate::__assert2_impl::error_chain::{IsError, IsErrorRef, IsSendSyncErrorRef};
+				let error = (&&&$crate::__assert2_impl::error_chain::Wrap(error)).__assert2_as_error();
+				let chain = $crate::__assert2_impl::error_chain::chain(error);
+				let mut type_matches = ::std::vec::Vec::new();
+				let mut found = false;
+				for (i, error) in chain.iter().enumerate() {
+					if let ::core::option::Option::Some(error) = error.downcast_ref::<$ty>() {
+						#[allow(unused_variables)]
+						match error {
+							$pat $(if $guard)? => {
+								found = true;
+								break;
+							},
+							#[allow(unreachable_patterns)]
+							_ => type_matches.push(i),
+						}
+					}
+				}
+				if !found {
+					$crate::__assert2_fail!("assert_err_chain", site, $crate::__assert2_impl::print::ErrChain {
+						chain: &chain,
+						type_matches: &type_matches,
+						error_expr: $crate::__assert2_stringify!($error),
+						type_name: $crate::__assert2_core_stringify!($ty),
+						pattern: $pattern_str,
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that a string matches a regular expression.
+///
+/// The text can be anything that implements `AsRef<str>`, and the pattern is compiled with the [`regex`](https://docs.rs/regex) crate.
+/// The pattern is not anchored implicitly, so use `^` and `$` to match the whole text.
+///
+/// If the text does not match, the text and the pattern are printed, and the macro panics.
+/// If possible, the longest prefix of the pattern that does match is printed too,
+/// to show where the pattern diverges from the text.
+///
+/// This macro is only available with the `regex` feature.
+///
+/// ```
+/// # use assert2::assert_regex;
+/// assert_regex!("foo 42", r"^foo \d+$");
+/// assert_regex!(String::from("Hello world!"), r"\bworld\b", "should greet the world");
+/// ```
+#[cfg(feature = "regex")]
+#[macro_export]
+macro_rules! assert_regex {
+	($text:expr, $pattern:expr $(,)?) => {
+		$crate::assert_regex!(@impl $text, $pattern, )
+	};
+	($text:expr, $pattern:expr, $($fmt:tt)+) => {
+		$crate::assert_regex!(@impl $text, $pattern, $($fmt)+)
+	};
+	(@impl $text:expr, $pattern:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$text, &$pattern) {
+			(text, pattern) => {
+				let text: &str = ::core::convert::AsRef::as_ref(text);
+				let pattern: &str = ::core::convert::AsRef::as_ref(pattern);
+				if let ::core::result::Result::Err(mismatch) = $crate::__assert2_impl::regex::check(text, pattern) {
+					$crate::__assert2_fail!("assert_regex", site, $crate::__assert2_impl::print::RegexMatch {
+						text,
+						pattern,
+						mismatch: &mismatch,
+						text_expr: $crate::__assert2_stringify!($text),
+						pattern_expr: $crate::__assert2_stringify!($pattern),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that a string or slice starts with an expected prefix.
+///
+/// Strings are compared character by character, and can be anything that implements `AsRef<str>`.
+/// Other values are compared element by element, and can be anything that implements `AsRef<[T]>`, where `T` implements `PartialEq` and `Debug`.
+///
+/// If the check fails, both values are printed, and the part where the actual value first diverges from the expected prefix is highlighted.
+/// The macro then panics.
+///
+/// ```
+/// # use assert2::assert_starts_with;
+/// assert_starts_with!("Hello world!", "Hello");
+/// assert_starts_with!(String::from("Hello world!"), "Hello", "should greet");
+/// assert_starts_with!(vec![1, 2, 3], [1, 2]);
+/// ```
+#[macro_export]
+macro_rules! assert_starts_with {
+	($actual:expr, $expected:expr $(,)?) => {
+		$crate::assert_starts_with!(@impl $actual, $expected, )
+	};
+	($actual:expr, $expected:expr, $($fmt:tt)+) => {
+		$crate::assert_starts_with!(@impl $actual, $expected, $($fmt)+)
+	};
+	(@impl $actual:expr, $expected:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$actual, &$expected) {
+			(actual, expected) => {
+				use $crate::__assert2_impl::affix::{IsSlice, IsStr};
+				let affix = $crate::__assert2_impl::affix::Affix::Prefix;
+				let wrap = $crate::__assert2_impl::affix::Wrap(actual, expected);
+				if let ::core::option::Option::Some(mismatch) = (&&wrap).__assert2_check_affix(affix) {
+					$crate::__assert2_fail!("assert_starts_with", site, $crate::__assert2_impl::print::AffixEq {
+						mismatch: &mismatch,
+						affix,
+						actual_expr: $crate::__assert2_stringify!($actual),
+						expected_expr: $crate::__assert2_stringify!($expected),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that a string or slice ends with an expected suffix.
+///
+/// Strings are compared character by character, and can be anything that implements `AsRef<str>`.
+/// Other values are compared element by element, and can be anything that implements `AsRef<[T]>`, where `T` implements `PartialEq` and `Debug`.
+///
+/// If the check fails, both values are printed, and the part where the actual value first diverges from the expected suffix is highlighted.
+/// The macro then panics.
+///
+/// ```
+/// # use assert2::assert_ends_with;
+/// assert_ends_with!("Hello world!", "world!");
+/// assert_ends_with!(vec![1, 2, 3], [2, 3], "should end with {} and {}", 2, 3);
+/// assert_ends_with!(b"foo.txt", b".txt");
+/// ```
+#[macro_export]
+macro_rules! assert_ends_with {
+	($actual:expr, $expected:expr $(,)?) => {
+		$crate::assert_ends_with!(@impl $actual, $expected, )
+	};
+	($actual:expr, $expected:expr, $($fmt:tt)+) => {
+		$crate::assert_ends_with!(@impl $actual, $expected, $($fmt)+)
+	};
+	(@impl $actual:expr, $expected:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$actual, &$expected) {
+			(actual, expected) => {
+				use $crate::__assert2_impl::affix::{IsSlice, IsStr};
+				let affix = $crate::__assert2_impl::affix::Affix::Suffix;
+				let wrap = $crate::__assert2_impl::affix::Wrap(actual, expected);
+				if let ::core::option::Option::Some(mismatch) = (&&wrap).__assert2_check_affix(affix) {
+					$crate::__assert2_fail!("assert_ends_with", site, $crate::__assert2_impl::print::AffixEq {
+						mismatch: &mismatch,
+						affix,
+						actual_expr: $crate::__assert2_stringify!($actual),
+						expected_expr: $crate::__assert2_stringify!($expected),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that a collection is empty.
+///
+/// The collection can be anything that can be iterated over by reference, such as a `Vec`, a slice or a `HashMap`,
+/// or an iterator that implements `Clone`.
+///
+/// If the collection is not empty, the length and the first elements of the collection are printed, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_empty;
+/// assert_empty!(Vec::<i32>::new());
+/// assert_empty!([1, 2, 3].iter().filter(|x| **x > 3), "no element should be larger than 3");
+/// ```
+#[macro_export]
+macro_rules! assert_empty {
+	($collection:expr $(,)?) => {
+		$crate::assert_empty!(@impl $collection, )
+	};
+	($collection:expr, $($fmt:tt)+) => {
+		$crate::assert_empty!(@impl $collection, $($fmt)+)
+	};
+	(@impl $collection:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match &$collection {
+			collection => {
+				use $crate::__assert2_impl::set::{IsCollection, IsIterator};
+				let elements = (&&$crate::__assert2_impl::set::Wrap(collection)).__assert2_elements();
+				let expected: usize = 0;
+				if elements.len() != expected {
+					$crate::__assert2_fail!("assert_empty", site, $crate::__assert2_impl::print::LenEq {
+						elements: &elements,
+						expected,
+						collection_expr: $crate::__assert2_stringify!($collection),
+						len_expr: ::core::option::Option::None,
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that a collection has a specific length.
+///
+/// This accepts the same collections as [`assert_empty!`](macro.assert_empty.html).
+/// The length is the number of elements produced when iterating over the collection.
+///
+/// If the length is different, the actual length and the first elements of the collection are printed, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_len;
+/// # use std::collections::HashMap;
+/// assert_len!(vec![1, 2, 3], 3);
+/// let map: HashMap<_, _> = [("a", 1), ("b", 2)].into_iter().collect();
+/// assert_len!(map, 2, "the map should have {} entries", 2);
+/// ```
+#[macro_export]
+macro_rules! assert_len {
+	($collection:expr, $len:expr $(,)?) => {
+		$crate::assert_len!(@impl $collection, $len, )
+	};
+	($collection:expr, $len:expr, $($fmt:tt)+) => {
+		$crate::assert_len!(@impl $collection, $len, $($fmt)+)
+	};
+	(@impl $collection:expr, $len:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match &$collection {
+			collection => {
+				use $crate::__assert2_impl::set::{IsCollection, IsIterator};
+				let elements = (&&$crate::__assert2_impl::set::Wrap(collection)).__assert2_elements();
+				let expected: usize = $len;
+				if elements.len() != expected {
+					$crate::__assert2_fail!("assert_len", site, $crate::__assert2_impl::print::LenEq {
+						elements: &elements,
+						expected,
+						collection_expr: $crate::__assert2_stringify!($collection),
+						len_expr: ::core::option::Option::Some($crate::__assert2_stringify!($len)),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that all items of an iterator are `Ok`, and evaluate to a `Vec` of the `Ok` values.
+///
+/// The argument can be anything that implements `IntoIterator` with items of type `Result<T, E>` or `&Result<T, E>`,
+/// where `E` implements `Debug`.
+///
+/// The iterator is consumed until the first `Err` is found.
+/// The index of the error and the expanded error value are printed, and the macro panics.
+/// Use `assert_all_ok!(count_errors = true, results)` to consume the remaining items and also print the total number of errors.
+///
+/// ```
+/// # use assert2::{assert, assert_all_ok};
+/// let values = assert_all_ok!(["1", "2", "3"].iter().map(|x| x.parse::<i32>()));
+/// assert!(values == [1, 2, 3]);
+///
+/// let results: Vec<Result<i32, String>> = vec![Ok(1), Ok(2)];
+/// assert_all_ok!(&results, "all items should be processed");
+/// assert_all_ok!(count_errors = true, results);
+/// ```
+#[macro_export]
+macro_rules! assert_all_ok {
+	(count_errors = $count:expr, $results:expr $(,)?) => {
+		$crate::assert_all_ok!(@impl $count, $results, )
+	};
+	(count_errors = $count:expr, $results:expr, $($fmt:tt)+) => {
+		$crate::assert_all_ok!(@impl $count, $results, $($fmt)+)
+	};
+	($results:expr $(,)?) => {
+		$crate::assert_all_ok!(@impl false, $results, )
+	};
+	($results:expr, $($fmt:tt)+) => {
+		$crate::assert_all_ok!(@impl false, $results, $($fmt)+)
+	};
+	(@impl $count:expr, $results:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match $crate::__assert2_impl::all_ok::check($results, $count) {
+			::core::result::Result::Ok(values) => values,
+			::core::result::Result::Err(first) => {
+				$crate::__assert2_fail!(@panic "assert_all_ok", site, $crate::__assert2_impl::print::AllOk {
+					first: &first,
+					expression: $crate::__assert2_stringify!($results),
+				}, $($fmt)*);
+			},
+		}
+	}};
+}
+
+/// Assert that a value matches a [`Matcher`](matchers::Matcher).
+///
+/// Matchers can be combined with [`all!()`](macro.all.html), [`any!()`](macro.any.html) and [`not()`](matchers::not).
+/// See the [`matchers`] module for the available matchers, or implement the [`Matcher`](matchers::Matcher) trait to create your own.
+///
+/// If the value does not match, the value and the description of the matcher are printed,
+/// together with the part of the matcher that was not satisfied, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_that;
+/// use assert2::matchers::*;
+/// assert_that!(5, all!(gt(3), lt(10)));
+/// assert_that!(String::from("foo"), any!(eq("foo"), eq("bar")), "unexpected name");
+/// ```
+#[macro_export]
+macro_rules! assert_that {
+	($value:expr, $matcher:expr $(,)?) => {
+		$crate::assert_that!(@impl $value, $matcher, )
+	};
+	($value:expr, $matcher:expr, $($fmt:tt)+) => {
+		$crate::assert_that!(@impl $value, $matcher, $($fmt)+)
+	};
+	(@impl $value:expr, $matcher:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$value, &$matcher) {
+			(value, matcher) => {
+				if !$crate::matchers::Matcher::matches(matcher, value) {
+					use $crate::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+					let debug = (&&&&$crate::__assert2_impl::maybe_debug::Wrap(value)).__assert2_maybe_debug().wrap(value);
+					$crate::__assert2_fail!("assert_that", site, $crate::__assert2_impl::print::MatcherExpr::new(
+						&debug,
+						value,
+						matcher,
+						$crate::__assert2_stringify!($value),
+						$crate::__assert2_stringify!($matcher),
+					), $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Combine matchers into a matcher that only matches if all of them match.
+///
+/// On failure, only the matchers that were not satisfied are reported.
+///
+/// ```
+/// # use assert2::assert_that;
+/// use assert2::matchers::*;
+/// assert_that!(5, all!(gt(3), lt(10), ne(7)));
+/// ```
+#[macro_export]
+macro_rules! all {
+	($matcher:expr $(,)?) => {
+		$matcher
+	};
+	($first:expr, $($rest:expr),+ $(,)?) => {
+		$crate::matchers::AllOf($first, $crate::all!($($rest),+))
+	};
+}
+
+/// Combine matchers into a matcher that matches if any of them match.
+///
+/// ```
+/// # use assert2::assert_that;
+/// use assert2::matchers::*;
+/// assert_that!(5, any!(lt(0), eq(5)));
+/// ```
+#[macro_export]
+macro_rules! any {
+	($matcher:expr $(,)?) => {
+		$matcher
+	};
+	($first:expr, $($rest:expr),+ $(,)?) => {
+		$crate::matchers::AnyOf($first, $crate::any!($($rest),+))
+	};
+}
+
+/// Assert that all values are different from each other.
+///
+/// Every pair of values is compared with `==`.
+/// The values must all have the same type, which must implement `PartialEq` and `Debug`.
+/// Since the values are separated by commas, a custom message must be given after a semicolon.
+///
+/// If any pair of values compares equal, all equal pairs and the values involved are printed, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_all_distinct;
+/// let (a, b, c) = (1, 2, 3);
+/// assert_all_distinct!(a, b, c);
+/// assert_all_distinct!("foo", "bar", "baz"; "all names should be unique");
+/// ```
+#[macro_export]
+macro_rules! assert_all_distinct {
+	($($value:expr),+ $(,)?) => {
+		$crate::assert_all_distinct!(@impl [$($value),+], )
+	};
+	($($value:expr),+ $(,)?; $($fmt:tt)+) => {
+		$crate::assert_all_distinct!(@impl [$($value),+], $($fmt)+)
+	};
+	(@impl [$($value:expr),+], $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match [$(&$value),+] {
+			values => {
+				let pairs = $crate::__assert2_impl::distinct::equal_pairs(&values);
+				if !pairs.is_empty() {
+					$crate::__assert2_fail!("assert_all_distinct", site, $crate::__assert2_impl::print::AllDistinct {
+						values: &values,
+						pairs: &pairs,
+						expressions: &[$($crate::__assert2_stringify!($value)),+],
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that two maps contain the same entries.
+///
+/// Both sides can be a map like [`HashMap`](std::collections::HashMap) or [`BTreeMap`](std::collections::BTreeMap),
+/// a collection of `(key, value)` tuples like a [`Vec`] or an array,
+/// or a cloneable iterator over `(&key, &value)` pairs.
+/// The keys must have the same type on both sides and implement [`PartialEq`] and [`Debug`](std::fmt::Debug).
+/// The values of the left side must implement [`PartialEq`] for the values of the right side.
+/// The order of the entries is ignored.
+///
+/// If the maps differ, the keys that only occur on one side are printed,
+/// followed by a diff of the values for each key that occurs on both sides with different values, and the macro panics.
+///
+/// ```
+/// # use assert2::assert_map_eq;
+/// # use std::collections::{BTreeMap, HashMap};
+/// let scores: HashMap<_, _> = [("alice", 3), ("bob", 5)].into_iter().collect();
+/// assert_map_eq!(scores, [("bob", 5), ("alice", 3)]);
+/// let sorted: BTreeMap<_, _> = scores.clone().into_iter().collect();
+/// assert_map_eq!(sorted, scores, "the {} should not change", "scores");
+/// ```
+#[macro_export]
+macro_rules! assert_map_eq {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_map_eq!(@impl $left, $right, )
+	};
+	($left:expr, $right:expr, $($fmt:tt)+) => {
+		$crate::assert_map_eq!(@impl $left, $right, $($fmt)+)
+	};
+	(@impl $left:expr, $right:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$left, &$right) {
+			(left, right) => {
+				use $crate::__assert2_impl::set::{IsCollection, IsIterator};
+				let left = (&&$crate::__assert2_impl::set::Wrap(left)).__assert2_elements();
+				let right = (&&$crate::__assert2_impl::set::Wrap(right)).__assert2_elements();
+				let diff = $crate::__assert2_impl::map::compare(&left, &right);
+				if !diff.is_empty() {
+					$crate::__assert2_fail!("assert_map_eq", site, $crate::__assert2_impl::print::MapEq {
+						diff: &diff,
+						left_expr: $crate::__assert2_stringify!($left),
+						right_expr: $crate::__assert2_stringify!($right),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that two iterators produce equal elements.
+///
+/// Both arguments can be anything that implements [`IntoIterator`], and are consumed by the macro.
+/// The elements are compared one by one, without collecting them first.
+/// The elements of the left side must implement [`PartialEq`] for the elements of the right side, and both must implement [`Debug`](std::fmt::Debug).
+///
+/// If the iterators differ, the first differing index is printed with a few elements of context from both sides,
+/// together with the lengths of both iterators if they are not equal, and the macro panics.
+/// To determine the lengths, the remaining elements of both iterators are consumed.
+///
+/// ```
+/// # use assert2::assert_iter_eq;
+/// let values = vec![1, 2, 3];
+/// assert_iter_eq!(values.iter().map(|x| x * 2), [2, 4, 6]);
+/// assert_iter_eq!("abc".chars(), ['a', 'b', 'c'], "the {} should match", "characters");
+/// ```
+#[macro_export]
+macro_rules! assert_iter_eq {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_iter_eq!(@impl $left, $right, )
+	};
+	($left:expr, $right:expr, $($fmt:tt)+) => {
+		$crate::assert_iter_eq!(@impl $left, $right, $($fmt)+)
+	};
+	(@impl $left:expr, $right:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match ($left, $right) {
+			(left, right) => {
+				if let ::core::option::Option::Some(mismatch) = $crate::__assert2_impl::iter_eq::compare(left, right) {
+					$crate::__assert2_fail!("assert_iter_eq", site, $crate::__assert2_impl::print::IterEq {
+						mismatch: &mismatch,
+						left_expr: $crate::__assert2_stringify!($left),
+						right_expr: $crate::__assert2_stringify!($right),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that a type-erased value can be downcast to a concrete type, and evaluate to a reference to the concrete value.
+///
+/// The value can be a `Box` or a reference to `dyn Any` or `dyn Error`, optionally with `Send` and `Sync` bounds.
+/// This includes the payload of a panic caught with [`std::panic::catch_unwind()`].
+///
+/// If the value has a different type, the expected type is printed together with what is known about the actual type, and the macro panics.
+/// The actual type name can only be printed for a number of well-known types, like `String`, `&str`, the primitive types and the error types of the standard library.
+/// For other values of `dyn Any`, the [`TypeId`](std::any::TypeId) is printed instead.
+/// For errors, the `Debug` representation of the error is printed too, which usually includes the type name.
+///
+/// ```
+/// # use assert2::{assert, assert_downcast};
+/// let payload = std::panic::catch_unwind(|| panic!("{}", "boom")).unwrap_err();
+/// let message = assert_downcast!(payload, String);
+/// assert!(message == "boom");
+///
+/// let error = "five".parse::<u32>().unwrap_err();
+/// let error: &dyn std::error::Error = &error;
+/// assert_downcast!(error, std::num::ParseIntError, "expected a {} error", "parse");
+/// ```
+#[macro_export]
+macro_rules! assert_downcast {
+	($value:expr, $type:ty $(,)?) => {
+		$crate::assert_downcast!(@impl $value, $type, )
+	};
+	($value:expr, $type:ty, $($fmt:tt)+) => {
+		$crate::assert_downcast!(@impl $value, $type, $($fmt)+)
+	};
+	(@impl $value:expr, $type:ty, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		#[allow(unused_imports)]
+		use $crate::__assert2_impl::downcast::{AsDowncastBox, AsDowncastRef, Downcast, DowncastTo};
+		let value = ($value).__assert2_as_downcast();
+		match DowncastTo::<$type>::__assert2_downcast_ref(value) {
+			::core::option::Option::Some(value) => value,
+			::core::option::Option::None => {
+				$crate::__assert2_fail!(@panic "assert_downcast", site, $crate::__assert2_impl::print::DowncastFailed {
+					expected: ::core::any::type_name::<$type>(),
+					actual: value.__assert2_actual(),
+					value_expr: $crate::__assert2_stringify!($value),
+					type_expr: $crate::__assert2_stringify!($type),
+				}, $($fmt)*);
+			},
+		}
+	}};
+}
+
+/// Assert that a string is equal to the contents of a golden file.
+///
+/// Relative paths are resolved relative to the directory containing the `Cargo.toml` of the calling crate.
+///
+/// If the contents differ, a line diff between the string and the golden file is printed, and the macro panics.
+/// The left side of the diff is the actual string, the right side is the golden file.
+///
+/// If the `ASSERT2_UPDATE_GOLDEN` environment variable is set to a non-zero value,
+/// the golden file is overwritten with the actual string instead of being compared.
+/// Missing parent directories are created automatically.
+///
+/// ```no_run
+/// # use assert2::assert_file_eq;
+/// # let output = String::new();
+/// assert_file_eq!(output, "tests/golden/output.txt");
+/// ```
+#[macro_export]
+macro_rules! assert_file_eq {
+	($actual:expr, $path:expr $(,)?) => {
+		$crate::assert_file_eq!(@impl $actual, $path, )
+	};
+	($actual:expr, $path:expr, $($fmt:tt)+) => {
+		$crate::assert_file_eq!(@impl $actual, $path, $($fmt)+)
+	};
+	(@impl $actual:expr, $path:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match (&$actual, &$path) {
+			(actual, path) => {
+				let actual: &str = ::core::convert::AsRef::as_ref(actual);
+				let path = $crate::__assert2_impl::golden::resolve_path(env!("CARGO_MANIFEST_DIR"), path);
+				if let Err(mismatch) = $crate::__assert2_impl::golden::compare(actual, &path) {
+					$crate::__assert2_fail!("assert_file_eq", site, $crate::__assert2_impl::print::FileEq {
+						actual,
+						path: &path,
+						mismatch: &mismatch,
+						actual_expr: $crate::__assert2_stringify!($actual),
+						path_expr: $crate::__assert2_stringify!($path),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+}
+
+/// Assert that the pretty `Debug` output of a value matches a stored snapshot.
+///
+/// Snapshots are stored in the `tests/snapshots` directory next to the `Cargo.toml` of the calling crate.
+/// By default, the snapshot file is named after the path of the function that contains the assertion.
+/// If a function takes more than one unnamed snapshot, a number is appended to the name of the second and later snapshots.
+/// You can also give the snapshot an explicit name with `assert_snapshot!(name = "my_snapshot", value)`.
+///
+/// The `Debug` output is compared with the stored snapshot and a line diff is printed if they differ.
+/// If the snapshot file does not exist, the assertion fails.
+/// Set the `ASSERT2_UPDATE_GOLDEN` environment variable to a non-zero value to create or overwrite all snapshots.
+///
+/// ```no_run
+/// # use assert2::assert_snapshot;
+/// assert_snapshot!(name = "numbers", vec![1, 2, 3]);
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+	(name = $name:expr, $value:expr $(,)?) => {
+		$crate::assert_snapshot!(@impl ::core::option::Option::Some($name), $value, )
+	};
+	(name = $name:expr, $value:expr, $($fmt:tt)+) => {
+		$crate::assert_snapshot!(@impl ::core::option::Option::Some($name), $value, $($fmt)+)
+	};
+	(@impl $name:expr, $value:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match &$value {
+			value => {
+				let actual = ::std::format!("{:#?}\n", value);
+				fn __assert2_item() {}
+				let function = $crate::__assert2_impl::golden::function_path(__assert2_item);
+				let path = $crate::__assert2_impl::golden::snapshot_path(env!("CARGO_MANIFEST_DIR"), function, $name);
+				if let Err(mismatch) = $crate::__assert2_impl::golden::compare_snapshot(&actual, &path) {
+					$crate::__assert2_fail!("assert_snapshot", site, $crate::__assert2_impl::print::SnapshotEq {
+						actual: &actual,
+						path: &path,
+						mismatch: &mismatch,
+						expression: $crate::__assert2_stringify!($value),
+					}, $($fmt)*);
+				}
+			}
+		}
+	}};
+	($value:expr $(,)?) => {
+		$crate::assert_snapshot!(@impl ::core::option::Option::None, $value, )
+	};
+	($value:expr, $($fmt:tt)+) => {
+		$crate::assert_snapshot!(@impl ::core::option::Option::None, $value, $($fmt)+)
+	};
+}
+
+/// Assert that an expression eventually evaluates to true or matches a pattern.
+///
+/// The expression is evaluated repeatedly until it passes, or until the timeout expires.
+/// Between attempts, the macro sleeps for the given interval.
+/// If no interval is given, it defaults to 10 milliseconds.
+///
+/// The timeout and interval can be given as a literal with a unit suffix (`ns`, `us`, `ms`, `s`, `min` or `h`),
+/// or as any expression of type [`Duration`](std::time::Duration) wrapped in parentheses.
+///
+/// Failed attempts are not printed.
+/// If the timeout expires, the expression is evaluated one last time.
+/// If it still fails, the expansion of that last attempt is printed and the macro panics
+/// with a message that includes the number of attempts.
+///
+/// The expression supports everything that [`assert!`](macro.assert.html) supports, including custom messages.
+///
+/// ```
+/// # use assert2::assert_eventually;
+/// # use std::sync::atomic::{AtomicBool, Ordering};
+/// # use std::sync::Arc;
+/// # use std::time::Duration;
+/// let done = Arc::new(AtomicBool::new(false));
+/// std::thread::spawn({
+///     let done = done.clone();
+///     move || done.store(true, Ordering::Relaxed)
+/// });
+/// assert_eventually!(timeout = 2s, interval = 5ms, done.load(Ordering::Relaxed));
+/// assert_eventually!(timeout = (Duration::from_secs(2)), done.load(Ordering::Relaxed) == true);
+/// ```
+#[macro_export]
+macro_rules! assert_eventually {
+	(timeout = $timeout:tt, interval = $interval:tt, $($tokens:tt)+) => {
+		$crate::assert_eventually!(@impl $timeout, $interval, $($tokens)+)
+	};
+	(timeout = $timeout:tt, $($tokens:tt)+) => {
+		$crate::assert_eventually!(@impl $timeout, 10ms, $($tokens)+)
+	};
+	(@impl $timeout:tt, $interval:tt, $($tokens:tt)+) => {{
+		let timeout: ::core::time::Duration = $crate::__assert2_impl::duration_impl!($timeout);
+		let interval: ::core::time::Duration = $crate::__assert2_impl::duration_impl!($interval);
+		let start = $crate::__assert2_impl::now();
+		let mut attempts = 0usize;
+		loop {
+			attempts += 1;
+			let elapsed = $crate::__assert2_impl::now().saturating_duration_since(start);
+			if elapsed >= timeout {
+				if let Err(_) = $crate::__assert2_impl::check_impl!($crate, "assert_eventually", $($tokens)+) {
+					$crate::__assert2_panic!("assertion did not pass within {:?} ({} attempts)", timeout, attempts);
+				}
+				break;
+			}
+			let silence = $crate::__assert2_impl::print::Silence::enter();
+			let passed = $crate::__assert2_impl::check_impl!($crate, "assert_eventually", $($tokens)+).is_ok();
+			drop(silence);
+			if passed {
+				break;
+			}
+			::std::thread::sleep(interval.min(timeout.saturating_sub(elapsed)));
+		}
+	}};
+}
+
+/// Assert that a block of code completes within a time budget.
+///
+/// The block is always run to completion.
+/// If it took longer than the budget, the elapsed time and the budget are printed and the macro panics.
+/// Otherwise, the macro evaluates to the value of the block.
+///
+/// The budget can be any expression of type [`Duration`](std::time::Duration),
+/// or a literal with a unit suffix (`ns`, `us`, `ms`, `s`, `min` or `h`).
+///
+/// ```
+/// # use assert2::assert_completes_within;
+/// # use std::time::Duration;
+/// let sum = assert_completes_within!(Duration::from_secs(10), {
+///     (0..1000).sum::<u64>()
+/// });
+/// assert_completes_within!(10s, std::thread::sleep(Duration::from_millis(1)), "{}", "sleeping took too long");
+/// ```
+#[macro_export]
+macro_rules! assert_completes_within {
+	($budget:tt, $block:expr $(,)?) => {
+		$crate::assert_completes_within!(@impl $budget, $block, )
+	};
+	($budget:tt, $block:expr, $($fmt:tt)+) => {
+		$crate::assert_completes_within!(@impl $budget, $block, $($fmt)+)
+	};
+	($budget:expr, $block:expr $(,)?) => {
+		$crate::assert_completes_within!(@impl ($budget), $block, )
+	};
+	($budget:expr, $block:expr, $($fmt:tt)+) => {
+		$crate::assert_completes_within!(@impl ($budget), $block, $($fmt)+)
+	};
+	(@impl $budget:tt, $block:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		let budget: ::core::time::Duration = $crate::__assert2_impl::duration_impl!($budget);
+		let start = $crate::__assert2_impl::now();
+		let value = $block;
+		let elapsed = $crate::__assert2_impl::now().saturating_duration_since(start);
+		if elapsed > budget {
+			$crate::__assert2_fail!("assert_completes_within", site, $crate::__assert2_impl::print::CompletesWithin {
+				budget,
+				elapsed,
+				budget_expr: $crate::__assert2_stringify!($budget),
+				block_expr: $crate::__assert2_stringify!($block),
+			}, $($fmt)*);
+		}
+		value
+	}};
+}
+
+/// Assert that a value is received from a channel within a timeout, and evaluate to the received value.
+///
+/// The receiver can be a [`std::sync::mpsc::Receiver`],
+/// a `crossbeam_channel::Receiver` if the `crossbeam` feature is enabled,
+/// or a `tokio::sync::mpsc::Receiver` or `UnboundedReceiver` if the `tokio` feature is enabled.
+/// Receiving from a `tokio` channel does not need a runtime, but it does block the current thread until a value is received or the timeout expires.
+///
+/// The timeout can be a literal with a unit suffix (`ns`, `us`, `ms`, `s`, `min` or `h`),
+/// or any expression of type [`Duration`](std::time::Duration) wrapped in parentheses.
+///
+/// If nothing is received within the timeout, or if all senders are dropped before a value is received,
+/// the reason is printed and the macro panics.
+///
+/// ```
+/// # use assert2::{assert, assert_recv};
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// std::thread::spawn(move || sender.send(10).unwrap());
+/// let value = assert_recv!(receiver, timeout = 10s);
+/// assert!(value == 10);
+/// ```
+#[macro_export]
+macro_rules! assert_recv {
+	($receiver:expr, timeout = $timeout:tt $(,)?) => {
+		$crate::assert_recv!(@impl $receiver, $timeout, )
+	};
+	($receiver:expr, timeout = $timeout:tt, $($fmt:tt)+) => {
+		$crate::assert_recv!(@impl $receiver, $timeout, $($fmt)+)
+	};
+	(@impl $receiver:expr, $timeout:tt, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		let timeout: ::core::time::Duration = $crate::__assert2_impl::duration_impl!($timeout);
+		#[allow(unused_imports)]
+		use $crate::__assert2_impl::recv::{RecvMut, RecvTimeout};
+		match ($receiver).__assert2_recv(timeout) {
+			::core::result::Result::Ok(value) => value,
+			::core::result::Result::Err(error) => {
+				$crate::__assert2_fail!(@panic "assert_recv", site, $crate::__assert2_impl::print::Recv {
+					error,
+					timeout,
+					receiver_expr: $crate::__assert2_stringify!($receiver),
+					timeout_expr: $crate::__assert2_stringify!($timeout),
+				}, $($fmt)*);
+			},
+		}
+	}};
+}
+
+/// Assert that a `Poll` value is `Ready`, and evaluate to the contained value.
+///
+/// If the value is `Poll::Pending`, an assertion failure is printed and the macro panics.
+///
+/// With the `async` feature enabled, you can also pass a future to the macro.
+/// The future is polled exactly once with a waker that does nothing.
+/// Pass a mutable reference to a future that implements `Unpin` if you want to poll it again later.
+///
+/// ```
+/// # use assert2::{assert, assert_ready};
+/// # use std::task::Poll;
+/// let value = assert_ready!(Poll::Ready(10));
+/// assert!(value == 10);
+/// ```
+#[macro_export]
+macro_rules! assert_ready {
+	($poll:expr $(,)?) => {
+		$crate::assert_ready!(@impl $poll, )
+	};
+	($poll:expr, $($fmt:tt)+) => {
+		$crate::assert_ready!(@impl $poll, $($fmt)+)
+	};
+	(@impl $poll:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match $crate::__assert2_poll!($poll) {
+			::core::task::Poll::Ready(value) => value,
+			poll => {
+				$crate::__assert2_print_poll!("assert_ready", site, poll, $poll, $($fmt)*);
+				panic!("{}", $crate::__assert2_impl::panic_message("assertion failed"));
+			},
+		}
+	}};
+}
+
+/// Assert that a `Poll` value is `Pending`.
+///
+/// If the value is `Poll::Ready`, an assertion failure is printed with the ready value and the macro panics.
+///
+/// With the `async` feature enabled, you can also pass a future to the macro.
+/// The future is polled exactly once with a waker that does nothing.
+/// Pass a mutable reference to a future that implements `Unpin` if you want to poll it again later.
+///
+/// ```
+/// # use assert2::assert_pending;
+/// # use std::task::Poll;
+/// assert_pending!(Poll::<i32>::Pending);
+/// ```
+#[macro_export]
+macro_rules! assert_pending {
+	($poll:expr $(,)?) => {
+		$crate::assert_pending!(@impl $poll, )
+	};
+	($poll:expr, $($fmt:tt)+) => {
+		$crate::assert_pending!(@impl $poll, $($fmt)+)
+	};
+	(@impl $poll:expr, $($fmt:tt)*) => {{
+		let site = $crate::__assert2_site!();
+		match $crate::__assert2_poll!($poll) {
+			::core::task::Poll::Pending => (),
+			poll => {
+				$crate::__assert2_print_poll!("assert_pending", site, poll, $poll, $($fmt)*);
+				$crate::__assert2_panic!("assertion failed");
+			},
+		}
+	}};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert2_poll {
+	($poll:expr) => {{
+		#[allow(unused_imports)]
+		use $crate::__assert2_impl::poll::{PollFuture, PollValue};
+		$crate::__assert2_impl::poll::Wrap($poll).__assert2_poll()
+	}};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert2_print_poll {
+	($macro_name:literal, $site:ident, $value:ident, $poll:expr, $($fmt:tt)*) => {{
+		use $crate::__assert2_impl::maybe_debug::{IsAssertFormat, IsDebug, IsDebugAndDisplay, IsDisplay, IsMaybeNotDebug};
+		let value = (&&&&$crate::__assert2_impl::maybe_debug::Wrap(&$value)).__assert2_maybe_debug().wrap(&$value);
+		$crate::__assert2_fail!(@print $macro_name, $site, $crate::__assert2_impl::print::PollExpr {
+			value: &value,
+			expression: $crate::__assert2_stringify!($poll),
+		}, $($fmt)*);
+	}};
+}
+
+/// Assert at compile time that a type implements a set of traits.
+///
+/// The macro takes a type followed by a colon and trait bounds, just like a where clause.
+/// It does not generate any code at runtime, but it causes a compile error pointing at the assertion if the type does not implement the traits.
+///
+/// The macro can be used both inside and outside of functions.
+///
+/// ```
+/// # use assert2::assert_impl;
+/// assert_impl!(String: Clone + Send + Sync);
+/// assert_impl!(str: std::fmt::Display);
+/// assert_impl!(Vec<u8>: std::io::Write + 'static);
+/// ```
+///
+/// ```compile_fail
+/// # use assert2::assert_impl;
+/// assert_impl!(std::rc::Rc<u8>: Send);
+/// ```
+#[macro_export]
+macro_rules! assert_impl {
+	($type:ty: $($bounds:tt)+) => {
+		const _: fn() = || {
+			fn type_must_implement<T: ?::core::marker::Sized + $($bounds)+>() {}
+			type_must_implement::<$type>();
+		};
+	};
+}
+
+/// Assert at compile time that one or more types implement [`Send`].
+///
+/// This is a shorthand for [`assert_impl!(T: Send)`](macro.assert_impl.html).
+///
+/// ```
+/// # use assert2::assert_send;
+/// assert_send!(String, Vec<u8>, std::sync::Arc<str>);
+/// ```
+///
+/// ```compile_fail
+/// # use assert2::assert_send;
+/// assert_send!(std::rc::Rc<u8>);
+/// ```
+#[macro_export]
+macro_rules! assert_send {
+	($($type:ty),+ $(,)?) => {
+		$($crate::assert_impl!($type: ::core::marker::Send);)+
+	};
+}
+
+/// Assert at compile time that one or more types implement [`Sync`].
+///
+/// This is a shorthand for [`assert_impl!(T: Sync)`](macro.assert_impl.html).
+///
+/// ```
+/// # use assert2::assert_sync;
+/// assert_sync!(String, std::sync::Mutex<u8>);
+/// ```
+///
+/// ```compile_fail
+/// # use assert2::assert_sync;
+/// assert_sync!(std::cell::Cell<u8>);
+/// ```
+#[macro_export]
+macro_rules! assert_sync {
+	($($type:ty),+ $(,)?) => {
+		$($crate::assert_impl!($type: ::core::marker::Sync);)+
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert2_try_check_impl {
+	($macro_name:literal, $($tokens:tt)*) => {{
+		let silence = $crate::__assert2_impl::print::Silence::enter();
+		let result = $crate::__assert2_impl::check_impl!($crate, $macro_name, $($tokens)*);
+		drop(silence);
+		result.map(|_| ())
+	}}
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert2_panic {
+	($message:literal) => {
+		if $crate::__assert2_impl::should_panic() {
+			panic!("{}", $crate::__assert2_impl::panic_message($message));
+		}
+	};
+	($($args:tt)*) => {
+		if $crate::__assert2_impl::should_panic() {
+			panic!($($args)*);
+		}
+	};
+}
+
+/// Print a failed assertion of a declarative macro, and panic.
+///
+/// With `@panic`, the macro always panics, so it can be used where a value is expected.
+/// With `@print`, the failure is only printed.
+/// Otherwise, the macro panics like [`__assert2_panic!()`], which depends on the `PanickingPolicy` if the thread is already panicking.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert2_fail {
+	(@print $macro_name:expr, $site:expr, $expression:expr, $($fmt:tt)*) => {
+		$crate::__assert2_impl::print::FailedCheck {
+			macro_name: $macro_name,
+			file: ::core::panic::Location::caller().file(),
+			line: ::core::panic::Location::caller().line(),
+			column: ::core::panic::Location::caller().column(),
+			custom_msg: $crate::__assert2_custom_msg!($($fmt)*),
+			expression: $expression,
+			fragments: &[],
+			defaults: ::core::option_env!("ASSERT2_DEFAULTS"),
+			site: $site,
+		}.print()
+	};
+	(@panic $macro_name:expr, $site:expr, $expression:expr, $($fmt:tt)*) => {{
+		$crate::__assert2_fail!(@print $macro_name, $site, $expression, $($fmt)*);
+		panic!("{}", $crate::__assert2_impl::panic_message("assertion failed"))
+	}};
+	($macro_name:expr, $site:expr, $expression:expr, $($fmt:tt)*) => {{
+		$crate::__assert2_fail!(@print $macro_name, $site, $expression, $($fmt)*);
+		$crate::__assert2_panic!("assertion failed");
+	}};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert2_site {
+	() => {{
+		static SITE: $crate::__assert2_impl::site::SiteCounter = $crate::__assert2_impl::site::SiteCounter::new();
+		SITE.hit()
+	}};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert2_custom_msg {
+	() => {
+		::core::option::Option::None
+	};
+	($($fmt:tt)+) => {
+		::core::option::Option::Some(::core::format_args!($($fmt)+))
+	};
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __assert2_stringify {
@@ -0,0 +1,213 @@
+//! Composable matchers for [`assert_that!()`](crate::assert_that).
+//!
+//! A [`Matcher`] checks a value and describes what it expects, so that a failed assertion can explain exactly which condition was not met.
+//! Matchers can be combined with [`all!()`](crate::all), [`any!()`](crate::any) and [`not()`].
+//!
+//! You can implement the [`Matcher`] trait for your own types to create reusable matchers.
+//!
+//! ```
+//! use assert2::assert_that;
+//! use assert2::matchers::*;
+//!
+//! assert_that!(5, all!(gt(3), lt(10)));
+//! assert_that!("foo", any!(eq("foo"), eq("bar")));
+//! assert_that!(7, not(predicate(|x: &i32| x % 2 == 0, "is even")));
+//! ```
+
+use std::fmt::Debug;
+
+pub use crate::{all, any};
+
+/// A condition that can be checked for a value of type `T`.
+pub trait Matcher<T: ?Sized> {
+	/// Check if a value matches.
+	fn matches(&self, value: &T) -> bool;
+
+	/// Describe the values that match, for example `is greater than 3`.
+	fn describe(&self) -> String;
+
+	/// Explain why a value does not match.
+	///
+	/// The default implementation returns the description of the matcher.
+	/// Combinators override this to only mention the parts that did not match.
+	fn explain(&self, value: &T) -> String {
+		let _ = value;
+		self.describe()
+	}
+}
+
+/// Matcher for values that are equal to the expected value.
+pub fn eq<E: Debug>(expected: E) -> Eq<E> {
+	Eq(expected)
+}
+
+/// Matcher for values that are not equal to the expected value.
+pub fn ne<E: Debug>(expected: E) -> Ne<E> {
+	Ne(expected)
+}
+
+/// Matcher for values that are less than the expected value.
+pub fn lt<E: Debug>(expected: E) -> Lt<E> {
+	Lt(expected)
+}
+
+/// Matcher for values that are less than or equal to the expected value.
+pub fn le<E: Debug>(expected: E) -> Le<E> {
+	Le(expected)
+}
+
+/// Matcher for values that are greater than the expected value.
+pub fn gt<E: Debug>(expected: E) -> Gt<E> {
+	Gt(expected)
+}
+
+/// Matcher for values that are greater than or equal to the expected value.
+pub fn ge<E: Debug>(expected: E) -> Ge<E> {
+	Ge(expected)
+}
+
+/// Matcher for values that do not match the inner matcher.
+pub fn not<M>(matcher: M) -> Not<M> {
+	Not(matcher)
+}
+
+/// Matcher for values that satisfy a predicate.
+///
+/// The description is used in the output of failed assertions, for example `is even`.
+pub fn predicate<F>(predicate: F, description: &str) -> Predicate<F> {
+	Predicate {
+		predicate,
+		description: description.to_owned(),
+	}
+}
+
+macro_rules! comparison_matcher {
+	($(#[$meta:meta])* $name:ident, $trait:ident, $description:literal, |$value:ident, $expected:ident| $check:expr) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone)]
+		pub struct $name<E>(pub E);
+
+		impl<T: ?Sized + $trait<E>, E: Debug> Matcher<T> for $name<E> {
+			fn matches(&self, $value: &T) -> bool {
+				let $expected = &self.0;
+				$check
+			}
+
+			fn describe(&self) -> String {
+				format!(concat!($description, " {:?}"), self.0)
+			}
+		}
+	};
+}
+
+comparison_matcher!(
+	/// Matcher created by [`eq()`].
+	Eq, PartialEq, "is equal to", |value, expected| value == expected
+);
+comparison_matcher!(
+	/// Matcher created by [`ne()`].
+	Ne, PartialEq, "is not equal to", |value, expected| value != expected
+);
+comparison_matcher!(
+	/// Matcher created by [`lt()`].
+	Lt, PartialOrd, "is less than", |value, expected| value < expected
+);
+comparison_matcher!(
+	/// Matcher created by [`le()`].
+	Le, PartialOrd, "is less than or equal to", |value, expected| value <= expected
+);
+comparison_matcher!(
+	/// Matcher created by [`gt()`].
+	Gt, PartialOrd, "is greater than", |value, expected| value > expected
+);
+comparison_matcher!(
+	/// Matcher created by [`ge()`].
+	Ge, PartialOrd, "is greater than or equal to", |value, expected| value >= expected
+);
+
+/// Matcher created by [`not()`].
+#[derive(Debug, Clone)]
+pub struct Not<M>(pub M);
+
+impl<T: ?Sized, M: Matcher<T>> Matcher<T> for Not<M> {
+	fn matches(&self, value: &T) -> bool {
+		!self.0.matches(value)
+	}
+
+	fn describe(&self) -> String {
+		format!("not ({})", self.0.describe())
+	}
+}
+
+/// Matcher created by [`predicate()`].
+#[derive(Clone)]
+pub struct Predicate<F> {
+	predicate: F,
+	description: String,
+}
+
+impl<T: ?Sized, F: Fn(&T) -> bool> Matcher<T> for Predicate<F> {
+	fn matches(&self, value: &T) -> bool {
+		(self.predicate)(value)
+	}
+
+	fn describe(&self) -> String {
+		self.description.clone()
+	}
+}
+
+/// Matcher for values that match both inner matchers.
+///
+/// Use [`all!()`](crate::all) to combine any number of matchers.
+#[derive(Debug, Clone)]
+pub struct AllOf<A, B>(pub A, pub B);
+
+impl<T: ?Sized, A: Matcher<T>, B: Matcher<T>> Matcher<T> for AllOf<A, B> {
+	fn matches(&self, value: &T) -> bool {
+		self.0.matches(value) && self.1.matches(value)
+	}
+
+	fn describe(&self) -> String {
+		format!("{} and {}", self.0.describe(), self.1.describe())
+	}
+
+	fn explain(&self, value: &T) -> String {
+		match (self.0.matches(value), self.1.matches(value)) {
+			(true, _) => self.1.explain(value),
+			(false, true) => self.0.explain(value),
+			(false, false) => format!("{} and {}", self.0.explain(value), self.1.explain(value)),
+		}
+	}
+}
+
+/// Matcher for values that match at least one of the inner matchers.
+///
+/// Use [`any!()`](crate::any) to combine any number of matchers.
+#[derive(Debug, Clone)]
+pub struct AnyOf<A, B>(pub A, pub B);
+
+impl<T: ?Sized, A: Matcher<T>, B: Matcher<T>> Matcher<T> for AnyOf<A, B> {
+	fn matches(&self, value: &T) -> bool {
+		self.0.matches(value) || self.1.matches(value)
+	}
+
+	fn describe(&self) -> String {
+		format!("{} or {}", self.0.describe(), self.1.describe())
+	}
+}
+
+#[test]
+fn test_describe_and_explain() {
+	use crate::assert;
+	let matcher = AllOf(gt(3), AllOf(lt(10), ne(7)));
+	assert!(Matcher::<i32>::describe(&matcher) == "is greater than 3 and is less than 10 and is not equal to 7");
+	assert!(matcher.matches(&5));
+	assert!(!matcher.matches(&7));
+	assert!(matcher.explain(&7) == "is not equal to 7");
+	assert!(matcher.explain(&1) == "is greater than 3");
+
+	let matcher = not(AnyOf(eq("foo"), eq("bar")));
+	assert!(Matcher::<&str>::describe(&matcher) == "not (is equal to \"foo\" or is equal to \"bar\")");
+	assert!(!matcher.matches(&"foo"));
+	assert!(matcher.matches(&"baz"));
+}
@@ -0,0 +1,305 @@
+use std::cell::RefCell;
+use std::sync::RwLock;
+
+use crate::__assert2_impl::print::options::OutputStream;
+
+/// A function that handles the failed assertions instead of printing them.
+pub type FailureHandler = fn(&crate::Failure);
+
+static HANDLER: RwLock<Option<FailureHandler>> = RwLock::new(None);
+
+thread_local! {
+	/// Buffered output for the current thread, if buffering is enabled.
+	static BUFFER: RefCell<Buffer> = const { RefCell::new(Buffer { depth: 0, data: String::new(), stream: OutputStream::Stderr }) };
+
+	/// The last report printed on the current thread, to detect repeated failures.
+	static REPEATS: RefCell<Repeats> = const { RefCell::new(Repeats { last: None }) };
+}
+
+struct Buffer {
+	/// The number of active [`OutputBuffer`] guards.
+	depth: usize,
+
+	/// The buffered output.
+	data: String,
+
+	/// The stream to write the buffered output to.
+	stream: OutputStream,
+}
+
+struct Repeats {
+	/// The last report that was printed, if any.
+	last: Option<LastReport>,
+}
+
+struct LastReport {
+	/// The address of the counter of the assertion site, to identify the assertion.
+	site: usize,
+
+	/// The report that was printed.
+	report: String,
+
+	/// The number of identical reports that were not printed since the last message.
+	count: usize,
+
+	/// The stream the report was printed to.
+	stream: OutputStream,
+}
+
+impl LastReport {
+	/// Take the message for the identical reports that were not printed, if any.
+	fn take_message(&mut self) -> Option<String> {
+		let count = std::mem::take(&mut self.count);
+		if count == 0 {
+			return None;
+		}
+		let count = crate::__assert2_impl::site::format_count(count);
+		Some(format!("… and {count} more identical {}\n\n", if count == "1" { "failure" } else { "failures" }))
+	}
+}
+
+impl Drop for Repeats {
+	fn drop(&mut self) {
+		// The output buffer may already be destroyed, so write the message directly.
+		if let Some(last) = &mut self.last {
+			if let Some(message) = last.take_message() {
+				write_output(&message, last.stream);
+			}
+		}
+	}
+}
+
+/// Scope guard that buffers the output of failed assertions on the current thread.
+///
+/// While the guard exists, the reports of failed assertions on the current thread are not printed immediately.
+/// Instead, they are printed with a single write when the guard is dropped,
+/// or just before an assertion panics, whichever happens first.
+///
+/// This keeps the output of a test together, even when running tests in parallel with `--nocapture`.
+///
+/// Guards can be nested: the output is only written when the outermost guard is dropped.
+/// Use [`buffer_output()`] to create a guard.
+pub struct OutputBuffer {
+	_not_send: std::marker::PhantomData<*const ()>,
+}
+
+/// Buffer the output of failed assertions on the current thread until the returned guard is dropped.
+///
+/// See [`OutputBuffer`] for more details.
+///
+/// ```should_panic
+/// # use assert2::check;
+/// let _buffer = assert2::buffer_output();
+/// check!(1 == 2);
+/// check!(2 == 3);
+/// // Both failures are printed together when the first check panics at the end of the scope.
+/// ```
+pub fn buffer_output() -> OutputBuffer {
+	BUFFER.with(|buffer| buffer.borrow_mut().depth += 1);
+	OutputBuffer {
+		_not_send: std::marker::PhantomData,
+	}
+}
+
+impl Drop for OutputBuffer {
+	fn drop(&mut self) {
+		flush_repeats();
+		let (data, stream) = BUFFER.with(|buffer| {
+			let mut buffer = buffer.borrow_mut();
+			buffer.depth -= 1;
+			if buffer.depth == 0 {
+				(std::mem::take(&mut buffer.data), buffer.stream)
+			} else {
+				(String::new(), buffer.stream)
+			}
+		});
+		write_output(&data, stream);
+	}
+}
+
+/// Run a function without printing the reports of failed assertions on the current thread.
+///
+/// Failed assertions inside the function still fail as usual: [`assert!`](crate::assert) still panics and [`check!`](crate::check) still fails the test.
+/// Only the printing of the report is suppressed.
+/// This is useful for tests that intentionally trigger failures, to keep the expected reports out of the test log.
+///
+/// ```
+/// # use assert2::assert;
+/// let result = std::panic::catch_unwind(|| {
+///     assert2::silence(|| assert!(1 + 1 == 3));
+/// });
+/// assert!(result.is_err());
+/// ```
+pub fn silence<T>(function: impl FnOnce() -> T) -> T {
+	let _silence = crate::__assert2_impl::print::Silence::enter();
+	function()
+}
+
+/// Set a function that handles failed assertions instead of printing them.
+///
+/// The handler is called for every failed assertion that would otherwise be printed,
+/// including warnings from [`check!()`](crate::check) with `level = warn`.
+/// It is not called for assertions inside [`silence()`], or for [`try_check!()`](crate::try_check) and [`try_assert!()`](crate::try_assert).
+/// The handler applies to all threads, and replaces the printing for all of them.
+/// It does not change whether the assertion panics or fails the test.
+///
+/// The handler can be used to collect metrics, to write the failures to a custom log, or to abort the process.
+/// To print the report as well, print the [`Failure`](crate::Failure) from the handler.
+/// Pass `None` to restore the default behaviour.
+///
+/// ```
+/// assert2::set_failure_handler(Some(|failure| {
+///     eprintln!("{failure}");
+///     eprintln!("see https://example.com/docs/{} for help", failure.macro_name());
+/// }));
+/// # assert2::set_failure_handler(None);
+/// ```
+pub fn set_failure_handler(handler: Option<FailureHandler>) {
+	*HANDLER.write().unwrap_or_else(|e| e.into_inner()) = handler;
+}
+
+/// Get the configured failure handler, if any.
+pub(crate) fn failure_handler() -> Option<FailureHandler> {
+	*HANDLER.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Write the report of a failed assertion to a stream, or buffer it if output buffering is enabled.
+///
+/// Buffered output is written to the stream of the last buffered report.
+pub(crate) fn write_report(report: &str, stream: OutputStream) {
+	let buffered = BUFFER.with(|buffer| {
+		let mut buffer = buffer.borrow_mut();
+		if buffer.depth > 0 {
+			buffer.data.push_str(report);
+			buffer.stream = stream;
+			true
+		} else {
+			false
+		}
+	});
+	if !buffered {
+		write_output(report, stream);
+	}
+}
+
+/// Write the report of a failed assertion, unless it is identical to the last report of the same assertion.
+///
+/// Identical reports are counted instead, and the count is printed before the next different report,
+/// when the output is flushed for a panic, or when the thread exits.
+/// This keeps a failing assertion in a loop from printing hundreds of copies of the same report.
+pub(crate) fn write_deduplicated_report(site: &crate::__assert2_impl::site::SiteCounter, report: &str, stream: OutputStream) {
+	let site = site as *const _ as usize;
+	let repeated = REPEATS.with(|repeats| match &mut repeats.borrow_mut().last {
+		Some(last) if last.site == site && last.report == report => {
+			last.count += 1;
+			true
+		},
+		_ => false,
+	});
+	if repeated {
+		return;
+	}
+	flush_repeats();
+	write_report(report, stream);
+	REPEATS.with(|repeats| {
+		repeats.borrow_mut().last = Some(LastReport {
+			site,
+			report: report.to_owned(),
+			count: 0,
+			stream,
+		});
+	});
+}
+
+/// Write the number of identical reports that were not printed, if any.
+fn flush_repeats() {
+	let message = REPEATS.with(|repeats| {
+		let mut repeats = repeats.borrow_mut();
+		let last = repeats.last.as_mut()?;
+		Some((last.take_message()?, last.stream))
+	});
+	if let Some((message, stream)) = message {
+		write_report(&message, stream);
+	}
+}
+
+/// Write all buffered output of the current thread immediately.
+pub(crate) fn flush() {
+	flush_repeats();
+	let (data, stream) = BUFFER.with(|buffer| {
+		let mut buffer = buffer.borrow_mut();
+		(std::mem::take(&mut buffer.data), buffer.stream)
+	});
+	write_output(&data, stream);
+}
+
+/// Write data to stderr or stdout with a single call, unless it is empty.
+///
+/// With the `android-log` feature, the data is written to logcat on Android instead.
+fn write_output(data: &str, stream: OutputStream) {
+	if data.is_empty() {
+		return;
+	}
+	#[cfg(all(feature = "android-log", target_os = "android"))]
+	{
+		let _ = stream;
+		android::write(data);
+	}
+	#[cfg(not(all(feature = "android-log", target_os = "android")))]
+	match stream {
+		OutputStream::Stderr => eprint!("{data}"),
+		OutputStream::Stdout => print!("{data}"),
+	}
+}
+
+#[cfg(all(feature = "android-log", target_os = "android"))]
+mod android {
+	use std::ffi::CString;
+	use std::os::raw::{c_char, c_int};
+
+	/// The `ANDROID_LOG_ERROR` priority from `android/log.h`.
+	const ANDROID_LOG_ERROR: c_int = 6;
+
+	#[link(name = "log")]
+	extern "C" {
+		fn __android_log_write(priority: c_int, tag: *const c_char, text: *const c_char) -> c_int;
+	}
+
+	/// Write data to logcat, one line per log message.
+	///
+	/// Logcat truncates long messages and does not keep the indentation of continued lines,
+	/// so each line is written as a separate message.
+	pub fn write(data: &str) {
+		let tag = CString::new("assert2").unwrap();
+		for line in data.lines() {
+			let line = CString::new(line.replace('\0', "\\0")).unwrap();
+			// SAFETY: Both pointers point to valid nul-terminated strings that outlive the call.
+			unsafe {
+				__android_log_write(ANDROID_LOG_ERROR, tag.as_ptr(), line.as_ptr());
+			}
+		}
+	}
+}
+
+#[test]
+fn test_deduplicate_reports() {
+	use crate::assert;
+	use crate::__assert2_impl::site::SiteCounter;
+
+	static FIRST: SiteCounter = SiteCounter::new();
+	static SECOND: SiteCounter = SiteCounter::new();
+	let take_buffer = || BUFFER.with(|buffer| std::mem::take(&mut buffer.borrow_mut().data));
+
+	let _buffer = buffer_output();
+	write_deduplicated_report(&FIRST, "first\n", OutputStream::Stderr);
+	write_deduplicated_report(&FIRST, "first\n", OutputStream::Stderr);
+	write_deduplicated_report(&FIRST, "first\n", OutputStream::Stderr);
+	write_deduplicated_report(&SECOND, "first\n", OutputStream::Stderr);
+	write_deduplicated_report(&SECOND, "second\n", OutputStream::Stderr);
+	write_deduplicated_report(&SECOND, "second\n", OutputStream::Stderr);
+	assert!(take_buffer() == "first\n… and 2 more identical failures\n\nfirst\nsecond\n");
+	flush_repeats();
+	assert!(take_buffer() == "… and 1 more identical failure\n\n");
+	flush_repeats();
+	assert!(take_buffer() == "");
+}
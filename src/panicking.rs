@@ -0,0 +1,51 @@
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+static POLICY: AtomicU8 = AtomicU8::new(PanickingPolicy::Suppress as u8);
+static SUPPRESSED: AtomicUsize = AtomicUsize::new(0);
+
+/// What to do when an assertion fails while the current thread is already panicking.
+///
+/// This happens when an assertion fails in a `Drop` implementation that runs during unwinding.
+/// Panicking while already panicking aborts the process,
+/// which can cause the output of the original panic to be lost.
+///
+/// The assertion failure is always printed in full before the policy is applied.
+/// The policy does not affect macros that must produce a value, such as [`let_assert!()`](macro.let_assert.html) and [`assert_ready!()`](macro.assert_ready.html).
+/// Those always panic when the assertion fails.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(u8)]
+pub enum PanickingPolicy {
+	/// Do not panic, but count the failure in [`suppressed_failures()`] (default).
+	Suppress,
+
+	/// Panic anyway, which aborts the process.
+	Abort,
+}
+
+/// Set the policy for assertions that fail while the current thread is already panicking.
+///
+/// The policy applies to all threads.
+/// See [`PanickingPolicy`] for more details.
+pub fn set_panicking_policy(policy: PanickingPolicy) {
+	POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// Get the number of failed assertions that did not panic because the thread was already panicking.
+///
+/// The count is shared by all threads in the process.
+pub fn suppressed_failures() -> usize {
+	SUPPRESSED.load(Ordering::Relaxed)
+}
+
+/// Check if a failed assertion should panic, according to the configured policy.
+///
+/// If the assertion should panic, any buffered output is written first,
+/// so that it appears before the panic message.
+pub(crate) fn should_panic() -> bool {
+	if !std::thread::panicking() || POLICY.load(Ordering::Relaxed) == PanickingPolicy::Abort as u8 {
+		crate::output::flush();
+		return true;
+	}
+	SUPPRESSED.fetch_add(1, Ordering::Relaxed);
+	false
+}
@@ -0,0 +1,96 @@
+use std::sync::RwLock;
+
+/// A function that removes sensitive data from text.
+type Redactor = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+static REDACTORS: RwLock<Vec<Redactor>> = RwLock::new(Vec::new());
+
+/// The text that replaces redacted data.
+const REDACTED: &str = "<redacted>";
+
+/// Add a function that removes sensitive data from the output of failed assertions.
+///
+/// The function receives text that is about to be printed and returns the text to print instead.
+/// It is applied to the formatted values before they are compared and diffed,
+/// and to the complete output, including the expression, the custom message and the run context.
+/// The output can contain color codes, so prefer to match the sensitive data itself rather than its surroundings.
+///
+/// Redactors are global: they apply to all threads until [`clear_redactors()`] is called.
+///
+/// To redact all values of a specific type, implement [`AssertFormat`](crate::AssertFormat) for it instead.
+///
+/// ```
+/// assert2::add_redactor(|text| text.replace("hunter2", "*******"));
+/// # assert2::clear_redactors();
+/// ```
+pub fn add_redactor(redactor: impl Fn(&str) -> String + Send + Sync + 'static) {
+	REDACTORS.write().unwrap_or_else(|e| e.into_inner()).push(Box::new(redactor));
+}
+
+/// Redact the word that follows every occurrence of `prefix` in the output of failed assertions.
+///
+/// The word ends at whitespace, a quote, a backslash or a closing delimiter.
+/// For example, `redact_after("Bearer ")` turns `"Authorization: Bearer abc.def"` into `"Authorization: Bearer <redacted>"`.
+///
+/// ```
+/// assert2::redact_after("Bearer ");
+/// # assert2::clear_redactors();
+/// ```
+pub fn redact_after(prefix: impl Into<String>) {
+	let prefix = prefix.into();
+	add_redactor(move |text| redact_words_after(text, &prefix));
+}
+
+/// Remove all redactors added with [`add_redactor()`] or [`redact_after()`].
+pub fn clear_redactors() {
+	REDACTORS.write().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Apply all registered redactors to the text.
+pub(crate) fn redact(text: String) -> String {
+	let redactors = REDACTORS.read().unwrap_or_else(|e| e.into_inner());
+	redactors.iter().fold(text, |text, redactor| redactor(&text))
+}
+
+/// Replace the word after every occurrence of `prefix` with a placeholder.
+fn redact_words_after(text: &str, prefix: &str) -> String {
+	if prefix.is_empty() {
+		return text.into();
+	}
+	let mut output = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(start) = rest.find(prefix) {
+		let (before, after) = rest.split_at(start + prefix.len());
+		output.push_str(before);
+		// Text is redacted more than once, so leave earlier placeholders alone.
+		if after.starts_with(REDACTED) {
+			rest = after;
+			continue;
+		}
+		let end = after
+			.find(|c: char| c.is_whitespace() || "\"'\\,;)]}>".contains(c))
+			.unwrap_or(after.len());
+		if end > 0 {
+			output.push_str(REDACTED);
+		}
+		rest = &after[end..];
+	}
+	output.push_str(rest);
+	output
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::assert;
+
+	#[test]
+	fn test_redact_words_after() {
+		assert!(redact_words_after("Authorization: Bearer abc.def", "Bearer ") == "Authorization: Bearer <redacted>");
+		assert!(redact_words_after("\"Bearer abc\", \"Bearer xyz\"", "Bearer ") == "\"Bearer <redacted>\", \"Bearer <redacted>\"");
+		assert!(redact_words_after("Config { token: \"abc\" }", "token: \"") == "Config { token: \"<redacted>\" }");
+		assert!(redact_words_after("no secrets here", "Bearer ") == "no secrets here");
+		assert!(redact_words_after("Bearer ", "Bearer ") == "Bearer ");
+		assert!(redact_words_after("Bearer <redacted>", "Bearer ") == "Bearer <redacted>");
+	}
+}
@@ -0,0 +1,101 @@
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// A failed assertion recorded for the SARIF log.
+struct Record {
+	/// If true, the assertion only printed a warning.
+	warning: bool,
+
+	/// The failed assertion.
+	failure: crate::Failure,
+}
+
+static RECORDS: Mutex<Vec<Record>> = Mutex::new(Vec::new());
+
+/// The environment variable with the path of the SARIF log.
+const PATH_VAR: &str = "ASSERT2_SARIF";
+
+/// Record a failed assertion and write the SARIF log, if the `ASSERT2_SARIF` environment variable is set.
+///
+/// The log is rewritten with all failures so far after every failure,
+/// so that it is complete even if the process is aborted by a panic.
+pub(crate) fn record(failure: &crate::Failure, warning: bool) {
+	let Some(path) = std::env::var_os(PATH_VAR) else {
+		return;
+	};
+	let path = path.to_string_lossy().replace("{pid}", &std::process::id().to_string());
+
+	let mut records = RECORDS.lock().unwrap_or_else(|e| e.into_inner());
+	records.push(Record { warning, failure: failure.clone() });
+	if let Err(e) = std::fs::write(&path, render(&records)) {
+		eprintln!("assert2: failed to write SARIF log to {path}: {e}");
+	}
+}
+
+/// Render the SARIF log for the recorded failures.
+fn render(records: &[Record]) -> String {
+	let mut output = String::new();
+	writeln!(output, "{{").unwrap();
+	writeln!(output, r#"  "$schema": "https://json.schemastore.org/sarif-2.1.0.json","#).unwrap();
+	writeln!(output, r#"  "version": "2.1.0","#).unwrap();
+	writeln!(output, r#"  "runs": [{{"#).unwrap();
+	writeln!(output, r#"    "tool": {{"driver": {{"name": "assert2", "version": {}, "informationUri": "https://github.com/de-vri-es/assert2-rs"}}}},"#, quote(env!("CARGO_PKG_VERSION"))).unwrap();
+	writeln!(output, r#"    "results": ["#).unwrap();
+	for (i, Record { warning, failure }) in records.iter().enumerate() {
+		writeln!(output, "      {{").unwrap();
+		writeln!(output, r#"        "ruleId": {},"#, quote(failure.macro_name())).unwrap();
+		writeln!(output, r#"        "level": "{}","#, if *warning { "warning" } else { "error" }).unwrap();
+		writeln!(output, r#"        "message": {{"text": {}}},"#, quote(failure.report().trim_end())).unwrap();
+		writeln!(output, r#"        "locations": [{{"physicalLocation": {{"artifactLocation": {{"uri": {}}}, "region": {{"startLine": {}, "startColumn": {}}}}}}}]"#,
+			quote(&failure.file().replace('\\', "/")),
+			failure.line(),
+			failure.column(),
+		).unwrap();
+		writeln!(output, "      }}{}", if i + 1 < records.len() { "," } else { "" }).unwrap();
+	}
+	writeln!(output, "    ]").unwrap();
+	writeln!(output, "  }}]").unwrap();
+	writeln!(output, "}}").unwrap();
+	output
+}
+
+/// Quote a string as a JSON string.
+fn quote(value: &str) -> String {
+	let mut output = String::with_capacity(value.len() + 2);
+	output.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => output.push_str("\\\""),
+			'\\' => output.push_str("\\\\"),
+			'\n' => output.push_str("\\n"),
+			'\r' => output.push_str("\\r"),
+			'\t' => output.push_str("\\t"),
+			c if c.is_control() => write!(output, "\\u{:04x}", c as u32).unwrap(),
+			c => output.push(c),
+		}
+	}
+	output.push('"');
+	output
+}
+
+#[test]
+fn test_render_sarif() {
+	use crate::assert;
+	let failure = crate::Failure::new("check", "src\\lib.rs", 12, 3, "a == \"b\"", None, "Assertion failed at src\\lib.rs:12:3:\n  check!( a == \"b\" )\n\n");
+	let records = [
+		Record { warning: false, failure: failure.clone() },
+		Record { warning: true, failure },
+	];
+	let sarif = render(&records);
+	assert!(sarif.starts_with("{\n  \"$schema\": \"https://json.schemastore.org/sarif-2.1.0.json\",\n  \"version\": \"2.1.0\",\n"));
+	assert!(sarif.contains(concat!(
+		"      {\n",
+		"        \"ruleId\": \"check\",\n",
+		"        \"level\": \"error\",\n",
+		"        \"message\": {\"text\": \"Assertion failed at src\\\\lib.rs:12:3:\\n  check!( a == \\\"b\\\" )\"},\n",
+		"        \"locations\": [{\"physicalLocation\": {\"artifactLocation\": {\"uri\": \"src/lib.rs\"}, \"region\": {\"startLine\": 12, \"startColumn\": 3}}}]\n",
+		"      },\n",
+	)));
+	assert!(sarif.contains("        \"level\": \"warning\",\n"));
+	assert!(sarif.ends_with("      }\n    ]\n  }]\n}\n"));
+}
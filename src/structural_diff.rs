@@ -0,0 +1,108 @@
+use std::fmt::Debug;
+
+/// Trait to compare two values field by field, used to report only the fields that differ.
+///
+/// When a `check!(a == b)` fails and the type of both sides implements this trait,
+/// the failure lists the paths of the fields that differ instead of a diff of the full `Debug` output.
+///
+/// With the `serde` feature enabled, values that implement `serde::Serialize` but not this trait are also compared field by field.
+/// Both sides are serialized and the serialized trees are compared, so the differences show the serialized field names and values.
+/// Map entries are compared by key and sequences element by element, with `<missing>` for entries that only exist on one side.
+///
+/// You normally derive this trait with [`#[derive(AssertDiff)]`](derive.AssertDiff.html).
+/// The derived implementation recurses into fields that implement `StructuralDiff` themselves,
+/// and compares all other fields with `PartialEq`, so they must implement `PartialEq` and `Debug`.
+///
+/// ```
+/// # use assert2::{let_assert, try_check, AssertDiff};
+/// #[derive(Debug, PartialEq, AssertDiff)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Debug, PartialEq, AssertDiff)]
+/// struct Config {
+///     name: String,
+///     server: Server,
+/// }
+///
+/// let config = Config { name: "main".into(), server: Server { host: "localhost".into(), port: 80 } };
+/// let expected = Config { name: "main".into(), server: Server { host: "localhost".into(), port: 8080 } };
+/// let_assert!(Err(failure) = try_check!(config == expected));
+/// assert!(failure.report().contains("server.port: 80 != 8080"));
+/// ```
+///
+/// # Field attributes
+///
+/// The derived comparison can be changed per field:
+///
+/// * `#[assert2(skip)]` does not compare the field at all, which is useful for timestamps or generated IDs.
+/// * `#[assert2(compare_with = path)]` compares the field with a function `fn(&T, &T) -> bool`.
+///   The field is reported as different if the function returns `false`, so it must still implement `Debug`.
+///
+/// These attributes only affect the field differences, not the `PartialEq` implementation used by `check!(a == b)`.
+/// To ignore the same fields in equality checks, implement `PartialEq` with [`structural_eq()`](Self::structural_eq):
+///
+/// ```
+/// # use assert2::{assert, AssertDiff, StructuralDiff};
+/// fn roughly_equal(a: &f64, b: &f64) -> bool {
+///     (a - b).abs() < 1e-6
+/// }
+///
+/// #[derive(Debug, AssertDiff)]
+/// struct Measurement {
+///     #[assert2(skip)]
+///     timestamp: u64,
+///     #[assert2(compare_with = roughly_equal)]
+///     value: f64,
+/// }
+///
+/// impl PartialEq for Measurement {
+///     fn eq(&self, other: &Self) -> bool {
+///         self.structural_eq(other)
+///     }
+/// }
+///
+/// assert!(Measurement { timestamp: 1, value: 0.3 } == Measurement { timestamp: 2, value: 0.1 + 0.2 });
+/// ```
+pub trait StructuralDiff {
+	/// Compare `self` with `other` and add the fields that differ to `diffs`.
+	///
+	/// The `path` is the path of `self` in the outermost value, which is empty for the outermost value itself.
+	fn structural_diff(&self, other: &Self, path: &str, diffs: &mut Vec<FieldDiff>);
+
+	/// Check if `self` and `other` have no differences according to [`structural_diff()`](Self::structural_diff).
+	fn structural_eq(&self, other: &Self) -> bool {
+		let mut diffs = Vec::new();
+		self.structural_diff(other, "", &mut diffs);
+		diffs.is_empty()
+	}
+}
+
+/// A single field that differs between two values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+	/// The path of the field, like `server.port`.
+	///
+	/// The path is empty if the outermost values differ as a whole, like for different enum variants.
+	pub path: String,
+
+	/// The `Debug` output of the field of the left value.
+	pub left: String,
+
+	/// The `Debug` output of the field of the right value.
+	pub right: String,
+}
+
+impl FieldDiff {
+	/// Create a difference for a value that can not be compared field by field.
+	pub fn new<T: Debug + ?Sized>(path: &str, left: &T, right: &T) -> Self {
+		use crate::__assert2_impl::print::format_debug;
+		Self {
+			path: path.into(),
+			left: format_debug(&left, false),
+			right: format_debug(&right, false),
+		}
+	}
+}
@@ -0,0 +1,118 @@
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// The failures of a single assertion.
+struct Entry {
+	/// The failed assertion, as written in the source code.
+	assertion: String,
+
+	/// The location of the assertion.
+	location: String,
+
+	/// The number of times the assertion failed.
+	count: usize,
+}
+
+static ENTRIES: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+/// Print a summary of all failed assertions in the process so far.
+///
+/// The summary lists each failed assertion once, with its location and the number of times it failed,
+/// in the order in which the assertions first failed.
+/// It includes assertions that failed without panicking, like [`check!()`](crate::check),
+/// but not warnings or assertions inside [`silence()`](crate::silence).
+/// Nothing is printed if no assertion failed.
+///
+/// With the `summary` option in the `ASSERT2` environment variable, the summary is printed automatically when the process exits.
+/// You can also call this function from a custom test harness, for example at the end of a test run.
+///
+/// ```
+/// assert2::print_summary();
+/// ```
+pub fn print_summary() {
+	let entries = ENTRIES.lock().unwrap_or_else(|e| e.into_inner());
+	if !entries.is_empty() {
+		// Write directly to stderr: this can run when the process exits, after thread local storage is destroyed.
+		eprint!("{}", render(&entries));
+	}
+}
+
+/// Record a failed assertion for the summary.
+///
+/// If `at_exit` is true, the summary is printed when the process exits.
+pub(crate) fn record(failure: &crate::Failure, at_exit: bool) {
+	if at_exit {
+		print_at_exit();
+	}
+	let location = format!("{}:{}:{}", failure.file(), failure.line(), failure.column());
+	let mut entries = ENTRIES.lock().unwrap_or_else(|e| e.into_inner());
+	match entries.iter_mut().find(|entry| entry.location == location) {
+		Some(entry) => entry.count += 1,
+		None => entries.push(Entry {
+			assertion: format!("{}!( {} )", failure.macro_name(), failure.expression()),
+			location,
+			count: 1,
+		}),
+	}
+}
+
+/// Print the summary when the process exits.
+///
+/// The standard library has no hook for the end of the process, so this uses `atexit()` from the C runtime.
+#[cfg(any(unix, windows))]
+fn print_at_exit() {
+	extern "C" {
+		fn atexit(callback: extern "C" fn()) -> std::os::raw::c_int;
+	}
+
+	extern "C" fn callback() {
+		// Unwinding out of the callback would abort the process.
+		let _ = std::panic::catch_unwind(print_summary);
+	}
+
+	static REGISTER: std::sync::Once = std::sync::Once::new();
+	// SAFETY: The callback is a valid function that does not unwind.
+	REGISTER.call_once(|| unsafe {
+		atexit(callback);
+	});
+}
+
+/// Print the summary when the process exits.
+///
+/// There is no C runtime to register an exit handler with on this platform, so the summary must be printed manually.
+#[cfg(not(any(unix, windows)))]
+fn print_at_exit() {}
+
+/// Render the summary table of the failed assertions.
+fn render(entries: &[Entry]) -> String {
+	let location_width = entries.iter().map(|entry| entry.location.chars().count()).max().unwrap_or(0);
+	let count_width = entries.iter().map(|entry| entry.count.to_string().len()).max().unwrap_or(0).max("count".len());
+
+	let mut output = String::new();
+	let total: usize = entries.iter().map(|entry| entry.count).sum();
+	writeln!(output, "Summary of failed assertions: {} {} failed {total} {}",
+		entries.len(),
+		if entries.len() == 1 { "assertion" } else { "assertions" },
+		if total == 1 { "time" } else { "times" },
+	).unwrap();
+	writeln!(output, "  {:>count_width$}  {:location_width$}  assertion", "count", "location").unwrap();
+	for entry in entries {
+		writeln!(output, "  {:>count_width$}  {:location_width$}  {}", entry.count, entry.location, entry.assertion).unwrap();
+	}
+	output
+}
+
+#[test]
+fn test_render_summary() {
+	use crate::assert;
+	let entries = [
+		Entry { assertion: "check!( a == b )".into(), location: "tests/foo.rs:12:5".into(), count: 148 },
+		Entry { assertion: "assert!( x )".into(), location: "src/lib.rs:3:2".into(), count: 1 },
+	];
+	assert!(render(&entries) == concat!(
+		"Summary of failed assertions: 2 assertions failed 149 times\n",
+		"  count  location           assertion\n",
+		"    148  tests/foo.rs:12:5  check!( a == b )\n",
+		"      1  src/lib.rs:3:2     assert!( x )\n",
+	));
+}
@@ -0,0 +1,53 @@
+use std::sync::RwLock;
+
+/// The size of a terminal in columns and rows.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TerminalSize {
+	/// The width of the terminal in columns.
+	pub width: usize,
+
+	/// The height of the terminal in rows.
+	pub height: usize,
+}
+
+/// A function that reports the size of the terminal, if known.
+pub type TerminalSizeProvider = fn() -> Option<TerminalSize>;
+
+static PROVIDER: RwLock<Option<TerminalSizeProvider>> = RwLock::new(None);
+
+/// Set the function used to determine the size of the terminal.
+///
+/// The terminal size is used to lay out parts of the failure output, such as the number of bytes per row of a hexdump.
+/// By default, the width is taken from the `width=N` option in the `ASSERT2` environment variable,
+/// or from the `COLUMNS` environment variable, and the height is taken from the `LINES` environment variable.
+///
+/// You can use this function to report the correct size in environments where that doesn't work,
+/// or to force a fixed size to get the same output on every machine.
+/// Pass `None` to restore the default behaviour.
+///
+/// ```
+/// use assert2::TerminalSize;
+/// assert2::set_terminal_size_provider(Some(|| Some(TerminalSize { width: 120, height: 40 })));
+/// ```
+pub fn set_terminal_size_provider(provider: Option<TerminalSizeProvider>) {
+	*PROVIDER.write().unwrap_or_else(|e| e.into_inner()) = provider;
+}
+
+/// Get the size of the terminal using the configured provider.
+pub(crate) fn size() -> Option<TerminalSize> {
+	let provider = *PROVIDER.read().unwrap_or_else(|e| e.into_inner());
+	match provider {
+		Some(provider) => provider(),
+		None => size_from_env(),
+	}
+}
+
+/// Get the size of the terminal from the `ASSERT2`, `COLUMNS` and `LINES` environment variables.
+fn size_from_env() -> Option<TerminalSize> {
+	fn parse(name: &str) -> Option<usize> {
+		std::env::var(name).ok()?.trim().parse().ok()
+	}
+	let width = crate::__assert2_impl::print::options::AssertOptions::get().width.or_else(|| parse("COLUMNS"))?;
+	let height = parse("LINES").unwrap_or(24);
+	Some(TerminalSize { width, height })
+}
@@ -0,0 +1,405 @@
+use std::sync::RwLock;
+use yansi::{Paint, Painted};
+
+use crate::__assert2_impl::print::options::ColorPalette;
+
+/// A color used by a [`Theme`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ThemeColor {
+	/// The default foreground color of the terminal.
+	Primary,
+	Black,
+	Red,
+	Green,
+	Yellow,
+	Blue,
+	Magenta,
+	Cyan,
+	White,
+	BrightBlack,
+	BrightRed,
+	BrightGreen,
+	BrightYellow,
+	BrightBlue,
+	BrightMagenta,
+	BrightCyan,
+	BrightWhite,
+
+	/// A color from the 256 color palette of the terminal.
+	Fixed(u8),
+
+	/// A true color, given as red, green and blue components.
+	Rgb(u8, u8, u8),
+}
+
+/// The colors used to print failed assertions.
+///
+/// The default theme prints the left side of a comparison in cyan and the right side in yellow.
+/// If that is hard to read with your terminal color scheme, you can change the colors with the `ASSERT2_THEME` environment variable,
+/// or with [`set_theme()`].
+///
+/// The `ASSERT2_THEME` environment variable holds a comma separated list of `role=color` pairs,
+/// where the roles are the fields of this struct.
+/// The color can be a name like `magenta` or `bright-green`, a number from the 256 color palette, or a hex code like `#ff8800`.
+/// For example: `ASSERT2_THEME=left=magenta,right=green`.
+/// Roles that are not mentioned keep their default color, and invalid entries are ignored.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Theme {
+	/// The color for the left side of a comparison, and for the expression of an assertion.
+	pub left: ThemeColor,
+
+	/// The color for the right side of a comparison, and for the value of an expression.
+	pub right: ThemeColor,
+
+	/// The color for operators.
+	pub operator: ThemeColor,
+
+	/// The color for names, like the name of the macro and of captured variables.
+	pub name: ThemeColor,
+
+	/// Underline highlighted differences, so that they can be seen without relying on color.
+	pub underline: bool,
+}
+
+impl Theme {
+	/// The default theme.
+	pub const DEFAULT: Theme = Theme {
+		left: ThemeColor::Cyan,
+		right: ThemeColor::Yellow,
+		operator: ThemeColor::Blue,
+		name: ThemeColor::Magenta,
+		underline: false,
+	};
+
+	/// A theme for color blind users.
+	///
+	/// The left and right side of a comparison are printed in blue and orange, which can be told apart with the common forms of color blindness.
+	/// Highlighted differences are also underlined.
+	/// This theme is selected with the `theme=colorblind` option in the `ASSERT2` environment variable.
+	pub const COLORBLIND: Theme = Theme {
+		left: ThemeColor::Fixed(33),
+		right: ThemeColor::Fixed(214),
+		operator: ThemeColor::Primary,
+		name: ThemeColor::Primary,
+		underline: true,
+	};
+
+	/// The color blind theme for terminals that only support the basic 16 colors.
+	const COLORBLIND_BASIC: Theme = Theme {
+		left: ThemeColor::BrightBlue,
+		right: ThemeColor::Yellow,
+		..Self::COLORBLIND
+	};
+
+	/// Parse a theme from a comma separated list of `role=color` pairs, starting from the default theme.
+	///
+	/// Invalid entries are ignored.
+	///
+	/// ```
+	/// # use assert2::{Theme, ThemeColor};
+	/// let theme = Theme::parse("left=magenta, right=#00ff00");
+	/// assert2::assert!(theme.left == ThemeColor::Magenta);
+	/// assert2::assert!(theme.right == ThemeColor::Rgb(0, 255, 0));
+	/// assert2::assert!(theme.operator == Theme::DEFAULT.operator);
+	/// ```
+	pub fn parse(spec: &str) -> Self {
+		Self::DEFAULT.with_spec(spec)
+	}
+
+	/// Change the colors of the theme from a comma separated list of `role=color` pairs.
+	fn with_spec(self, spec: &str) -> Self {
+		let mut theme = self;
+		for entry in spec.split(',') {
+			let Some((role, color)) = entry.split_once('=') else {
+				continue;
+			};
+			let Some(color) = ThemeColor::parse(color.trim()) else {
+				continue;
+			};
+			match role.trim().to_ascii_lowercase().as_str() {
+				"left" => theme.left = color,
+				"right" => theme.right = color,
+				"operator" => theme.operator = color,
+				"name" => theme.name = color,
+				_ => (),
+			}
+		}
+		theme
+	}
+}
+
+impl Default for Theme {
+	fn default() -> Self {
+		Self::DEFAULT
+	}
+}
+
+impl ThemeColor {
+	/// Parse a color name, palette index or hex code.
+	fn parse(color: &str) -> Option<Self> {
+		if let Some(hex) = color.strip_prefix('#') {
+			if hex.len() != 6 || !hex.is_ascii() {
+				return None;
+			}
+			let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+			return Some(Self::Rgb(component(0)?, component(2)?, component(4)?));
+		}
+		if let Ok(index) = color.parse() {
+			return Some(Self::Fixed(index));
+		}
+		let color = color.to_ascii_lowercase().replace('_', "-");
+		let color = match color.as_str() {
+			"primary" | "default" => Self::Primary,
+			"black" => Self::Black,
+			"red" => Self::Red,
+			"green" => Self::Green,
+			"yellow" => Self::Yellow,
+			"blue" => Self::Blue,
+			"magenta" => Self::Magenta,
+			"cyan" => Self::Cyan,
+			"white" => Self::White,
+			"bright-black" => Self::BrightBlack,
+			"bright-red" => Self::BrightRed,
+			"bright-green" => Self::BrightGreen,
+			"bright-yellow" => Self::BrightYellow,
+			"bright-blue" => Self::BrightBlue,
+			"bright-magenta" => Self::BrightMagenta,
+			"bright-cyan" => Self::BrightCyan,
+			"bright-white" => Self::BrightWhite,
+			_ => return None,
+		};
+		Some(color)
+	}
+
+	/// Convert the color to the color type of `yansi`.
+	fn to_yansi(self) -> yansi::Color {
+		match self {
+			Self::Primary => yansi::Color::Primary,
+			Self::Black => yansi::Color::Black,
+			Self::Red => yansi::Color::Red,
+			Self::Green => yansi::Color::Green,
+			Self::Yellow => yansi::Color::Yellow,
+			Self::Blue => yansi::Color::Blue,
+			Self::Magenta => yansi::Color::Magenta,
+			Self::Cyan => yansi::Color::Cyan,
+			Self::White => yansi::Color::White,
+			Self::BrightBlack => yansi::Color::BrightBlack,
+			Self::BrightRed => yansi::Color::BrightRed,
+			Self::BrightGreen => yansi::Color::BrightGreen,
+			Self::BrightYellow => yansi::Color::BrightYellow,
+			Self::BrightBlue => yansi::Color::BrightBlue,
+			Self::BrightMagenta => yansi::Color::BrightMagenta,
+			Self::BrightCyan => yansi::Color::BrightCyan,
+			Self::BrightWhite => yansi::Color::BrightWhite,
+			Self::Fixed(index) => yansi::Color::Fixed(index),
+			Self::Rgb(r, g, b) => yansi::Color::Rgb(r, g, b),
+		}
+	}
+}
+
+static OVERRIDE: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// Set the colors used to print failed assertions.
+///
+/// This takes precedence over the `ASSERT2_THEME` environment variable.
+/// Pass `None` to restore the default behaviour.
+///
+/// ```
+/// use assert2::{Theme, ThemeColor};
+/// assert2::set_theme(Some(Theme { left: ThemeColor::Magenta, right: ThemeColor::Green, ..Theme::DEFAULT }));
+/// # assert2::set_theme(None);
+/// ```
+pub fn set_theme(theme: Option<Theme>) {
+	*OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+/// A built-in theme that can be selected with the `theme` option in the `ASSERT2` environment variable.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ThemePreset {
+	/// The default theme.
+	Default,
+
+	/// The theme for color blind users.
+	Colorblind,
+}
+
+/// Get the current theme.
+///
+/// The colors from the `ASSERT2_THEME` environment variable are applied on top of the theme selected in the `ASSERT2` environment variable.
+fn theme() -> Theme {
+	if let Some(theme) = *OVERRIDE.read().unwrap_or_else(|e| e.into_inner()) {
+		return theme;
+	}
+	let options = crate::__assert2_impl::print::options::AssertOptions::get();
+	let theme = match (options.theme, options.palette) {
+		(ThemePreset::Default, _) => Theme::DEFAULT,
+		(ThemePreset::Colorblind, ColorPalette::Basic) => Theme::COLORBLIND_BASIC,
+		(ThemePreset::Colorblind, _) => Theme::COLORBLIND,
+	};
+	match std::env::var("ASSERT2_THEME") {
+		Ok(spec) => theme.with_spec(&spec),
+		Err(_) => theme,
+	}
+}
+
+/// Get the color for the left side of a comparison.
+pub(crate) fn left() -> yansi::Color {
+	theme().left.to_yansi()
+}
+
+/// Get the color for the right side of a comparison.
+pub(crate) fn right() -> yansi::Color {
+	theme().right.to_yansi()
+}
+
+/// Get the color for operators.
+pub(crate) fn operator() -> yansi::Color {
+	theme().operator.to_yansi()
+}
+
+/// Get the color for names.
+pub(crate) fn name() -> yansi::Color {
+	theme().name.to_yansi()
+}
+
+/// Get the style for highlighted differences in text of the given color.
+///
+/// With the basic 16 colors, the color is used as background with black text.
+/// If the terminal supports more colors, a dark shade of the color is used as background instead.
+///
+/// If the theme says so, the differences are underlined too.
+pub(crate) fn highlight(color: yansi::Color) -> yansi::Style {
+	let palette = crate::__assert2_impl::print::options::AssertOptions::get().palette;
+	let style = highlight_with_palette(color, palette);
+	if theme().underline {
+		style.underline()
+	} else {
+		style
+	}
+}
+
+/// Get the style for highlighted differences in text of the given color, using a specific palette.
+fn highlight_with_palette(color: yansi::Color, palette: ColorPalette) -> yansi::Style {
+	let inverted = yansi::Style::new().fg(yansi::Color::Black).bg(color).bold();
+	let Some((r, g, b)) = to_rgb(color) else {
+		return inverted;
+	};
+	// Use a dark shade of the color, which keeps the text in the normal color readable.
+	let shade = |x: u8| (x as u16 * 3 / 10) as u8;
+	let background = match palette {
+		ColorPalette::Basic => return inverted,
+		ColorPalette::Fixed => yansi::Color::Fixed(rgb_to_fixed(shade(r), shade(g), shade(b))),
+		ColorPalette::TrueColor => yansi::Color::Rgb(shade(r), shade(g), shade(b)),
+	};
+	yansi::Style::new().fg(color).bg(background).bold()
+}
+
+/// Get the approximate RGB value of a color, if known.
+fn to_rgb(color: yansi::Color) -> Option<(u8, u8, u8)> {
+	let rgb = match color {
+		yansi::Color::Black => (0, 0, 0),
+		yansi::Color::Red => (205, 49, 49),
+		yansi::Color::Green => (13, 188, 121),
+		yansi::Color::Yellow => (229, 229, 16),
+		yansi::Color::Blue => (36, 114, 200),
+		yansi::Color::Magenta => (188, 63, 188),
+		yansi::Color::Cyan => (17, 168, 205),
+		yansi::Color::White => (229, 229, 229),
+		yansi::Color::BrightBlack => (102, 102, 102),
+		yansi::Color::BrightRed => (241, 76, 76),
+		yansi::Color::BrightGreen => (35, 209, 139),
+		yansi::Color::BrightYellow => (245, 245, 67),
+		yansi::Color::BrightBlue => (59, 142, 234),
+		yansi::Color::BrightMagenta => (214, 112, 214),
+		yansi::Color::BrightCyan => (41, 184, 219),
+		yansi::Color::BrightWhite => (255, 255, 255),
+		yansi::Color::Fixed(index) => return fixed_to_rgb(index),
+		yansi::Color::Rgb(r, g, b) => (r, g, b),
+		yansi::Color::Primary => return None,
+	};
+	Some(rgb)
+}
+
+/// The intensity of the six levels of each component in the color cube of the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Get the RGB value of a color of the 256 color palette.
+fn fixed_to_rgb(index: u8) -> Option<(u8, u8, u8)> {
+	match index {
+		0..=15 => None,
+		16..=231 => {
+			let index = index - 16;
+			Some((CUBE_LEVELS[index as usize / 36], CUBE_LEVELS[index as usize / 6 % 6], CUBE_LEVELS[index as usize % 6]))
+		},
+		232..=255 => {
+			let level = 8 + (index - 232) * 10;
+			Some((level, level, level))
+		},
+	}
+}
+
+/// Get the closest color of the color cube in the 256 color palette.
+fn rgb_to_fixed(r: u8, g: u8, b: u8) -> u8 {
+	let level = |x: u8| {
+		CUBE_LEVELS.iter()
+			.enumerate()
+			.min_by_key(|(_, &level)| (level as i16 - x as i16).abs())
+			.map_or(0, |(i, _)| i as u8)
+	};
+	16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// Extension trait to paint values with the colors of the current theme.
+pub(crate) trait Themed: Paint {
+	/// Paint the value with the color for the left side of a comparison.
+	fn paint_left(&self) -> Painted<&Self> {
+		self.fg(left())
+	}
+
+	/// Paint the value with the color for the right side of a comparison.
+	fn paint_right(&self) -> Painted<&Self> {
+		self.fg(right())
+	}
+
+	/// Paint the value with the color for operators.
+	fn paint_operator(&self) -> Painted<&Self> {
+		self.fg(operator())
+	}
+
+	/// Paint the value with the color for names.
+	fn paint_name(&self) -> Painted<&Self> {
+		self.fg(name())
+	}
+}
+
+impl<T: ?Sized> Themed for T {}
+
+#[test]
+fn test_parse_theme() {
+	use crate::assert;
+	assert!(Theme::parse("") == Theme::DEFAULT);
+	assert!(Theme::parse("left=bright_magenta,RIGHT=Green") == Theme { left: ThemeColor::BrightMagenta, right: ThemeColor::Green, ..Theme::DEFAULT });
+	assert!(Theme::parse("operator=208, name=#FF8800") == Theme { operator: ThemeColor::Fixed(208), name: ThemeColor::Rgb(255, 136, 0), ..Theme::DEFAULT });
+	assert!(Theme::parse("left=bogus,right,middle=red,name=#12345") == Theme::DEFAULT);
+
+	// Colors from the environment are applied on top of the built-in themes.
+	assert!(Theme::COLORBLIND.with_spec("left=white") == Theme { left: ThemeColor::White, ..Theme::COLORBLIND });
+}
+
+#[test]
+fn test_highlight_with_palette() {
+	use crate::assert;
+	let inverted = yansi::Style::new().fg(yansi::Color::Black).bg(yansi::Color::Cyan).bold();
+	assert!(highlight_with_palette(yansi::Color::Cyan, ColorPalette::Basic) == inverted);
+	assert!(highlight_with_palette(yansi::Color::Primary, ColorPalette::TrueColor).background == Some(yansi::Color::Primary));
+
+	let style = highlight_with_palette(yansi::Color::Rgb(200, 100, 0), ColorPalette::TrueColor);
+	assert!(style.foreground == Some(yansi::Color::Rgb(200, 100, 0)));
+	assert!(style.background == Some(yansi::Color::Rgb(60, 30, 0)));
+	let style = highlight_with_palette(yansi::Color::Rgb(200, 100, 0), ColorPalette::Fixed);
+	assert!(style.background == Some(yansi::Color::Fixed(16 + 36)));
+
+	assert!(fixed_to_rgb(16 + 36 * 5 + 6 * 2 + 1) == Some((255, 135, 95)));
+	assert!(fixed_to_rgb(232) == Some((8, 8, 8)));
+	assert!(rgb_to_fixed(255, 135, 95) == 16 + 36 * 5 + 6 * 2 + 1);
+}
@@ -0,0 +1,38 @@
+use assert2::{assert_ends_with, assert_starts_with};
+
+#[test]
+fn assert_starts_with_pass() {
+	assert_starts_with!("Hello world!", "Hello");
+	assert_starts_with!("Hello world!", "");
+	assert_starts_with!(String::from("Hello"), String::from("Hello"));
+	assert_starts_with!("Hello world!", "Hello", "{}", "math broke");
+	assert_starts_with!(vec![1, 2, 3], [1, 2]);
+	assert_starts_with!([1, 2, 3], vec![1, 2, 3],);
+	assert_starts_with!(&b"hello"[..], b"he");
+}
+
+#[test]
+fn assert_ends_with_pass() {
+	assert_ends_with!("Hello world!", "world!");
+	assert_ends_with!("Hello world!", "");
+	assert_ends_with!(String::from("Hello"), "Hello", "{}", "math broke");
+	assert_ends_with!(vec![1, 2, 3], [2, 3]);
+	assert_ends_with!(vec!["a", "b"], vec!["b"],);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_starts_with1, assert_starts_with!("Hello world!", "Help"));
+test_panic!(panic_starts_with2, assert_starts_with!("He", "Hello", "{}", "too short"));
+test_panic!(panic_starts_with3, assert_starts_with!(vec![1, 2, 3], [1, 3]));
+test_panic!(panic_ends_with1, assert_ends_with!("Hello world!", "World!"));
+test_panic!(panic_ends_with2, assert_ends_with!("!", "world!", "{}", "too short"));
+test_panic!(panic_ends_with3, assert_ends_with!(b"foo.txt", b".rs"));
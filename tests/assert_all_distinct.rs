@@ -0,0 +1,24 @@
+use assert2::assert_all_distinct;
+
+#[test]
+fn assert_all_distinct_pass() {
+	let (a, b, c) = (1, 2, 3);
+	assert_all_distinct!(a);
+	assert_all_distinct!(a, b, c);
+	assert_all_distinct!(a, b, c,);
+	assert_all_distinct!("foo", "bar"; "{}", "names broke");
+	assert_all_distinct!(String::from("foo"), String::from("bar"),; "{}", "names broke");
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_all_distinct1, assert_all_distinct!(1, 2, 1));
+test_panic!(panic_assert_all_distinct2, assert_all_distinct!("a", "b", "a", "b"; "{}", "duplicates"));
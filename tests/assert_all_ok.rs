@@ -0,0 +1,31 @@
+use assert2::{assert, assert_all_ok};
+
+#[test]
+fn assert_all_ok_pass() {
+	let values = assert_all_ok!(vec![Ok::<_, String>(1), Ok(2)]);
+	assert!(values == [1, 2]);
+
+	let results: Vec<Result<i32, String>> = vec![Ok(1), Ok(2)];
+	let values = assert_all_ok!(&results, "{}", "results broke");
+	assert!(values == [&1, &2]);
+	assert_all_ok!(results.iter(),);
+	assert_all_ok!(count_errors = true, results.iter());
+	assert_all_ok!(count_errors = true, results, "{}", "results broke");
+
+	let values: Vec<i32> = assert_all_ok!(Vec::<Result<i32, ()>>::new());
+	assert!(values.is_empty());
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_all_ok1, assert_all_ok!(vec![Ok(1), Err("broken")]));
+test_panic!(panic_assert_all_ok2, assert_all_ok!(["1", "x"].iter().map(|x| x.parse::<i32>()), "{}", "parse failed"));
+test_panic!(panic_assert_all_ok3, assert_all_ok!(count_errors = true, vec![Err(1), Ok(2), Err(3)]));
@@ -0,0 +1,35 @@
+use assert2::assert_bytes_eq;
+
+#[test]
+fn assert_bytes_eq_pass() {
+	assert_bytes_eq!(b"hello", b"hello");
+	assert_bytes_eq!(b"hello", "hello");
+	assert_bytes_eq!(vec![1u8, 2, 3], [1, 2, 3]);
+	assert_bytes_eq!(&[1u8, 2, 3][..], vec![1, 2, 3]);
+	assert_bytes_eq!(b"", "", "{}", "bytes broke");
+	assert_bytes_eq!(b"", "", "{}", "bytes broke",);
+}
+
+#[test]
+fn assert_bytes_eq_no_move() {
+	let a = vec![1u8, 2, 3];
+	let b = vec![1u8, 2, 3];
+	assert_bytes_eq!(a, b);
+	drop(a);
+	drop(b);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_bytes_eq1, assert_bytes_eq!(b"hello", b"hallo"));
+test_panic!(panic_assert_bytes_eq2, assert_bytes_eq!(b"hello", b"hello world"));
+test_panic!(panic_assert_bytes_eq3, assert_bytes_eq!(b"hello world", b"", "{}", "bytes broke"));
+test_panic!(panic_assert_bytes_eq4, assert_bytes_eq!(vec![0u8; 100], [&[0u8; 50][..], &[1u8; 50][..]].concat()));
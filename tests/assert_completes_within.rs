@@ -0,0 +1,27 @@
+use assert2::{assert, assert_completes_within};
+use std::time::Duration;
+
+#[test]
+fn assert_completes_within_pass() {
+	let sum = assert_completes_within!(Duration::from_secs(10), (0..100).sum::<u32>());
+	assert!(sum == 4950);
+	assert_completes_within!(10s, {});
+	assert_completes_within!(10s, {}, "{}", "time broke");
+	let value = assert_completes_within!(1min, {
+		let x = 2;
+		x * 3
+	});
+	assert!(value == 6);
+}
+
+#[test]
+#[should_panic]
+fn panic_assert_completes_within() {
+	assert_completes_within!(1ms, std::thread::sleep(Duration::from_millis(20)));
+}
+
+#[test]
+#[should_panic]
+fn panic_assert_completes_within_expr() {
+	assert_completes_within!(Duration::from_millis(1), std::thread::sleep(Duration::from_millis(20)), "{}", "too slow");
+}
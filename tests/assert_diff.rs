@@ -0,0 +1,162 @@
+use assert2::{assert, check, let_assert, try_check, AssertDiff, FieldDiff, StructuralDiff};
+
+#[derive(Debug, Clone, PartialEq, AssertDiff)]
+struct Server {
+	host: String,
+	port: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, AssertDiff)]
+struct Config {
+	name: String,
+	server: Server,
+	limits: Limits,
+	mode: Mode,
+}
+
+#[derive(Debug, Clone, PartialEq, AssertDiff)]
+struct Limits(u32, u32);
+
+#[derive(Debug, Clone, PartialEq, AssertDiff)]
+enum Mode {
+	Off,
+	On { level: u8 },
+	Custom(Server),
+}
+
+#[derive(Debug, Clone, PartialEq, AssertDiff)]
+struct Wrapper<T> {
+	value: T,
+}
+
+fn same_length(a: &str, b: &str) -> bool {
+	a.len() == b.len()
+}
+
+#[derive(Debug, Clone, AssertDiff)]
+struct Record {
+	#[assert2(skip)]
+	id: u64,
+	#[assert2(compare_with = same_length)]
+	label: String,
+	server: Server,
+}
+
+impl PartialEq for Record {
+	fn eq(&self, other: &Self) -> bool {
+		self.structural_eq(other)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, AssertDiff)]
+enum Event {
+	Created(#[assert2(skip)] u64, String),
+}
+
+fn record() -> Record {
+	Record {
+		id: 1,
+		label: "abc".into(),
+		server: Server { host: "localhost".into(), port: 80 },
+	}
+}
+
+fn config() -> Config {
+	Config {
+		name: "main".into(),
+		server: Server { host: "localhost".into(), port: 80 },
+		limits: Limits(1, 2),
+		mode: Mode::On { level: 1 },
+	}
+}
+
+fn diffs<T: StructuralDiff>(left: &T, right: &T) -> Vec<FieldDiff> {
+	let mut diffs = Vec::new();
+	left.structural_diff(right, "", &mut diffs);
+	diffs
+}
+
+fn diff(path: &str, left: &str, right: &str) -> FieldDiff {
+	FieldDiff { path: path.into(), left: left.into(), right: right.into() }
+}
+
+#[test]
+fn structural_diff_equal() {
+	assert!(diffs(&config(), &config()).is_empty());
+	check!(config() == config());
+}
+
+#[test]
+fn structural_diff_fields() {
+	let mut other = config();
+	other.server.port = 8080;
+	other.limits.1 = 3;
+	other.mode = Mode::On { level: 2 };
+	assert!(diffs(&config(), &other) == [
+		diff("server.port", "80", "8080"),
+		diff("limits.1", "2", "3"),
+		diff("mode.level", "1", "2"),
+	]);
+
+	let mut other = config();
+	other.mode = Mode::Custom(Server { host: "remote".into(), port: 80 });
+	let mut config = config();
+	config.mode = Mode::Custom(Server { host: "local".into(), port: 80 });
+	assert!(diffs(&config, &other) == [diff("mode.0.host", "\"local\"", "\"remote\"")]);
+}
+
+#[test]
+fn structural_diff_variants() {
+	let mut other = config();
+	other.mode = Mode::Off;
+	assert!(diffs(&config(), &other) == [diff("mode", "On { level: 1 }", "Off")]);
+	assert!(diffs(&Mode::Off, &Mode::On { level: 1 }) == [diff("", "Off", "On { level: 1 }")]);
+}
+
+#[test]
+fn structural_diff_generic() {
+	assert!(diffs(&Wrapper { value: 1 }, &Wrapper { value: 2 }) == [diff("value", "1", "2")]);
+}
+
+#[test]
+fn structural_diff_attributes() {
+	let mut other = record();
+	other.id = 2;
+	other.label = "xyz".into();
+	assert!(diffs(&record(), &other).is_empty());
+	check!(record() == other);
+
+	other.label = "abcd".into();
+	other.server.port = 8080;
+	assert!(diffs(&record(), &other) == [
+		diff("label", "\"abc\"", "\"abcd\""),
+		diff("server.port", "80", "8080"),
+	]);
+	let_assert!(Err(failure) = try_check!(record() == other));
+	assert!(failure.report().contains("with field differences:\n  label: \"abc\" != \"abcd\"\n  server.port: 80 != 8080"));
+
+	let left = Event::Created(1, "a".into());
+	assert!(diffs(&left, &Event::Created(2, "a".into())).is_empty());
+	assert!(diffs(&left, &Event::Created(2, "b".into())) == [diff("1", "\"a\"", "\"b\"")]);
+}
+
+#[test]
+fn structural_diff_report() {
+	let mut other = config();
+	other.server.port = 8080;
+	let_assert!(Err(failure) = try_check!(config() == other));
+	assert!(failure.report().contains("with field differences:\n  server.port: 80 != 8080"));
+	assert!(!failure.report().contains("name"));
+
+	// Values that differ as a whole use the normal expansion.
+	let_assert!(Err(failure) = try_check!(Mode::Off == Mode::On { level: 1 }));
+	assert!(!failure.report().contains("with field differences:"));
+}
+
+#[test]
+#[should_panic]
+fn panic_structural_diff() {
+	let mut other = config();
+	other.name = "other".into();
+	assert!(config() == other);
+}
@@ -0,0 +1,61 @@
+use assert2::{assert, assert_downcast};
+use std::any::Any;
+use std::error::Error;
+
+#[derive(Debug)]
+struct MyError;
+
+impl std::fmt::Display for MyError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.write_str("my error")
+	}
+}
+
+impl Error for MyError {}
+
+#[test]
+fn assert_downcast_any() {
+	let value: Box<dyn Any> = Box::new(5i32);
+	assert!(*assert_downcast!(value, i32) == 5);
+
+	let value: &dyn Any = &String::from("foo");
+	assert!(assert_downcast!(value, String) == "foo");
+
+	let payload = std::panic::catch_unwind(|| panic!("boom")).unwrap_err();
+	assert!(*assert_downcast!(payload, &str) == "boom");
+	assert!(*assert_downcast!(&payload, &str, "{}", "payload broke") == "boom");
+}
+
+#[test]
+fn assert_downcast_error() {
+	let error: Box<dyn Error + Send + Sync> = Box::new(MyError);
+	let my_error: &MyError = assert_downcast!(error, MyError);
+	assert!(my_error.to_string() == "my error");
+
+	let error = MyError;
+	let error: &dyn Error = &error;
+	assert_downcast!(error, MyError,);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_downcast_known, {
+	let value: Box<dyn Any> = Box::new(String::from("foo"));
+	assert_downcast!(value, i32);
+});
+test_panic!(panic_assert_downcast_unknown, {
+	let value: Box<dyn Any + Send> = Box::new(MyError);
+	assert_downcast!(value, String, "{}", "wrong type");
+});
+test_panic!(panic_assert_downcast_error, {
+	let error: Box<dyn Error> = Box::new(std::fmt::Error);
+	assert_downcast!(error, MyError);
+});
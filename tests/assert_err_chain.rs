@@ -0,0 +1,82 @@
+use assert2::assert_err_chain;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+enum ConfigError {
+	Missing(String),
+	Io(io::Error),
+}
+
+impl fmt::Display for ConfigError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Missing(key) => write!(f, "missing key: {key}"),
+			Self::Io(_) => write!(f, "failed to read config"),
+		}
+	}
+}
+
+impl Error for ConfigError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Missing(_) => None,
+			Self::Io(e) => Some(e),
+		}
+	}
+}
+
+#[derive(Debug)]
+struct AppError(ConfigError);
+
+impl fmt::Display for AppError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "failed to start application")
+	}
+}
+
+impl Error for AppError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		Some(&self.0)
+	}
+}
+
+fn io_error() -> AppError {
+	AppError(ConfigError::Io(io::Error::new(io::ErrorKind::NotFound, "no such file")))
+}
+
+#[test]
+fn assert_err_chain_pass() {
+	let error = io_error();
+	assert_err_chain!(error, AppError);
+	assert_err_chain!(error, ConfigError);
+	assert_err_chain!(error, io::Error);
+	assert_err_chain!(error, ConfigError => ConfigError::Io(_));
+	assert_err_chain!(error, io::Error => e if e.kind() == io::ErrorKind::NotFound);
+	assert_err_chain!(error, io::Error, "{}", "errors broke");
+	assert_err_chain!(error, io::Error => _, "{}", "errors broke",);
+	assert_err_chain!(AppError(ConfigError::Missing("name".into())), ConfigError => ConfigError::Missing(key) if key == "name");
+}
+
+#[test]
+fn assert_err_chain_boxed() {
+	let error: Box<dyn Error> = Box::new(io_error());
+	assert_err_chain!(error, io::Error);
+	let error: Box<dyn Error + Send + Sync> = Box::new(io::Error::other("oops"));
+	assert_err_chain!(error, io::Error);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_err_chain1, assert_err_chain!(io_error(), fmt::Error));
+test_panic!(panic_assert_err_chain2, assert_err_chain!(io_error(), ConfigError => ConfigError::Missing(_)));
+test_panic!(panic_assert_err_chain3, assert_err_chain!(io_error(), io::Error => e if e.kind() == io::ErrorKind::PermissionDenied, "{}", "errors broke"));
@@ -0,0 +1,33 @@
+use assert2::assert_eventually;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[test]
+fn assert_eventually_pass() {
+	let counter = AtomicUsize::new(0);
+	assert_eventually!(timeout = 1s, interval = 1ms, counter.fetch_add(1, Ordering::Relaxed) == 5);
+	assert_eventually!(timeout = 1s, counter.fetch_add(1, Ordering::Relaxed) >= 10, "{}", "counting broke");
+	assert_eventually!(timeout = (Duration::from_secs(1)), interval = 500us, counter.fetch_add(1, Ordering::Relaxed) > 12);
+	assert_eventually!(timeout = 1.5s, let 20 = counter.fetch_add(1, Ordering::Relaxed));
+}
+
+#[test]
+fn assert_eventually_threads() {
+	let counter = std::sync::Arc::new(AtomicUsize::new(0));
+	let thread = std::thread::spawn({
+		let counter = counter.clone();
+		move || {
+			std::thread::sleep(Duration::from_millis(5));
+			counter.store(1, Ordering::Relaxed);
+		}
+	});
+	assert_eventually!(timeout = 10s, interval = 1ms, counter.load(Ordering::Relaxed) == 1);
+	thread.join().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "assertion did not pass within")]
+fn panic_assert_eventually() {
+	let counter = AtomicUsize::new(0);
+	assert_eventually!(timeout = 20ms, interval = 1ms, counter.fetch_add(1, Ordering::Relaxed) == usize::MAX);
+}
@@ -0,0 +1,60 @@
+use assert2::assert_fields_eq;
+
+#[derive(Debug)]
+struct Pet {
+	name: String,
+	age: u32,
+	shaved: bool,
+}
+
+#[derive(Debug)]
+struct Wrapper<T> {
+	value: T,
+}
+
+fn scrappy() -> Pet {
+	Pet {
+		name: "Scrappy".into(),
+		age: 7,
+		shaved: false,
+	}
+}
+
+#[test]
+fn assert_fields_eq_pass() {
+	let pet = scrappy();
+	assert_fields_eq!(pet, Pet { name: "Scrappy", .. });
+	assert_fields_eq!(pet, Pet { age: 7, shaved: false, .. });
+	assert_fields_eq!(pet, Pet { .. });
+	assert_fields_eq!(scrappy(), Pet { age: 3 + 4, .. }, "{}", "pets broke");
+	assert_fields_eq!(scrappy(), Pet { age: 7, .. }, "{}", "pets broke",);
+	assert_fields_eq!(Wrapper { value: 1.5 }, Wrapper { value: 1.5, .. });
+}
+
+#[test]
+fn assert_fields_eq_no_move() {
+	let pet = scrappy();
+	assert_fields_eq!(pet, Pet { name: String::from("Scrappy"), .. });
+	drop(pet);
+}
+
+#[test]
+fn assert_fields_eq_shadowing() {
+	let age = 7;
+	let pet = scrappy();
+	assert_fields_eq!(pet, Pet { age: age, .. });
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_fields_eq1, assert_fields_eq!(scrappy(), Pet { age: 8, .. }));
+test_panic!(panic_assert_fields_eq2, assert_fields_eq!(scrappy(), Pet { name: "Coco", age: 7, shaved: true, .. }));
+test_panic!(panic_assert_fields_eq3, assert_fields_eq!(Wrapper { value: "a" }, Wrapper { value: "b", .. }, "{}", "pets broke"));
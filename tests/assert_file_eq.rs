@@ -0,0 +1,23 @@
+use assert2::assert_file_eq;
+
+#[test]
+fn assert_file_eq_pass() {
+	assert_file_eq!("Hello\nworld!\n", "tests/golden/hello.txt");
+	assert_file_eq!(String::from("Hello\nworld!\n"), "tests/golden/hello.txt");
+	assert_file_eq!("Hello\nworld!\n", std::path::Path::new("tests/golden/hello.txt"), "{}", "golden broke");
+	assert_file_eq!("Hello\nworld!\n", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/hello.txt"));
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_file_eq1, assert_file_eq!("Hello\nWorld!\n", "tests/golden/hello.txt"));
+test_panic!(panic_assert_file_eq2, assert_file_eq!("Hello\n", "tests/golden/hello.txt", "{}", "golden broke"));
+test_panic!(panic_assert_file_eq3, assert_file_eq!("Hello\nworld!\n", "tests/golden/non-existing.txt"));
@@ -0,0 +1,75 @@
+use assert2::{assert, check, let_assert, try_check, AssertFormat};
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq)]
+struct Blob(Vec<u8>);
+
+impl AssertFormat for Blob {
+	fn assert_fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+		write!(f, "Blob({} bytes)", self.0.len())
+	}
+}
+
+#[derive(Debug, PartialEq)]
+struct Lines(Vec<&'static str>);
+
+impl AssertFormat for Lines {
+	fn assert_fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+		if f.alternate() {
+			write!(f, "{}", self.0.join("\n"))
+		} else {
+			write!(f, "{}", self.0.join(" / "))
+		}
+	}
+}
+
+/// A type that implements `AssertFormat` and `Display`, but not `Debug`.
+#[derive(PartialEq)]
+struct Token(&'static str);
+
+impl std::fmt::Display for Token {
+	fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+		f.write_str(self.0)
+	}
+}
+
+impl AssertFormat for Token {
+	fn assert_fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+		write!(f, "Token(<{} chars>)", self.0.len())
+	}
+}
+
+#[test]
+fn assert_format_pass() {
+	check!(Blob(vec![1]) == Blob(vec![1]));
+	assert!(Token("a") == Token("a"));
+}
+
+#[test]
+fn assert_format_comparison() {
+	let_assert!(Err(failure) = try_check!(Blob(vec![1, 2]) == Blob(vec![1])));
+	assert!(failure.report().contains("with expansion:\n  Blob(2 bytes) == Blob(1 bytes)\n"));
+
+	// The format takes precedence over `Display` too, even if it is requested explicitly.
+	let_assert!(Err(failure) = try_check!(Token("secret") == Token("public"); format = display));
+	assert!(failure.report().contains("with expansion:\n  Token(<6 chars>) == Token(<6 chars>)\n"));
+}
+
+#[test]
+fn assert_format_pretty() {
+	// The compact output is too long, so the alternate format is used for the diff.
+	let left = Lines(vec!["the first line of text", "the second line of text"]);
+	let right = Lines(vec!["the first line of text", "the other line of text"]);
+	let_assert!(Err(failure) = try_check!(left == right));
+	assert!(failure.report().contains("with diff:\n"));
+	assert!(failure.report().contains("the first line of text\n"));
+	assert!(!failure.report().contains(" / "));
+}
+
+#[test]
+fn assert_format_pattern() {
+	let_assert!(Err(failure) = try_check!(let Some(_) = None::<Blob>));
+	assert!(failure.report().contains("with expansion:\n  None"));
+	let_assert!(Err(failure) = try_check!(matches!(Blob(vec![1]), Blob(v) if v.is_empty())));
+	assert!(failure.report().contains("with expansion:\n  Blob(1 bytes)"));
+}
@@ -0,0 +1,17 @@
+use assert2::{assert_impl, assert_send, assert_sync};
+
+struct Foo<'a, T> {
+	_value: &'a T,
+}
+
+assert_impl!(Foo<'static, u8>: Send + Sync);
+assert_impl!(str: std::fmt::Display + std::fmt::Debug);
+assert_send!(Foo<'static, u8>, String);
+assert_sync!(Foo<'static, u8>, String,);
+
+#[test]
+fn assert_impl_in_function() {
+	assert_impl!(Vec<u8>: std::io::Write + Clone + 'static);
+	assert_send!(std::sync::Arc<str>);
+	assert_sync!(std::sync::Mutex<u8>);
+}
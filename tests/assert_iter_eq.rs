@@ -0,0 +1,25 @@
+use assert2::assert_iter_eq;
+
+#[test]
+fn assert_iter_eq_pass() {
+	let values = vec![1, 2, 3];
+	assert_iter_eq!(values.iter(), &[1, 2, 3]);
+	assert_iter_eq!(values.iter().map(|x| x * 2), [2, 4, 6]);
+	assert_iter_eq!(Vec::<i32>::new(), []);
+	assert_iter_eq!("abc".chars(), ['a', 'b', 'c'], "{}", "characters broke");
+	assert_iter_eq!(values, vec![1, 2, 3],);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_iter_eq_element, assert_iter_eq!(0..100, (0..100).map(|x| if x == 50 { 0 } else { x })));
+test_panic!(panic_assert_iter_eq_shorter, assert_iter_eq!(0..3, 0..4));
+test_panic!(panic_assert_iter_eq_longer, assert_iter_eq!(vec!["a", "b"], ["a"], "{}", "too long"));
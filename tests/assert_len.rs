@@ -0,0 +1,35 @@
+use assert2::{assert_empty, assert_len};
+use std::collections::{BTreeMap, HashSet};
+
+#[test]
+fn assert_empty_pass() {
+	assert_empty!(Vec::<i32>::new());
+	assert_empty!([0u8; 0]);
+	assert_empty!(HashSet::<i32>::new(), "{}", "sets broke");
+	assert_empty!([1, 2, 3].iter().filter(|x| **x > 3),);
+}
+
+#[test]
+fn assert_len_pass() {
+	assert_len!(vec![1, 2, 3], 3);
+	assert_len!([1, 2, 3], 3,);
+	assert_len!([1, 2, 3][1..].iter(), 1 + 1, "{}", "slices broke");
+	let map: BTreeMap<_, _> = [("a", 1), ("b", 2)].into_iter().collect();
+	assert_len!(map, 2);
+	assert_len!((0..10).step_by(2), 5);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_empty1, assert_empty!(vec![1]));
+test_panic!(panic_assert_empty2, assert_empty!(0..100, "{}", "too many"));
+test_panic!(panic_assert_len1, assert_len!(vec![1, 2], 3));
+test_panic!(panic_assert_len2, assert_len!(Vec::<i32>::new(), 1, "{}", "too few"));
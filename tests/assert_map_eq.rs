@@ -0,0 +1,31 @@
+use assert2::assert_map_eq;
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn assert_map_eq_pass() {
+	let hash: HashMap<_, _> = [("a", 1), ("b", 2)].into_iter().collect();
+	let btree: BTreeMap<_, _> = [("b", 2), ("a", 1)].into_iter().collect();
+	assert_map_eq!(hash, btree);
+	assert_map_eq!(hash, [("a", 1), ("b", 2)]);
+	assert_map_eq!(vec![("b", 2), ("a", 1)], btree, "{}", "maps broke");
+	assert_map_eq!(hash.iter().filter(|(_, v)| **v > 1), [("b", 2)],);
+	assert_map_eq!(HashMap::<i32, i32>::new(), []);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_map_eq_only_left, assert_map_eq!([(1, "a"), (2, "b")], [(1, "a")]));
+test_panic!(panic_assert_map_eq_only_right, assert_map_eq!([(1, "a")], [(1, "a"), (2, "b")]));
+test_panic!(panic_assert_map_eq_value, assert_map_eq!(
+	[("a", "hello world"), ("b", "foo"), ("c", "x")].into_iter().collect::<HashMap<_, _>>(),
+	[("c", "x"), ("a", "hello word"), ("d", "bar")],
+	"{}", "maps differ",
+));
@@ -0,0 +1,39 @@
+use assert2::{assert, assert_pending, assert_ready};
+use std::task::Poll;
+
+#[test]
+fn assert_ready_pass() {
+	let value = assert_ready!(Poll::Ready(10));
+	assert!(value == 10);
+	let value = assert_ready!(Poll::Ready(String::from("foo")), "{}", "poll broke");
+	assert!(value == "foo");
+	assert_pending!(Poll::<i32>::Pending);
+	assert_pending!(Poll::<i32>::Pending, "{}", "poll broke");
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn assert_ready_future() {
+	let value = assert_ready!(async { 5 });
+	assert!(value == 5);
+
+	let mut future = std::future::pending::<i32>();
+	assert_pending!(&mut future);
+	assert_pending!(&mut future);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_ready, assert_ready!(Poll::<i32>::Pending));
+test_panic!(panic_assert_pending, assert_pending!(Poll::Ready(10)));
+test_panic!(panic_assert_pending_non_debug, assert_pending!(Poll::Ready(NotDebug)));
+
+struct NotDebug;
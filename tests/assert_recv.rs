@@ -0,0 +1,58 @@
+use assert2::{assert, assert_recv};
+use std::time::Duration;
+
+#[test]
+fn assert_recv_std() {
+	let (sender, receiver) = std::sync::mpsc::channel();
+	sender.send(1).unwrap();
+	assert!(assert_recv!(receiver, timeout = 1ms) == 1);
+
+	std::thread::spawn(move || {
+		std::thread::sleep(Duration::from_millis(10));
+		sender.send(2).unwrap();
+	});
+	assert!(assert_recv!(&receiver, timeout = (Duration::from_secs(10)), "{}", "nothing received") == 2);
+}
+
+#[cfg(feature = "crossbeam")]
+#[test]
+fn assert_recv_crossbeam() {
+	let (sender, receiver) = crossbeam_channel::unbounded();
+	sender.send("foo").unwrap();
+	assert!(assert_recv!(receiver, timeout = 1ms) == "foo");
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn assert_recv_tokio() {
+	let (sender, mut receiver) = tokio::sync::mpsc::channel(1);
+	std::thread::spawn(move || {
+		std::thread::sleep(Duration::from_millis(10));
+		sender.blocking_send(3).unwrap();
+	});
+	assert!(assert_recv!(receiver, timeout = 10s) == 3);
+
+	let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+	sender.send(4).unwrap();
+	assert!(assert_recv!(receiver, timeout = 1ms,) == 4);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_recv_timeout, {
+	let (_sender, receiver) = std::sync::mpsc::channel::<i32>();
+	assert_recv!(receiver, timeout = 10ms)
+});
+test_panic!(panic_assert_recv_disconnected, {
+	let (sender, receiver) = std::sync::mpsc::channel::<i32>();
+	drop(sender);
+	assert_recv!(receiver, timeout = 10s, "{}", "sender dropped")
+});
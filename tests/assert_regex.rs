@@ -0,0 +1,26 @@
+#![cfg(feature = "regex")]
+
+use assert2::assert_regex;
+
+#[test]
+fn assert_regex_pass() {
+	assert_regex!("foo 42", r"^foo \d+$");
+	assert_regex!(String::from("Hello world!"), "world");
+	assert_regex!("abc", String::from("^a.c$"));
+	assert_regex!("abc", "b", "{}", "regex broke");
+	assert_regex!("abc", "b", "{}", "regex broke",);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_regex1, assert_regex!("foo bar", r"^foo \d+$"));
+test_panic!(panic_assert_regex2, assert_regex!("abc", "x|y", "{}", "regex broke"));
+test_panic!(panic_assert_regex3, assert_regex!("abc", "(unclosed"));
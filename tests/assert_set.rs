@@ -0,0 +1,64 @@
+use assert2::{assert_set_eq, assert_subset, assert_superset};
+use std::collections::{BTreeSet, HashSet};
+
+#[test]
+fn assert_subset_pass() {
+	let set: HashSet<_> = [1, 2, 3].into_iter().collect();
+	assert_subset!([1, 2], set);
+	assert_subset!(vec![3, 3], [1, 2, 3]);
+	assert_subset!([0; 0], [1]);
+	assert_subset!(set.iter().filter(|x| **x != 2), set);
+	assert_subset!([1], [1], "{}", "sets broke");
+	assert_subset!([1], [1], "{}", "sets broke",);
+}
+
+#[test]
+fn assert_superset_pass() {
+	let set: BTreeSet<_> = ["a", "b", "c"].into_iter().collect();
+	assert_superset!(set, ["a", "c"]);
+	assert_superset!(set, Vec::<&str>::new());
+	assert_superset!(set, set.iter().skip(1));
+	assert_superset!([1], [1], "{}", "sets broke");
+	assert_superset!([1], [1], "{}", "sets broke",);
+}
+
+#[test]
+fn assert_set_eq_pass() {
+	let set: HashSet<_> = [1, 2, 3].into_iter().collect();
+	assert_set_eq!(set, [3, 1, 2]);
+	assert_set_eq!(vec![1, 1, 2, 3], set);
+	assert_set_eq!([0; 0], Vec::<i32>::new());
+	assert_set_eq!(set.iter().filter(|x| **x != 2), [1, 3]);
+	assert_set_eq!([1], [1], "{}", "sets broke");
+	assert_set_eq!([1], [1], "{}", "sets broke",);
+}
+
+#[test]
+fn assert_set_no_move() {
+	let a = vec![String::from("a")];
+	let b = vec![String::from("a"), String::from("b")];
+	assert_subset!(a, b);
+	assert_superset!(b, a);
+	assert_set_eq!(a, a);
+	drop(a);
+	drop(b);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_subset1, assert_subset!([1, 2, 4], [1, 2, 3]));
+test_panic!(panic_assert_subset2, assert_subset!([1], [0; 0], "{}", "sets broke"));
+test_panic!(panic_assert_subset3, assert_subset!(HashSet::from([1, 5]), HashSet::from([1])));
+test_panic!(panic_assert_superset1, assert_superset!([1, 2], [1, 2, 3]));
+test_panic!(panic_assert_superset2, assert_superset!(BTreeSet::from([1]), [1, 2].iter(), "{}", "sets broke"));
+test_panic!(panic_assert_set_eq1, assert_set_eq!([1, 2], [1, 2, 3]));
+test_panic!(panic_assert_set_eq2, assert_set_eq!([1, 2, 3], [2, 3]));
+test_panic!(panic_assert_set_eq3, assert_set_eq!(HashSet::from([1, 5]), BTreeSet::from([1, 6]), "{}", "sets broke"));
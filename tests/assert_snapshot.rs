@@ -0,0 +1,34 @@
+use assert2::assert_snapshot;
+
+#[derive(Debug)]
+struct Pet {
+	#[allow(dead_code)]
+	name: &'static str,
+	#[allow(dead_code)]
+	age: u32,
+}
+
+#[test]
+fn assert_snapshot_pass() {
+	assert_snapshot!(name = "pet", Pet { name: "Scrappy", age: 7 });
+	assert_snapshot!(name = "pet", Pet { name: "Scrappy", age: 7 }, "{}", "snapshot broke");
+	assert_snapshot!(name = "numbers", vec![1, 2, 3]);
+}
+
+#[test]
+fn assert_snapshot_unnamed() {
+	assert_snapshot!(Pet { name: "Scrappy", age: 7 });
+	assert_snapshot!([1, 2]);
+}
+
+#[test]
+#[should_panic]
+fn panic_assert_snapshot() {
+	assert_snapshot!(name = "pet", Pet { name: "Coco", age: 7 });
+}
+
+#[test]
+#[should_panic]
+fn panic_assert_snapshot_missing() {
+	assert_snapshot!(name = "missing", Pet { name: "Coco", age: 7 });
+}
@@ -0,0 +1,39 @@
+use assert2::assert_that;
+use assert2::matchers::*;
+
+struct IsEven;
+
+impl Matcher<i32> for IsEven {
+	fn matches(&self, value: &i32) -> bool {
+		value % 2 == 0
+	}
+
+	fn describe(&self) -> String {
+		"is even".into()
+	}
+}
+
+#[test]
+fn assert_that_pass() {
+	assert_that!(5, gt(3));
+	assert_that!(5, all!(gt(3), lt(10)));
+	assert_that!(5, all!(ge(5), le(5), ne(4),));
+	assert_that!(5, any!(eq(1), eq(5)), "{}", "math broke");
+	assert_that!(String::from("foo"), eq("foo"));
+	assert_that!("foo", not(eq("bar")),);
+	assert_that!(4, all!(IsEven, predicate(|x: &i32| *x > 0, "is positive")));
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_that1, assert_that!(12, all!(gt(3), lt(10))));
+test_panic!(panic_assert_that2, assert_that!(3, any!(eq(1), eq(2)), "{}", "no match"));
+test_panic!(panic_assert_that3, assert_that!(4, not(IsEven)));
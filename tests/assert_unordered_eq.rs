@@ -0,0 +1,42 @@
+use assert2::assert_unordered_eq;
+use std::collections::HashMap;
+
+#[test]
+fn assert_unordered_eq_pass() {
+	assert_unordered_eq!(vec![1, 2, 3], [3, 2, 1]);
+	assert_unordered_eq!([1, 1, 2], vec![1, 2, 1]);
+	assert_unordered_eq!(Vec::<i32>::new(), [0; 0]);
+	assert_unordered_eq!(["a", "b"], ["b", "a"], "{}", "words broke");
+	assert_unordered_eq!(["a", "b"], ["b", "a"], "{}", "words broke",);
+}
+
+#[test]
+fn assert_unordered_eq_hash_map() {
+	let map: HashMap<_, _> = [("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+	let keys: Vec<_> = map.keys().copied().collect();
+	assert_unordered_eq!(keys, ["c", "a", "b"]);
+}
+
+#[test]
+fn assert_unordered_eq_no_move() {
+	let a = vec![String::from("a"), String::from("b")];
+	let b = vec![String::from("b"), String::from("a")];
+	assert_unordered_eq!(a, b);
+	drop(a);
+	drop(b);
+}
+
+macro_rules! test_panic {
+	($name:ident, $($expr:tt)*) => {
+		#[test]
+		#[should_panic]
+		fn $name() {
+			$($expr)*;
+		}
+	}
+}
+
+test_panic!(panic_assert_unordered_eq1, assert_unordered_eq!([1, 2, 3], [1, 2, 4]));
+test_panic!(panic_assert_unordered_eq2, assert_unordered_eq!([1, 1, 2], [1, 2, 2]));
+test_panic!(panic_assert_unordered_eq3, assert_unordered_eq!([1, 2], [1, 2, 2], "{}", "counts broke"));
+test_panic!(panic_assert_unordered_eq4, assert_unordered_eq!(Vec::<i32>::new(), [1]));
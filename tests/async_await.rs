@@ -0,0 +1,95 @@
+use assert2::{assert, check, let_assert, try_check};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// Poll a future to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+	fn clone(_: *const ()) -> RawWaker {
+		RawWaker::new(std::ptr::null(), &VTABLE)
+	}
+	fn noop(_: *const ()) {}
+	static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+	// SAFETY: The waker does nothing, so the vtable trivially upholds the contract.
+	let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+	let mut context = Context::from_waker(&waker);
+	let mut future = Box::pin(future);
+	loop {
+		if let Poll::Ready(value) = future.as_mut().poll(&mut context) {
+			return value;
+		}
+	}
+}
+
+/// Check at compile time that a future can be sent to another thread.
+fn assert_send<F: Future + Send>(future: F) -> F {
+	future
+}
+
+/// A future that is pending once before it resolves, to force a real await point.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+	type Output = ();
+
+	fn poll(mut self: Pin<&mut Self>, _context: &mut Context<'_>) -> Poll<()> {
+		if self.0 {
+			Poll::Ready(())
+		} else {
+			self.0 = true;
+			Poll::Pending
+		}
+	}
+}
+
+async fn value<T>(value: T) -> T {
+	YieldNow(false).await;
+	value
+}
+
+#[test]
+fn await_pass() {
+	block_on(assert_send(async {
+		check!(value(1).await == 1);
+		check!(value(1).await == value(1).await, "{}", "async broke");
+		assert!(value(true).await);
+		assert!(value(1).await + 1 == 2);
+		assert!(0 < value(1).await <= 1);
+		assert!(value(2).await == 1 || value(1).await == 1);
+		assert!(!value(false).await);
+		assert!(value(Some(1)).await.is_some());
+		assert!(matches!(value(Some(1)).await, Some(1)));
+		assert!(let Some(1) = value(Some(1)).await);
+		let_assert!(Some(x) = value(Some(1)).await);
+		assert!(x == 1);
+	}));
+}
+
+#[test]
+fn await_let_chain() {
+	block_on(assert_send(async {
+		// Only the earlier steps of the chain are async.
+		assert!(let Some(x) = value(Some(2)).await && x > 1);
+		check!(let Some(x) = value(Some(2)).await && let Ok(y) = value(Ok::<_, ()>(x)).await && y == 2);
+		assert!(value(1).await == 1 && let Some(_) = Some(1));
+	}));
+}
+
+#[test]
+fn await_fail() {
+	block_on(assert_send(async {
+		let_assert!(Err(failure) = try_check!(value(1).await == 2));
+		assert!(failure.report().contains("with expansion:\n  1 == 2"));
+		let_assert!(Err(failure) = try_check!(let Some(x) = value(Some(1)).await && x > value(1).await));
+		assert!(failure.report().contains("with bindings:\n  x = 1"));
+	}));
+}
+
+#[test]
+#[should_panic]
+fn panic_await() {
+	block_on(async {
+		assert!(value(1).await == 2);
+	});
+}
@@ -0,0 +1,25 @@
+use assert2::check;
+
+#[test]
+fn buffer_output_pass() {
+	let _outer = assert2::buffer_output();
+	let _inner = assert2::buffer_output();
+	check!(1 == 1);
+}
+
+#[test]
+#[should_panic]
+fn panic_buffer_output() {
+	let _buffer = assert2::buffer_output();
+	check!(1 == 2);
+	check!(2 == 3);
+}
+
+#[test]
+fn buffer_output_flushed_on_panic() {
+	let result = std::panic::catch_unwind(|| {
+		let _buffer = assert2::buffer_output();
+		check!(1 == 2);
+	});
+	assert!(result.is_err());
+}
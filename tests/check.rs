@@ -4,6 +4,8 @@
 use assert2::assert;
 use assert2::check;
 use assert2::debug_assert;
+use assert2::let_assert;
+use assert2::try_check;
 
 #[test]
 fn check_pass() {
@@ -174,3 +176,587 @@ test_panic!(panic_assert3, assert!(true && false));
 test_panic!(panic_assert4, assert!(true && false, "{}", "logic broke"));
 test_panic!(panic_assert5, assert!(let Ok(_) = Result::<i32, i32>::Err(10)));
 test_panic!(panic_assert6, assert!(let Ok(_) = Result::<i32, i32>::Err(10), "{}", "rust broke"));
+
+#[test]
+fn check_level_pass() {
+	check!(1 == 1; level = warn);
+	check!(1 == 1; level = error);
+	check!(1 == 1; level = fatal);
+	check!(1 == 1, "{}", "math broke"; level = fatal);
+	check!(1 == 1, "{}", "math broke",; level = fatal);
+	check!(let Ok(10) = Result::<i32, i32>::Ok(10); level = fatal,);
+}
+
+#[test]
+fn check_level_warn() {
+	check!(1 == 2; level = warn);
+	check!(true && false, "{}", "logic broke"; level = warn);
+	check!(let Ok(_) = Result::<i32, i32>::Err(10); level = warn);
+}
+
+#[test]
+#[should_panic(expected = "check failed")]
+fn check_level_error() {
+	check!(1 == 2; level = error);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed")]
+fn check_level_fatal() {
+	check!(1 == 2, "{}", "math broke"; level = fatal);
+	unreachable!();
+}
+
+#[test]
+fn byte_buffers() {
+	let left = b"hello\xffworld".to_vec();
+	let failure = assert2::try_check!(left == b"hello world").unwrap_err();
+	let report = failure.report();
+	assert!(report.contains("with hexdump:\n"));
+	assert!(report.contains("  00000000  68 65 6c 6c 6f ff 77 6f  hello.wo  68 65 6c 6c 6f 20 77 6f  hello wo\n"));
+	assert!(report.contains("with lossy UTF-8:\n  \"hello\u{fffd}world\" == \"hello world\""));
+
+	let failure = assert2::try_check!(left == b"hello world"; format = debug).unwrap_err();
+	assert!(!failure.report().contains("hexdump"));
+
+	let numbers = vec![1, 2, 3];
+	let failure = assert2::try_check!(numbers == [1, 4, 3]).unwrap_err();
+	assert!(!failure.report().contains("hexdump"));
+	assert!(std::mem::size_of_val(&numbers[0]) == 4);
+}
+
+#[test]
+fn long_periodic_values() {
+	let left = vec![0u32; 10000];
+	let mut right = left.clone();
+	right[9000] = 1;
+	let failure = assert2::try_check!(left == right).unwrap_err();
+	assert!(!failure.report().contains("repeating output truncated"));
+	assert!(!failure.report().contains("Debug output of left and right is identical"));
+	assert!(failure.report().contains("<     0,\n>     1,\n"));
+}
+
+#[test]
+fn identical_debug_output() {
+	#[derive(Debug)]
+	struct Never;
+	impl PartialEq for Never {
+		fn eq(&self, _other: &Self) -> bool {
+			false
+		}
+	}
+
+	let never = Never;
+	let reference = &never;
+	let failure = assert2::try_check!(reference == reference).unwrap_err();
+	let address = format!("{:p}", reference);
+	assert!(failure.report().contains("Debug output of left and right is identical!"));
+	assert!(failure.report().contains("  left type:  &check::identical_debug_output::Never\n"));
+	assert!(failure.report().contains(&format!("  left address:  {address}\n  right address: {address}\n")));
+	assert!(failure.report().contains("hint: both sides are the same value"));
+
+	let failure = assert2::try_check!(never == Never).unwrap_err();
+	assert!(!failure.report().contains("address"));
+	assert!(failure.report().contains("hint: the values may differ in state"));
+
+	let failure = assert2::try_check!(f64::NAN == f64::NAN).unwrap_err();
+	assert!(failure.report().contains("hint: `NaN` is not equal to anything"));
+}
+
+#[test]
+#[should_panic(expected = "check failed: check!( 1 == 2 ) at tests/check.rs:")]
+fn check_panic_message() {
+	check!(1 == 2);
+}
+
+#[test]
+fn assert_panic_message() {
+	let result = std::panic::catch_unwind(|| assert2::silence(|| assert!(1 + 1 == 3, "math {}", "broke")));
+	let payload = result.unwrap_err();
+	let message = payload.downcast_ref::<String>().unwrap();
+	assert!(message.starts_with("assertion failed: assert!( 1 + 1 == 3 ) at tests/check.rs:"));
+	assert!(message.ends_with(": math broke"));
+}
+
+#[test]
+fn checkpoint_pass() {
+	check!(1 == 1);
+	assert2::checkpoint!();
+	assert2::checkpoint!("{}", "no checks failed");
+}
+
+#[test]
+#[should_panic(expected = "2 checks failed so far")]
+fn checkpoint_failed() {
+	check!(1 == 2);
+	check!(let Some(_) = None::<i32>);
+	check!(2 == 2);
+	assert2::checkpoint!();
+	unreachable!();
+}
+
+#[test]
+#[should_panic(expected = "1 check failed so far: math broke")]
+fn checkpoint_failed_message() {
+	assert2::let_check!(Some(x) = None::<i32>, "{}", "option broke" => {
+		check!(x == 1);
+	});
+	assert2::checkpoint!("{} broke", "math");
+}
+
+#[test]
+fn checkpoint_after_unwind() {
+	let result = std::panic::catch_unwind(|| {
+		check!(1 == 2);
+		assert2::checkpoint!();
+	});
+	assert!(result.is_err());
+	assert2::checkpoint!();
+}
+
+#[derive(Debug)]
+struct Token {
+	value: String,
+}
+
+impl Token {
+	fn is_valid(&self) -> bool {
+		!self.value.is_empty()
+	}
+}
+
+fn parse(token: Token) -> Result<usize, String> {
+	token.value.parse().map_err(|_| token.value)
+}
+
+/// A value that counts how often it is formatted with `Debug`.
+struct Counted<'a>(&'a std::cell::Cell<usize>);
+
+impl std::fmt::Debug for Counted<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		self.0.set(self.0.get() + 1);
+		f.write_str("Counted")
+	}
+}
+
+#[test]
+fn check_let_chain_pass() {
+	let token = Some(Token { value: "10".into() });
+	check!(let Some(x) = token && x.is_valid() && let Ok(y) = parse(x) && y == 10);
+	assert!(let (Some(a), b) = (Some(1), 2) && a < b);
+	assert!(true && let Some(_) = Some(1), "{}", "chains broke");
+}
+
+#[test]
+fn let_chain_pass_does_not_format() {
+	let count = std::cell::Cell::new(0);
+	check!(let Some(x) = Some(Counted(&count)) && let Some(_) = Some(&x) && true);
+	assert!(count.get() == 0);
+
+	let_assert!(Err(failure) = try_check!(let Some(x) = Some(Counted(&count)) && let None = Some(&x)));
+	assert!(failure.report().contains("x = Counted"));
+}
+
+#[test]
+fn assert_let_bindings() {
+	let value: Result<(i32, String), ()> = Ok((10, String::from("foo")));
+	let () = assert!(let Ok(_) = &value);
+	let number: &i32 = assert!(let Ok((number, _)) = &value);
+	assert!(*number == 10);
+	let (number, name) = assert!(let Ok((number, name)) = &value, "{}", "bindings broke");
+	assert!(*number == 10);
+	assert!(name == "foo");
+	assert!(assert!(let Some(x) = Some(5)) == &5);
+	let number: &i32 = assert!(let Ok((number, _)) | Err(number) = &Ok::<_, i32>((10, ())));
+	assert!(*number == 10);
+	drop(value);
+}
+
+test_panic!(panic_assert_let_bindings, {
+	let _x: &i32 = assert!(let Some(x) = None::<i32>);
+});
+
+test_panic!(panic_let_chain1, check!(let Some(x) = Some(Token { value: "".into() }) && x.is_valid()));
+test_panic!(panic_let_chain2, assert!(let Some(x) = Some(Token { value: "a".into() }) && x.is_valid() && let Ok(_) = parse(x)));
+test_panic!(panic_let_chain3, assert!(let Some(x) = Some(1) && let None = Some(x), "{}", "chains broke"));
+test_panic!(panic_let_chain4, assert!(false && let Some(_) = Some(1)));
+
+struct Opaque(i32);
+
+impl std::fmt::Debug for Opaque {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("Opaque { .. }")
+	}
+}
+
+impl PartialEq for Opaque {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+#[test]
+fn comparison_chain_pass() {
+	let x = 5;
+	check!(0 < x <= 5);
+	check!(1 <= 2 < 3 == 3 != 4, "{}", "math broke");
+	assert!(x - 1 < x < x + 1);
+	assert!("a" < "b" < "c",);
+	let_assert!(Ok(()) = try_check!(0 < x < 10));
+}
+
+#[test]
+fn comparison_chain_evaluates_once() {
+	let mut calls = 0;
+	let mut next = || {
+		calls += 1;
+		calls
+	};
+	assert!(0 < next() < 10);
+	assert!(calls == 1);
+}
+
+#[test]
+fn comparison_chain_fail() {
+	let x = 10;
+	let_assert!(Err(failure) = try_check!(0 < x < 5 < 20, "{} out of range", x));
+	assert!(failure.report().contains("with operands:\n  0 = 0\n  x = 10\n  5 = 5\n  20 = 20\n"));
+	assert!(failure.report().contains("failed comparison:\n  x < 5\n"));
+	assert!(failure.message() == Some("10 out of range"));
+}
+
+test_panic!(panic_comparison_chain, assert!(1 < 3 <= 2));
+
+#[test]
+fn disjunction_pass() {
+	let x = 5;
+	check!(x == 1 || x == 5);
+	check!(x < 0 || x > 2 || false, "{}", "math broke");
+	assert!(false || x == 5);
+
+	// Later alternatives are not evaluated if an earlier one holds.
+	let mut evaluated = false;
+	assert!(x == 5 || { evaluated = true; false });
+	assert!(!evaluated);
+}
+
+#[test]
+fn disjunction_mutating_alternative() {
+	struct Machine {
+		state: i32,
+	}
+
+	impl Machine {
+		fn advance(&mut self) -> i32 {
+			self.state += 1;
+			self.state
+		}
+	}
+
+	let mut machine = Machine { state: 1 };
+	assert!(machine.state == 2 || machine.advance() == 2);
+	assert!(machine.state == 2);
+
+	let_assert!(Err(failure) = try_check!(machine.state == 5 || machine.advance() == 5));
+	let report = failure.report();
+	assert!(report.contains("alternative 1 of 2:\n  machine.state == 5\nwith expansion:\n  3 == 5\n"));
+	assert!(report.contains("alternative 2 of 2:\n  machine.advance() == 5\nwith expansion:\n  false"));
+}
+
+#[test]
+fn disjunction_fail() {
+	let x: i32 = 5;
+	let_assert!(Err(failure) = try_check!(x == 1 || x > 10 || x.is_negative()));
+	let report = failure.report();
+	assert!(report.contains("alternative 1 of 3:\n  x == 1\nwith expansion:\n  5 == 1\n"));
+	assert!(report.contains("alternative 2 of 3:\n  x > 10\nwith expansion:\n  5 > 10\n"));
+	assert!(report.contains("alternative 3 of 3:\n  x.is_negative()\nwith expansion:\n  false"));
+}
+
+test_panic!(panic_disjunction, assert!(1 == 2 || 3 < 2, "{}", "logic broke"));
+
+#[test]
+fn negation_pass() {
+	let set: std::collections::BTreeSet<i32> = [1, 2, 3].into();
+	let x = 4;
+	check!(!set.contains(&x));
+	check!(!(x == 5), "{}", "math broke");
+	assert!(!set.is_empty());
+	assert!(!std::convert::identity(false));
+
+	// Calls that may move their operands are checked without printing the operands.
+	let value = "false".parse::<bool>();
+	let name = String::from("foo");
+	assert!(!value.unwrap());
+	assert!(!name.into_bytes().is_empty());
+}
+
+#[test]
+fn negation_fail() {
+	let set: std::collections::BTreeSet<i32> = [1, 2, 3].into();
+	let x = 2;
+	let_assert!(Err(failure) = try_check!(!set.contains(&x)));
+	assert!(failure.report().contains("with operands:\n  set = {1, 2, 3}\n"));
+	assert!(failure.report().contains(" = 2\nwith expansion:\n  !true"));
+
+	let_assert!(Err(failure) = try_check!(!(x + 1 > 2)));
+	assert!(failure.report().contains("with operands:\n  x + 1 = 3\n  2 = 2\n"));
+}
+
+#[test]
+fn negation_pass_does_not_format() {
+	fn is_counted(_: &Counted, _: &Counted) -> bool {
+		false
+	}
+
+	let count = std::cell::Cell::new(0);
+	let value = Some(Counted(&count));
+	let counted = Counted(&count);
+	check!(!value.is_none());
+	check!(!is_counted(&counted, &counted));
+	assert!(count.get() == 0);
+
+	let_assert!(Err(_) = try_check!(!value.is_some()));
+	assert!(count.get() == 1);
+}
+
+test_panic!(panic_negation, assert!(!Vec::<i32>::new().is_empty()));
+test_panic!(panic_negation_call, {
+	let v = vec![1, 2];
+	assert!(!v.contains(&1), "{}", "should not contain 1");
+});
+
+#[test]
+fn matches_pass() {
+	let value = Some(String::from("foo"));
+	check!(matches!(value, Some(_)));
+	check!(std::matches!(value, Some(ref x) if x.len() == 3), "{}", "rust broke");
+	assert!(core::matches!(&value, Some(x) if x == "foo",));
+	assert!(matches!(1 + 1, 1 | 2));
+
+	// The value is not moved by a failed match.
+	let_assert!(Err(_) = try_check!(matches!(value, None)));
+	assert!(value.is_some());
+}
+
+#[test]
+fn matches_fail() {
+	let value = Some(3);
+	let_assert!(Err(failure) = try_check!(matches!(value, None)));
+	assert!(failure.report().contains("let None = value"));
+	assert!(failure.report().contains("with expansion:\n  Some(3)"));
+
+	let_assert!(Err(failure) = try_check!(matches!(value, Some(x) if x > 5)));
+	assert!(failure.report().contains("with expansion:\n  Some(3)"));
+}
+
+#[test]
+fn let_fail_actual_variant() {
+	let value: Result<i32, String> = Err("boom".into());
+	let_assert!(Err(failure) = try_check!(let Ok(_) = value));
+	assert!(failure.report().contains("actual variant: Err\nwith expansion:\n  Err(\"boom\")"));
+
+	// The variant is not repeated if only the payload does not match.
+	let_assert!(Err(failure) = try_check!(let Err(1) = Err::<i32, i32>(2)));
+	assert!(!failure.report().contains("actual variant"));
+
+	let_assert!(Err(failure) = try_check!(matches!(Some(3), None)));
+	assert!(failure.report().contains("actual variant: Some\n"));
+	assert!(!failure.report().contains("did you mean"));
+}
+
+#[test]
+fn let_fail_similar_variant() {
+	#[derive(Debug)]
+	enum State {
+		Connected,
+		Disconnected,
+	}
+	let_assert!(Err(failure) = try_check!(let State::Connected = State::Disconnected));
+	assert!(failure.report().contains("actual variant: Disconnected\nhint: did you mean Disconnected instead of Connected?\n"));
+	assert!(let State::Connected = State::Connected);
+}
+
+#[test]
+fn display_fallback() {
+	#[derive(PartialEq)]
+	struct Version(u32, u32);
+
+	impl std::fmt::Display for Version {
+		fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+			write!(f, "v{}.{}", self.0, self.1)
+		}
+	}
+
+	#[derive(PartialEq)]
+	struct Opaque;
+
+	let_assert!(Err(failure) = try_check!(Version(1, 2) == Version(1, 3)));
+	assert!(failure.report().contains("with expansion:\n  v1.2 == v1.3"));
+
+	let_assert!(Err(failure) = try_check!(Opaque != Opaque));
+	assert!(failure.report().contains("<object of type check::display_fallback::Opaque>"));
+
+	// Types that implement `Debug` still use it.
+	let_assert!(Err(failure) = try_check!(String::from("a") == "b"));
+	assert!(failure.report().contains("\"a\" == \"b\""));
+}
+
+test_panic!(panic_matches, assert!(matches!(Err::<i32, i32>(1), Ok(_)), "{}", "expected ok"));
+
+#[test]
+fn method_call_pass() {
+	let result: Result<i32, String> = Ok(1);
+	check!(result.is_ok());
+	check!(Some(1).is_some(), "{}", "rust broke");
+	assert!(None::<i32>.is_none());
+	assert!("x".parse::<i32>().is_err(),);
+
+	// The receiver is only borrowed.
+	assert!(result.is_ok());
+	assert!(result == Ok(1));
+}
+
+#[test]
+fn method_call_fail() {
+	let result: Result<i32, String> = Err("boom".into());
+	let_assert!(Err(failure) = try_check!(result.is_ok()));
+	assert!(failure.report().contains("with receiver:\n  Err(\"boom\")"));
+
+	let_assert!(Err(failure) = try_check!(Some(5).is_none()));
+	assert!(failure.report().contains("with receiver:\n  Some(5)"));
+}
+
+#[test]
+fn method_call_args_pass() {
+	let text = String::from("Hello world");
+	let list = vec![1, 2, 3];
+	let needle = "world";
+	check!(text.contains("world"));
+	check!(text.contains(&needle), "{}", "rust broke");
+	check!(list.contains(&2));
+	assert!(text.starts_with('H'));
+	assert!(text.ends_with(&needle));
+	assert!(text.eq_ignore_ascii_case("HELLO WORLD"));
+	assert!(list.starts_with(&[1, 2]));
+}
+
+#[test]
+fn method_call_args_fail() {
+	let text = String::from("Hello world");
+	let_assert!(Err(failure) = try_check!(text.contains("moon")));
+	assert!(failure.report().contains("with receiver:\n  \"Hello world\"\nwith argument:\n  \"moon\""));
+
+	let list = vec![1, 2, 3];
+	let_assert!(Err(failure) = try_check!(list.contains(&4)));
+	assert!(failure.report().contains("with receiver:\n  [1, 2, 3]\nwith argument:\n  4"));
+}
+
+test_panic!(panic_method_call_args, assert!("foo".starts_with("bar")));
+test_panic!(panic_method_call, assert!("x".parse::<i32>().is_ok(), "{}", "should parse"));
+
+#[test]
+fn arithmetic_values_pass() {
+	let a = 1;
+	let b = 2;
+	let c = 3;
+	check!(a + b == c);
+	assert!((a + b) * c == 9, "{}", "math broke");
+
+	// Operands that do not implement `Copy` may be moved by the arithmetic.
+	let name = String::from("foo");
+	assert!(name + "bar" == "foobar");
+}
+
+#[test]
+fn arithmetic_values_fail() {
+	let a = 1;
+	let b = 2;
+	let c = 3;
+	let name = String::from("foo");
+	let_assert!(Err(failure) = try_check!(a + b == c * 2));
+	assert!(failure.report().contains("with values:\n  a = 1\n  b = 2\n  c = 3"));
+
+	let_assert!(Err(failure) = try_check!(name + "bar" == "foo"));
+	assert!(!failure.report().contains("with values:"));
+}
+
+test_panic!(panic_arithmetic_values, {
+	let x = 4;
+	let y = -1;
+	assert!(x * x - y == 15, "{}", "math broke");
+});
+
+#[test]
+fn check_expand_pass() {
+	let left = Opaque(1);
+	let right = Opaque(1);
+	check!(left == right; expand_left = left.0);
+	assert!(left == right, "{}", left.0; expand_left = left.0, expand_right = format!("Opaque({})", right.0));
+	check!(1 < 2; expand_right = "two", level = fatal);
+}
+
+#[test]
+fn check_expand_fail() {
+	let left = Opaque(1);
+	let right = Opaque(2);
+	let_assert!(Err(failure) = try_check!(left == right, "{} != {}", left.0, right.0; expand_left = left.0, expand_right = format!("Opaque({})", right.0)));
+	assert!(failure.report().contains("with expansion:\n  1 == Opaque(2)\n"));
+	assert!(failure.message() == Some("1 != 2"));
+}
+
+#[test]
+fn check_format_display() {
+	let left = std::net::Ipv4Addr::new(10, 0, 0, 1);
+	let right = std::net::Ipv4Addr::new(10, 0, 0, 2);
+	check!(left != right; format = display, level = warn);
+
+	let_assert!(Err(failure) = try_check!(left == right; format = display));
+	assert!(failure.report().contains("with expansion:\n  10.0.0.1 == 10.0.0.2\n"));
+
+	let_assert!(Err(failure) = try_check!("a" == "b"; format = display));
+	assert!(failure.report().contains("with expansion:\n  a == b\n"));
+	let_assert!(Err(failure) = try_check!("a" == "b"; format = debug));
+	assert!(failure.report().contains("with expansion:\n  \"a\" == \"b\"\n"));
+
+	// Types without `Display` still use `Debug`.
+	let_assert!(Err(failure) = try_check!([1] == [2]; format = display));
+	assert!(failure.report().contains("with expansion:\n  [1] == [2]\n"));
+}
+
+#[test]
+fn check_format_hex() {
+	let flags = 0x41u8;
+	let_assert!(Err(failure) = try_check!(flags & 0x0f == 0x02; format = hex));
+	assert!(failure.report().contains("with expansion:\n  0x01 == 0x02\n"));
+
+	let mask = 0x0100u16;
+	let_assert!(Err(failure) = try_check!(mask == 0x0200; format = binary));
+	assert!(failure.report().contains("with expansion:\n  0b0000000100000000 == 0b0000001000000000\n"));
+
+	let_assert!(Err(failure) = try_check!(&flags == &0x42; format = hex));
+	assert!(failure.report().contains("with expansion:\n  0x41 == 0x42\n"));
+	let_assert!(Err(failure) = try_check!(flags == 0x42; format = decimal));
+	assert!(failure.report().contains("with expansion:\n  65 == 66\n"));
+
+	// Other types are not affected.
+	let_assert!(Err(failure) = try_check!(Some(1) == Some(2); format = hex));
+	assert!(failure.report().contains("with expansion:\n  Some(1) == Some(2)\n"));
+}
+
+#[test]
+fn check_format_hex_bit_diff() {
+	let flags = 0x41u8;
+	let_assert!(Err(failure) = try_check!(flags == 0x03; format = hex));
+	assert!(failure.report().contains("with expansion:\n  0x41 == 0x03\nbits set only in left: 0x40\nbits set only in right: 0x02\n"));
+
+	let_assert!(Err(failure) = try_check!(-1i16 == 0x7fff; format = binary));
+	assert!(failure.report().contains("bits set only in left: 0b1000000000000000\n"));
+	assert!(!failure.report().contains("bits set only in right"));
+
+	// The bits are not shown in decimal mode or for other comparisons.
+	let_assert!(Err(failure) = try_check!(flags == 0x03));
+	assert!(!failure.report().contains("bits set only"));
+	let_assert!(Err(failure) = try_check!(flags < 0x03; format = hex));
+	assert!(!failure.report().contains("bits set only"));
+}
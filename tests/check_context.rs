@@ -0,0 +1,74 @@
+use assert2::{assert, check, check_ctx, CheckContext};
+
+#[test]
+fn context_pass() {
+	let mut ctx = CheckContext::new();
+	for i in 0..3 {
+		check_ctx!(ctx, i < 3);
+	}
+	assert!(ctx.is_ok());
+}
+
+#[test]
+fn context_collects_all_failures() {
+	let result = std::panic::catch_unwind(|| {
+		assert2::silence(|| {
+			let mut ctx = CheckContext::new();
+			for i in 0..6 {
+				check_ctx!(ctx, i % 2 == 0, "odd: {}", i);
+			}
+			assert!(ctx.failures().len() == 3);
+			assert!(assert2::__assert2_impl::pending_failures() == 0);
+		})
+	});
+	let message = result.unwrap_err().downcast::<String>().unwrap();
+	assert!(message.starts_with("3 checks failed in context created at tests/check_context.rs:"));
+	assert!(message.contains("\n  check_ctx!( i % 2 == 0 ) at tests/check_context.rs:"));
+	assert!(message.ends_with(": odd: 5"));
+	assert!(assert2::__assert2_impl::pending_failures() == 0);
+}
+
+#[test]
+fn context_by_reference() {
+	fn check_even(ctx: &mut CheckContext, value: i32) {
+		check_ctx!(ctx, value % 2 == 0);
+	}
+
+	let failures = assert2::silence(|| {
+		let mut ctx = CheckContext::new();
+		check_even(&mut ctx, 1);
+		check_even(&mut ctx, 2);
+		check_even(&mut ctx, 3);
+		ctx.into_failures()
+	});
+	assert!(failures.len() == 2);
+	check!(failures[0].expression() == "value % 2 == 0");
+	check!(failures[0].macro_name() == "check_ctx");
+	assert!(assert2::__assert2_impl::pending_failures() == 0);
+}
+
+#[test]
+fn context_try_check() {
+	let mut ctx = CheckContext::new();
+	if let Err(failure) = assert2::try_check!(1 + 1 == 3) {
+		ctx.add_failure(failure);
+	}
+	let failures = ctx.into_failures();
+	assert!(failures.len() == 1);
+	assert!(failures[0].expression() == "1 + 1 == 3");
+}
+
+#[test]
+fn context_moved_to_other_thread() {
+	let mut ctx = CheckContext::new();
+	assert2::silence(|| check_ctx!(ctx, 1 == 2));
+	let failures = std::thread::spawn(move || ctx.into_failures()).join().unwrap();
+	assert!(failures.len() == 1);
+
+	let mut ctx = CheckContext::new();
+	assert2::silence(|| check_ctx!(ctx, 1 == 2));
+	let result = std::thread::spawn(move || assert2::silence(|| drop(ctx))).join();
+	let message = result.unwrap_err().downcast::<String>().unwrap();
+	assert!(message.starts_with("1 check failed in context created at tests/check_context.rs:"));
+	assert!(assert2::__assert2_impl::pending_failures() == 0);
+}
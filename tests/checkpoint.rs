@@ -0,0 +1,23 @@
+use assert2::{assert, check, let_assert, InspectCheckpoint};
+
+#[test]
+fn checkpoint_pass() {
+	let items = [1, 2, 3, 4, 5, 6];
+	check!(items.iter().filter(|x| *x % 2 == 0).inspect_checkpoint("after filter").count() == 3);
+	assert!(items.iter().inspect_checkpoint("all").map(|x| x * 2).inspect_checkpoint("doubled").sum::<i32>() == 42);
+	let_assert!(Some(x) = items.iter().inspect_checkpoint("all").max());
+	assert!(*x == 6);
+}
+
+#[test]
+fn checkpoint_outside_assertion() {
+	let doubled: Vec<_> = [1, 2, 3].iter().inspect_checkpoint("unused").map(|x| x * 2).collect();
+	assert!(doubled == [2, 4, 6]);
+}
+
+#[test]
+#[should_panic]
+fn panic_checkpoint() {
+	let items = [1, 2, 3, 4, 5, 6];
+	assert!(items.iter().filter(|x| *x % 2 == 0).inspect_checkpoint("after filter").count() == 2);
+}
@@ -0,0 +1,19 @@
+use assert2::assert_completes_within;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A fake clock that advances one second every time it is read.
+fn fake_now() -> Instant {
+	static BASE: Mutex<Option<Instant>> = Mutex::new(None);
+	static TICKS: AtomicU64 = AtomicU64::new(0);
+	let base = *BASE.lock().unwrap().get_or_insert_with(Instant::now);
+	base + Duration::from_secs(TICKS.fetch_add(1, Ordering::Relaxed))
+}
+
+#[test]
+#[should_panic]
+fn panic_assert_completes_within_fake_clock() {
+	assert2::set_time_source(Some(fake_now));
+	assert_completes_within!(500ms, ());
+}
@@ -0,0 +1,16 @@
+use assert2::assert;
+use assert2::diff::SingleLineDiff;
+
+fn render() -> String {
+	let mut buffer = String::new();
+	SingleLineDiff::new("Hello world", "Hello moon").write_left(&mut buffer);
+	buffer
+}
+
+#[test]
+fn color_override() {
+	assert2::set_color_override(Some(true));
+	assert!(render().contains('\x1b'));
+	assert2::set_color_override(Some(false));
+	assert!(render() == "Hello world");
+}
@@ -0,0 +1,48 @@
+use assert2::{assert, const_assert};
+
+const fn checked_sub(a: u32, b: u32) -> u32 {
+	const_assert!(a >= b);
+	a - b
+}
+
+const fn first(values: &[u32]) -> u32 {
+	const_assert!(let [_, ..] = values, "no values");
+	values[0]
+}
+
+const fn non_zero(value: u32) -> u32 {
+	const_assert!(value != 0, "value must not be zero",);
+	value
+}
+
+const DIFFERENCE: u32 = checked_sub(10, 3);
+const FIRST: u32 = first(&[4, 5, 6]);
+
+const _: () = const_assert!(DIFFERENCE == 7);
+const _: () = const_assert!(let 4 = FIRST,);
+
+#[test]
+fn const_assert_pass() {
+	assert!(DIFFERENCE == 7);
+	assert!(FIRST == 4);
+	assert!(non_zero(1) == 1);
+	const_assert!(1 + 1 == 2);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: a >= b")]
+fn const_assert_fail() {
+	checked_sub(1, 2);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: value != 0: value must not be zero")]
+fn const_assert_fail_message() {
+	non_zero(0);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: let [_, ..] = values: no values")]
+fn const_assert_let_fail() {
+	first(&[]);
+}
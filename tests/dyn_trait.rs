@@ -0,0 +1,74 @@
+use assert2::{assert, check, impl_assert_dyn, AssertDyn, DynPartialEq};
+
+trait Plugin: AssertDyn + DynPartialEq {
+	fn name(&self) -> &str;
+}
+impl_assert_dyn!(dyn Plugin, PartialEq);
+
+trait Named: AssertDyn {
+	fn name(&self) -> &str;
+}
+impl_assert_dyn!(dyn Named, PartialEq = |a, b| a.name() == b.name());
+
+#[derive(Debug, PartialEq)]
+struct Echo {
+	prefix: &'static str,
+}
+
+#[derive(Debug, PartialEq)]
+struct Reverse;
+
+impl Plugin for Echo {
+	fn name(&self) -> &str {
+		"echo"
+	}
+}
+
+impl Plugin for Reverse {
+	fn name(&self) -> &str {
+		"reverse"
+	}
+}
+
+impl Named for Echo {
+	fn name(&self) -> &str {
+		"echo"
+	}
+}
+
+impl Named for Reverse {
+	fn name(&self) -> &str {
+		"echo"
+	}
+}
+
+#[test]
+fn dyn_partial_eq() {
+	let a: Box<dyn Plugin> = Box::new(Echo { prefix: "a" });
+	let b: Box<dyn Plugin> = Box::new(Echo { prefix: "a" });
+	check!(a == b);
+	check!(a.name() == "echo");
+	let c: Box<dyn Plugin> = Box::new(Reverse);
+	check!(a != c);
+}
+
+#[test]
+fn dyn_custom_comparator() {
+	let a: &dyn Named = &Echo { prefix: "a" };
+	let b: &dyn Named = &Reverse;
+	assert!(a == b);
+}
+
+#[test]
+fn dyn_debug_shows_concrete_type() {
+	let a: &dyn Plugin = &Echo { prefix: "a" };
+	assert!(format!("{a:?}") == "<dyn_trait::Echo> Echo { prefix: \"a\" }");
+}
+
+#[test]
+#[should_panic]
+fn panic_dyn_different_types() {
+	let a: Box<dyn Plugin> = Box::new(Echo { prefix: "a" });
+	let b: Box<dyn Plugin> = Box::new(Reverse);
+	assert!(a == b);
+}
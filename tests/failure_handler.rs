@@ -0,0 +1,26 @@
+use assert2::{assert, check};
+use std::sync::Mutex;
+
+static HANDLED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn handler(failure: &assert2::Failure) {
+	HANDLED.lock().unwrap().push(failure.expression().to_owned());
+}
+
+// The handler is global, so all checks are in a single test.
+#[test]
+fn failure_handler() {
+	assert2::set_failure_handler(Some(handler));
+
+	let result = std::panic::catch_unwind(|| assert!(1 + 1 == 3));
+	assert!(result.is_err());
+
+	check!(2 == 3; level = warn);
+	assert2::silence(|| {
+		check!(3 == 4; level = warn);
+	});
+	let _ = assert2::try_check!(4 == 5);
+
+	assert2::set_failure_handler(None);
+	assert!(*HANDLED.lock().unwrap() == ["1 + 1 == 3", "2 == 3"]);
+}
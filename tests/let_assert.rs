@@ -67,6 +67,16 @@ fn consume() {
 	assert!(x == "foo");
 }
 
+#[test]
+fn or_pattern_bindings() {
+	let_assert!(Ok(x) | Err(x) = Result::<i32, i32>::Err(3));
+	assert!(x == 3);
+	let_assert!(Some(Ok(x) | Err(x)) = Some(Ok::<i32, i32>(2)));
+	assert!(x == 2);
+	let_assert!(Some(Ok(x)) | Some(Err(x)) = Some(Err::<String, String>("foo".into())));
+	assert!(x == "foo");
+}
+
 macro_rules! test_panic {
 	($name:ident, $($expr:tt)*) => {
 		#[test]
@@ -83,3 +93,4 @@ test_panic!(
 	let_assert!(Ok(_x) = Result::<i32, i32>::Err(10), "{}", "rust broke")
 );
 test_panic!(panic_let_assert_no_capture, let_assert!(None = Some(10)));
+test_panic!(panic_let_assert_or_pattern, let_assert!(Some(Ok(_x)) | Some(Err(_x)) = None::<Result<i32, i32>>));
@@ -0,0 +1,47 @@
+use assert2::{assert, check, let_check};
+
+#[test]
+fn let_check_pass() {
+	let mut ran = 0;
+	let_check!(Some(x) = Some(10) => {
+		check!(x == 10);
+		ran += 1;
+	});
+	let_check!(Ok(x) = Result::<i32, i32>::Ok(10), "{}", "rust broke" => {
+		check!(x == 10);
+		ran += 1;
+	});
+	let_check!(Some(_) | None = Some(1), "{}", "rust broke", => {
+		ran += 1;
+	});
+	let_check!(Some(Ok(x)) | Some(Err(x)) = Some(Ok::<i32, i32>(10)) => {
+		check!(x == 10);
+		ran += 1;
+	});
+	assert!(ran == 4);
+}
+
+#[test]
+#[should_panic(expected = "check failed")]
+fn let_check_fail_skips_block() {
+	let mut ran = false;
+	let_check!(Some(_) = None::<i32> => {
+		ran = true;
+	});
+	assert!(!ran);
+}
+
+#[test]
+#[should_panic(expected = "check failed")]
+fn let_check_fail_continues() {
+	let mut ran = 0;
+	let_check!(Ok(x) = Result::<i32, i32>::Err(10), "{}", "rust broke" => {
+		check!(x == 10);
+	});
+	ran += 1;
+	let_check!(Some(x) = Some(10) => {
+		check!(x == 10);
+		ran += 1;
+	});
+	assert!(ran == 2);
+}
@@ -0,0 +1,28 @@
+use assert2::{assert, check};
+
+struct AssertOnDrop;
+
+impl Drop for AssertOnDrop {
+	fn drop(&mut self) {
+		assert!(1 == 2);
+		check!(1 == 2);
+	}
+}
+
+#[test]
+fn assert_while_panicking_is_suppressed() {
+	let before = assert2::suppressed_failures();
+	let result = std::panic::catch_unwind(|| {
+		let _guard = AssertOnDrop;
+		panic!("original panic");
+	});
+	let payload = result.unwrap_err();
+	assert!(payload.downcast_ref::<&str>() == Some(&"original panic"));
+	assert!(assert2::suppressed_failures() >= before + 2);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed")]
+fn assert_in_drop_panics_when_not_panicking() {
+	let _guard = AssertOnDrop;
+}
@@ -0,0 +1,52 @@
+use assert2::{assert, check, let_assert, let_check, try_check};
+use assert2::{assert_eventually, assert_len, assert_set_eq, assert_starts_with};
+use std::num::ParseIntError;
+
+fn parse(input: &str) -> Result<i32, ParseIntError> {
+	input.parse()
+}
+
+fn parse_list(input: &str) -> Result<Vec<i32>, ParseIntError> {
+	input.split(',').map(parse).collect()
+}
+
+fn all_forms(input: &str) -> Result<(), ParseIntError> {
+	check!(parse(input)? == 1);
+	check!(parse(input)? == 1, "{}", parse(input)?);
+	assert!(parse(input)? == 1);
+	assert!(parse(input)? + 1 == 2);
+	assert!(0 < parse(input)? <= 1);
+	assert!(parse(input)? == 2 || parse(input)? == 1);
+	assert!(!(parse(input)? == 2));
+	assert!(Some(parse(input)?).is_some());
+	assert!(matches!(parse(input)?, 1));
+	assert!(let 1 = parse(input)?);
+	assert!(let Some(x) = Some(parse(input)?) && x == 1);
+	let_assert!(1 = parse(input)?);
+	let_check!(Some(x) = Some(parse(input)?) => {
+		assert!(x == 1);
+	});
+	let_assert!(Ok(()) = try_check!(parse(input)? == 1));
+	Ok(())
+}
+
+fn other_macros(input: &str) -> Result<(), ParseIntError> {
+	assert_len!(parse_list(input)?, 2);
+	assert_set_eq!(parse_list(input)?, [2, 1]);
+	assert_starts_with!(parse_list(input)?[0].to_string(), "1");
+	assert_eventually!(timeout = 10ms, parse_list(input)?.len() == 2);
+	Ok(())
+}
+
+#[test]
+fn question_mark_pass() {
+	assert!(let Ok(()) = all_forms("1"));
+	assert!(let Ok(()) = other_macros("1,2"));
+}
+
+#[test]
+fn question_mark_propagates_error() {
+	// The error is returned before the assertion is checked, so nothing fails.
+	assert!(let Err(_) = all_forms("x"));
+	assert!(let Err(_) = other_macros("1,x"));
+}
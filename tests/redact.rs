@@ -0,0 +1,34 @@
+use assert2::{assert, check, let_assert, try_check};
+
+#[derive(Debug, PartialEq)]
+struct Request {
+	path: &'static str,
+	authorization: &'static str,
+}
+
+// Redactors are global, so everything is tested in a single test to avoid interfering with other tests.
+#[test]
+fn redaction() {
+	assert2::redact_after("Bearer ");
+	assert2::add_redactor(|text| text.replace("hunter2", "<password>"));
+
+	let request = Request { path: "/", authorization: "Bearer abc.def" };
+	let expected = Request { path: "/index", authorization: "Bearer abc.def" };
+	let_assert!(Err(failure) = try_check!(request == expected, "password: {}", "hunter2"));
+	let report = failure.report();
+	assert!(!report.contains("abc.def"));
+	assert!(report.contains("authorization: \"Bearer <redacted>\""));
+	assert!(!report.contains("hunter2"));
+	assert!(failure.message() == Some("password: <password>"));
+
+	// The expression is redacted too.
+	let_assert!(Err(failure) = try_check!(request.path == "hunter2"));
+	assert!(failure.expression() == "request.path == \"<password>\"");
+
+	// Passing checks are not affected.
+	check!(request.authorization == "Bearer abc.def");
+
+	assert2::clear_redactors();
+	let_assert!(Err(failure) = try_check!(request.authorization == "Bearer xyz"));
+	assert!(failure.report().contains("Bearer abc.def"));
+}
@@ -0,0 +1,18 @@
+use assert2::{check, set_run_context};
+
+#[test]
+fn run_context_pass() {
+	set_run_context("seed", format_args!("{:#x}", 0xdeadbeefu32));
+	set_run_context("seed", 10);
+	set_run_context(String::from("iteration"), 3);
+	check!(1 == 1);
+	assert2::remove_run_context("iteration");
+	assert2::clear_run_context();
+}
+
+#[test]
+#[should_panic]
+fn panic_run_context() {
+	set_run_context("seed", format_args!("{:#x}", 0xdeadbeefu32));
+	check!(1 == 2);
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "serde")]
+
+use assert2::{assert, check, let_assert, try_check, AssertDiff};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Server {
+	host: String,
+	port: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Config {
+	name: String,
+	server: Server,
+	tags: Vec<String>,
+	mode: Mode,
+	retries: Option<Retries>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Retries(u32);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+enum Mode {
+	Off,
+	On { level: u8 },
+}
+
+fn config() -> Config {
+	Config {
+		name: "main".into(),
+		server: Server { host: "localhost".into(), port: 80 },
+		tags: vec!["a".into(), "b".into()],
+		mode: Mode::On { level: 1 },
+		retries: Some(Retries(3)),
+	}
+}
+
+#[test]
+fn serde_diff_pass() {
+	check!(config() == config());
+}
+
+#[test]
+fn serde_diff_fields() {
+	let mut other = config();
+	other.server.port = 8080;
+	other.mode = Mode::On { level: 2 };
+	other.retries = Some(Retries(4));
+	let_assert!(Err(failure) = try_check!(config() == other));
+	let report = failure.report();
+	assert!(report.contains("with field differences:\n"));
+	assert!(report.contains("  mode.On.level: 1 != 2\n"));
+	assert!(report.contains("  retries: 3 != 4\n"));
+	assert!(report.contains("  server.port: 80 != 8080\n"));
+	assert!(!report.contains("name"));
+}
+
+#[test]
+fn serde_diff_missing() {
+	let mut other = config();
+	other.tags.push("c".into());
+	other.mode = Mode::Off;
+	other.retries = None;
+	let_assert!(Err(failure) = try_check!(config() == other));
+	let report = failure.report();
+	assert!(report.contains("  tags[2]: <missing> != \"c\"\n"));
+	assert!(report.contains("  mode: {On: {level: 1}} != \"Off\"\n"));
+	assert!(report.contains("  retries: Some(3) != None\n"));
+
+	let left = BTreeMap::from([(1, "one"), (2, "two")]);
+	let right = BTreeMap::from([(2, "deux"), (3, "three")]);
+	let_assert!(Err(failure) = try_check!(left == right));
+	let report = failure.report();
+	assert!(report.contains("  [1]: \"one\" != <missing>\n"));
+	assert!(report.contains("  [2]: \"two\" != \"deux\"\n"));
+	assert!(report.contains("  [3]: <missing> != \"three\"\n"));
+}
+
+#[test]
+fn serde_diff_whole_value() {
+	// Values that differ as a whole use the normal expansion.
+	let_assert!(Err(failure) = try_check!(1 == 2));
+	assert!(failure.report().contains("with expansion:\n  1 == 2"));
+	let_assert!(Err(failure) = try_check!(Some(1) == None));
+	assert!(!failure.report().contains("with field differences:"));
+
+	// Sequences of scalars are not structs or maps, so they use the normal expansion.
+	let_assert!(Err(failure) = try_check!(vec![1, 2] == vec![1, 3]));
+	assert!(failure.report().contains("with expansion:\n  [1, 2] == [1, 3]"));
+	let_assert!(Err(failure) = try_check!(vec![b'a'] == vec![b'b']));
+	assert!(failure.report().contains("with hexdump:\n"));
+}
+
+#[test]
+fn serde_diff_operand_options() {
+	// An explicit format is not ignored in favor of the field differences.
+	let mut other = config();
+	other.server.port = 8080;
+	let_assert!(Err(failure) = try_check!(config() == other; format = debug));
+	assert!(!failure.report().contains("with field differences:"));
+	let_assert!(Err(failure) = try_check!(vec![16u32, 32] == vec![16u32, 33]; format = hex));
+	assert!(!failure.report().contains("with field differences:"));
+}
+
+#[derive(Debug, PartialEq, Serialize, AssertDiff)]
+struct Both {
+	value: u32,
+}
+
+#[test]
+fn serde_diff_prefers_structural_diff() {
+	let_assert!(Err(failure) = try_check!(Both { value: 1 } == Both { value: 2 }));
+	assert!(failure.report().contains("with field differences:\n  value: 1 != 2"));
+}
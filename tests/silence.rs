@@ -0,0 +1,31 @@
+use assert2::{assert, check, try_check};
+
+#[test]
+fn silence_pass() {
+	let value = assert2::silence(|| {
+		check!(1 == 1);
+		10
+	});
+	assert!(value == 10);
+}
+
+#[test]
+fn silence_still_fails() {
+	let result = std::panic::catch_unwind(|| {
+		assert2::silence(|| assert!(1 == 2));
+	});
+	assert!(result.is_err());
+
+	let result = std::panic::catch_unwind(|| {
+		assert2::silence(|| {
+			check!(1 == 2);
+		});
+	});
+	assert!(result.is_err());
+}
+
+#[test]
+fn silence_nested() {
+	let result = assert2::silence(|| assert2::silence(|| try_check!(1 == 2)));
+	assert!(result.is_err());
+}
@@ -0,0 +1,76 @@
+use assert2::{assert, check, let_assert, let_check, try_check};
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+enum Shape {
+	Circle { radius: u32 },
+	Square(u32),
+}
+
+#[test]
+fn let_assert_through_pointers() {
+	let boxed = Box::new(Some(Shape::Circle { radius: 2 }));
+	let_assert!(Some(Shape::Circle { radius }) = &*boxed);
+	assert!(*radius == 2);
+
+	let rc = Rc::new(Shape::Square(3));
+	let_assert!(Shape::Square(side) = &*rc);
+	assert!(*side == 3);
+
+	let arc = Arc::new(Some(String::from("hello")));
+	let_assert!(Some(text) = &*arc);
+	assert!(text == "hello");
+
+	// The pointers are only borrowed.
+	assert!(Rc::strong_count(&rc) == 1);
+	assert!(boxed.is_some());
+
+	// A temporary pointer lives as long as the bindings.
+	let_assert!(Some(value) = &*Arc::new(Some(5)));
+	assert!(*value == 5);
+}
+
+#[test]
+fn let_assert_move_out_of_box() {
+	let boxed = Box::new(Ok::<_, String>(vec![1, 2]));
+	let_assert!(Ok(list) = *boxed);
+	assert!(list == [1, 2]);
+}
+
+#[test]
+fn let_assert_as_deref() {
+	let name: Option<Rc<str>> = Some("name".into());
+	let_assert!(Some("name") = name.as_deref());
+	let list: Option<Box<[i32]>> = Some(vec![1, 2, 3].into());
+	let_assert!(Some([first, .., last]) = list.as_deref());
+	assert!((*first, *last) == (1, 3));
+}
+
+#[test]
+fn assert_let_through_pointers() {
+	let arc = Arc::new(Some(String::from("hello")));
+	check!(let Some(_) = &*arc);
+	let text = assert!(let Some(text) = &*arc);
+	assert!(text == "hello");
+
+	let rc = Rc::new(Shape::Square(3));
+	let_check!(Shape::Square(side) = &*rc => {
+		check!(*side == 3);
+	});
+	assert!(let Shape::Square(3) = *rc);
+}
+
+#[test]
+fn let_fail_through_pointer() {
+	let rc = Rc::new(Shape::Square(3));
+	let_assert!(Err(failure) = try_check!(let Shape::Circle { .. } = &*rc));
+	assert!(failure.report().contains("actual variant: Square\nwith expansion:\n  Square(3)"));
+}
+
+#[test]
+#[should_panic]
+fn panic_let_assert_through_pointer() {
+	let arc = Arc::new(None::<i32>);
+	let_assert!(Some(_x) = &*arc);
+}
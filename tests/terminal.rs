@@ -0,0 +1,8 @@
+use assert2::{assert_bytes_eq, TerminalSize};
+
+#[test]
+#[should_panic]
+fn panic_assert_bytes_eq_wide_terminal() {
+	assert2::set_terminal_size_provider(Some(|| Some(TerminalSize { width: 200, height: 50 })));
+	assert_bytes_eq!([0u8; 64], [&[0u8; 40][..], &[1u8; 24][..]].concat());
+}
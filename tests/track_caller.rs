@@ -0,0 +1,26 @@
+use assert2::{assert, try_check};
+
+#[track_caller]
+fn check_positive(value: i32) -> Result<(), assert2::Failure> {
+	try_check!(value > 0)
+}
+
+fn check_negative(value: i32) -> Result<(), assert2::Failure> {
+	try_check!(value < 0)
+}
+
+#[test]
+fn track_caller_reports_caller_location() {
+	let failure = check_positive(-1).unwrap_err();
+	assert!(failure.file() == file!());
+	assert!(failure.line() == line!() - 2);
+	assert!(failure.report().contains(&format!("at {}:{}:", file!(), line!() - 3)));
+}
+
+#[test]
+fn without_track_caller_reports_macro_location() {
+	let failure = check_negative(1).unwrap_err();
+	assert!(failure.line() == 9);
+	// Like for panics, the compiler counts the leading tab as four columns.
+	assert!(failure.column() == 5);
+}
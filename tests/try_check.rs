@@ -0,0 +1,43 @@
+use assert2::{assert, try_assert, try_check, Failure};
+
+#[test]
+fn try_check_pass() {
+	assert!(let Ok(()) = try_check!(1 == 1));
+	assert!(let Ok(()) = try_check!(1 == 1, "{}", "math broke"));
+	assert!(let Ok(()) = try_check!(let Some(_) = Some(10)));
+}
+
+#[test]
+fn try_check_fail() {
+	assert!(let Err(_) = try_check!(1 + 1 == 3));
+	let failure = try_check!(1 + 1 == 3, "math is {}", "broken").unwrap_err();
+	assert!(failure.macro_name() == "try_check");
+	assert!(failure.file() == file!());
+	assert!(failure.line() == line!() - 3);
+	assert!(failure.message() == Some("math is broken"));
+	assert!(failure.report().contains("1 + 1 == 3"));
+	assert!(!failure.report().contains('\x1b'));
+
+	let failure = try_check!(let Some(_) = None::<i32>).unwrap_err();
+	assert!(failure.to_string().contains("None"));
+}
+
+#[derive(Debug)]
+struct MyError(#[allow(dead_code)] Failure);
+
+impl From<Failure> for MyError {
+	fn from(failure: Failure) -> Self {
+		Self(failure)
+	}
+}
+
+fn check_answer(answer: i32) -> Result<i32, MyError> {
+	try_assert!(answer == 42, "wrong answer");
+	Ok(answer)
+}
+
+#[test]
+fn try_assert_returns_error() {
+	assert!(let Ok(42) = check_answer(42));
+	assert!(let Err(MyError(_)) = check_answer(41));
+}